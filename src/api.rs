@@ -0,0 +1,95 @@
+//! Programmatic API for embedding cleen in other tools.
+//!
+//! The command functions under [`crate::commands`] are written for a human
+//! at a terminal: they print progress, prompt, and render hints. The
+//! functions here do the same work without touching stdout or stdin and
+//! return structured results, so editor extensions and build tools can
+//! drive cleen without scraping its output.
+
+use crate::commands::install;
+use crate::core::config::Config;
+pub use crate::core::report::{BuildReport, InstallReport};
+use crate::core::version::{VersionInfo, VersionManager};
+use crate::error::{CleenError, Result};
+use std::time::Instant;
+
+/// What to install and how.
+#[derive(Debug, Clone)]
+pub struct InstallOptions {
+    /// `"latest"`, `"0.30.109"`, or `"v0.30.109"`.
+    pub version: String,
+    /// Replace the version if it is already installed.
+    pub force: bool,
+    /// Keep the downloaded archive under `~/.cleen/cache/downloads` so a
+    /// later reinstall of the same version needs no network.
+    pub keep_archive: bool,
+}
+
+impl Default for InstallOptions {
+    fn default() -> Self {
+        Self {
+            version: "latest".to_string(),
+            force: false,
+            keep_archive: false,
+        }
+    }
+}
+
+/// Install a compiler version using the user's cleen configuration.
+///
+/// An already-installed version is not an error: the report comes back
+/// with `already_installed: true`. A version directory whose binary has gone
+/// missing is reinstalled in place.
+pub fn install(options: &InstallOptions) -> Result<InstallReport> {
+    let config = Config::load()?;
+    install_with_config(&config, options)
+}
+
+/// [`install`] against an explicit configuration, e.g. one rooted at a
+/// directory other than `~/.cleen`.
+pub fn install_with_config(config: &Config, options: &InstallOptions) -> Result<InstallReport> {
+    let started = Instant::now();
+    let silent = &mut |_: &str| {};
+    match install::install_compiler(
+        config,
+        &options.version,
+        options.force,
+        options.keep_archive,
+        silent,
+    ) {
+        Err(CleenError::VersionAlreadyInstalled { version }) => {
+            let binary_path = config.get_version_binary(&version);
+            if !binary_path.exists() {
+                return install::install_compiler(
+                    config,
+                    &version,
+                    true,
+                    options.keep_archive,
+                    silent,
+                );
+            }
+            Ok(InstallReport {
+                version,
+                binary_path,
+                already_installed: true,
+                from_archive_cache: false,
+                asset_name: None,
+                bytes: 0,
+                duration: started.elapsed(),
+                reinstalled: false,
+            })
+        }
+        other => other,
+    }
+}
+
+/// Installed compiler versions, oldest first.
+pub fn installed_versions() -> Result<Vec<VersionInfo>> {
+    let config = Config::load()?;
+    installed_versions_with_config(&config)
+}
+
+/// [`installed_versions`] against an explicit configuration.
+pub fn installed_versions_with_config(config: &Config) -> Result<Vec<VersionInfo>> {
+    VersionManager::new(config.clone()).list_installed_versions()
+}
@@ -2,12 +2,13 @@
 //!
 //! This is an alias for `cleen frame` commands, providing a shorter syntax:
 //!   frame build    → cleen frame build
-//!   frame scan     → cleen frame scan
+//!   frame scan     → cleen frame scan (alias: info)
 //!   frame new      → cleen frame new
 //!   frame serve    → cleen frame serve
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 // Use the cleen library
 use cleen::core::frame;
@@ -17,6 +18,10 @@ use cleen::core::frame;
 #[clap(about = "Clean Framework CLI - Build full-stack web applications")]
 #[clap(version = env!("CARGO_PKG_VERSION"))]
 pub struct Cli {
+    /// Print how long each phase (fetch, download, extraction, build...) took
+    #[clap(long, global = true)]
+    timings: bool,
+
     #[clap(subcommand)]
     command: Commands,
 }
@@ -31,10 +36,11 @@ enum Commands {
         #[clap(short, long, default_value = "api")]
         template: String,
         /// Port for development server (default: 3000)
-        #[clap(short, long, default_value = "3000")]
+        #[clap(short, long, default_value = "3000", value_parser = frame::parse_project_port)]
         port: u16,
     },
     /// Scan and discover project files (dry-run for build)
+    #[clap(visible_alias = "info")]
     Scan {
         /// Project directory to scan (default: current directory)
         #[clap(default_value = ".")]
@@ -42,6 +48,9 @@ enum Commands {
         /// Output format: text or json
         #[clap(short, long, default_value = "text")]
         format: String,
+        /// Shorthand for --format json
+        #[clap(long, conflicts_with = "format")]
+        json: bool,
         /// Show verbose output including file paths
         #[clap(short, long)]
         verbose: bool,
@@ -55,8 +64,23 @@ enum Commands {
         #[clap(short, long, default_value = "dist")]
         output: String,
         /// Optimization level: 0, 1, 2, 3, s, z (default: 2)
-        #[clap(short = 'O', long, default_value = "2")]
+        #[clap(short = 'O', long, default_value = "2", value_parser = frame::parse_opt_level)]
         optimize: String,
+        /// Build once per level (repeatable), keeping each as <name>.opt<level>.wasm
+        #[clap(long = "opt-level", conflicts_with = "optimize", value_parser = frame::parse_opt_level)]
+        opt_levels: Vec<String>,
+        /// Installed compiler version to use instead of the project's pin
+        #[clap(long)]
+        compiler: Option<String>,
+        /// Fail the build if frame-cli reports any warnings
+        #[clap(long)]
+        strict: bool,
+        /// Add the output directory to .gitignore if it isn't ignored yet
+        #[clap(long)]
+        update_gitignore: bool,
+        /// Delete the output directory before building
+        #[clap(long)]
+        clean: bool,
     },
     /// Start a development server for a Frame application
     Serve {
@@ -72,9 +96,34 @@ enum Commands {
         /// Enable debug output
         #[clap(short, long)]
         debug: bool,
+        /// Installed compiler version to use instead of the project's pin
+        #[clap(long)]
+        compiler: Option<String>,
+        /// Keep the compiled WASM at this path (default: a temp file)
+        #[clap(long)]
+        out: Option<PathBuf>,
+        /// Recompile and restart the server when source files change
+        #[clap(long)]
+        watch: bool,
+        /// Run the server in the background, logging to ~/.cleen/logs/frame-server.log
+        #[clap(long, conflicts_with = "watch")]
+        detach: bool,
+        /// Ask the runtime to record request timings (FRAME_PROFILE=1) and
+        /// summarize them when the server stops
+        #[clap(long, conflicts_with = "detach")]
+        profile: bool,
     },
     /// Stop a running Frame development server
     Stop,
+    /// Show the log of a server started with `serve --detach`
+    Logs {
+        /// Number of lines to show from the end of the log
+        #[clap(short = 'n', long, default_value = "50")]
+        lines: usize,
+        /// Keep printing new output as the server writes it
+        #[clap(short, long)]
+        follow: bool,
+    },
 }
 
 fn main() -> Result<()> {
@@ -83,6 +132,10 @@ fn main() -> Result<()> {
     // See main cleen binary for rationale.
     cleen::core::heartbeat::maybe_send_weekly();
 
+    if cli.timings {
+        cleen::utils::timing::enable();
+    }
+
     let result = match cli.command {
         Commands::New {
             name,
@@ -92,22 +145,71 @@ fn main() -> Result<()> {
         Commands::Scan {
             project,
             format,
+            json,
             verbose,
-        } => frame::scan_project(&project, &format, verbose).map_err(|e| anyhow::anyhow!(e)),
+        } => frame::scan_project(&project, if json { "json" } else { &format }, verbose)
+            .map_err(|e| anyhow::anyhow!(e)),
         Commands::Build {
             input,
             output,
             optimize,
-        } => frame::build_project(&input, &output, &optimize).map_err(|e| anyhow::anyhow!(e)),
+            opt_levels,
+            compiler,
+            strict,
+            update_gitignore,
+            clean,
+        } => {
+            let cleaned = if clean {
+                frame::clean_output(&input, &output)
+            } else {
+                Ok(())
+            };
+            cleaned
+                .and_then(|()| {
+                    let compiler = compiler.as_deref();
+                    if opt_levels.is_empty() {
+                        frame::build_project(&input, &output, &optimize, compiler)
+                            .and_then(|report| frame::finish_build(&report, strict))
+                    } else {
+                        frame::build_project_levels(&input, &output, &opt_levels, compiler)
+                            .and_then(|builds| frame::finish_level_builds(&builds, strict))
+                    }
+                })
+                .and_then(|()| frame::check_output_ignored(&input, &output, update_gitignore))
+                .map_err(|e| anyhow::anyhow!(e))
+        }
         Commands::Serve {
             input,
             port,
             host,
             debug,
-        } => frame::serve_application(&input, port, &host, debug).map_err(|e| anyhow::anyhow!(e)),
+            compiler,
+            out,
+            watch,
+            detach,
+            profile,
+        } => frame::serve_application(
+            &input,
+            port,
+            &host,
+            debug,
+            compiler.as_deref(),
+            out.as_deref(),
+            watch,
+            detach,
+            profile,
+        )
+        .map_err(|e| anyhow::anyhow!(e)),
         Commands::Stop => frame::stop_server().map_err(|e| anyhow::anyhow!(e)),
+        Commands::Logs { lines, follow } => {
+            frame::show_server_logs(lines, follow).map_err(|e| anyhow::anyhow!(e))
+        }
     };
 
+    if let Some(timings) = cleen::utils::timing::take() {
+        eprint!("{}", timings.report());
+    }
+
     if let Err(e) = result {
         eprintln!("Error: {e}");
         std::process::exit(1);
@@ -1,8 +1,8 @@
-use crate::core::{github::GitHubClient, version::normalize};
+use crate::core::{config::Config, github::GitHubClient, version::normalize};
 use anyhow::Result;
 
 pub fn list_available_versions() -> Result<()> {
-    let github_client = GitHubClient::new(None);
+    let github_client = GitHubClient::from_config(&Config::load()?);
 
     match github_client.get_releases("Ivan-Pasco", "clean-language-compiler") {
         Ok(releases) => {
@@ -1,5 +1,8 @@
 use crate::core::config::{read_active_version, Config};
+use crate::core::size_cache::SizeCache;
+use crate::core::version::{self, VersionMetadata};
 use crate::error::Result;
+use crate::utils::fs::format_size;
 use std::fs;
 use std::path::PathBuf;
 
@@ -10,8 +13,13 @@ pub struct CleanupCandidate {
     size_bytes: u64,
     is_active: bool,
     is_frame_dependency: bool,
+    is_recently_used: bool,
 }
 
+/// Versions activated or run within this many days are kept by cleanup
+/// even when they fall outside the most recent `keep_count`.
+const RECENTLY_USED_DAYS: i64 = 14;
+
 /// List versions that can be cleaned up
 pub fn list_cleanup_candidates(config: &Config) -> Result<Vec<CleanupCandidate>> {
     let versions_dir = config.get_versions_dir();
@@ -21,6 +29,7 @@ pub fn list_cleanup_candidates(config: &Config) -> Result<Vec<CleanupCandidate>>
         return Ok(candidates);
     }
 
+    let mut sizes = SizeCache::load(config);
     for entry in fs::read_dir(&versions_dir)? {
         let entry = entry?;
         let path = entry.path();
@@ -34,13 +43,13 @@ pub fn list_cleanup_candidates(config: &Config) -> Result<Vec<CleanupCandidate>>
             None => continue,
         };
 
-        // Skip the "frame" directory (it's for Frame CLI versions)
-        if version == "frame" {
+        // Skip Frame CLI versions and leftovers of interrupted installs
+        if !version::is_version_dir_name(&version) {
             continue;
         }
 
         // Calculate directory size
-        let size_bytes = calculate_dir_size(&path).unwrap_or(0);
+        let size_bytes = sizes.size_of(&path);
 
         // Check if this is the active version
         let is_active = config.active_version.as_ref() == Some(&version);
@@ -48,16 +57,31 @@ pub fn list_cleanup_candidates(config: &Config) -> Result<Vec<CleanupCandidate>>
         // Check if Frame CLI depends on this version
         let is_frame_dependency = check_frame_dependency(config, &version);
 
+        let last_used =
+            VersionMetadata::load(&path).last_used_at(&config.get_version_binary(&version));
+        let is_recently_used = last_used
+            .map(|t| chrono::Utc::now() - t < chrono::Duration::days(RECENTLY_USED_DAYS))
+            .unwrap_or(false);
+
         candidates.push(CleanupCandidate {
             version,
             size_bytes,
             is_active,
             is_frame_dependency,
+            is_recently_used,
         });
     }
 
-    // Sort by version (oldest first based on semantic version parsing)
-    candidates.sort_by(|a, b| compare_versions(&a.version, &b.version));
+    sizes.save();
+
+    // Sort by version (oldest first based on semantic version parsing).
+    // Nightly builds have no semver to compare, so they rank below every
+    // release and `--keep` counts releases only.
+    candidates.sort_by(|a, b| {
+        version::is_nightly(&b.version)
+            .cmp(&version::is_nightly(&a.version))
+            .then_with(|| compare_versions(&a.version, &b.version))
+    });
 
     Ok(candidates)
 }
@@ -87,24 +111,6 @@ fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
     std::cmp::Ordering::Equal
 }
 
-/// Calculate total size of a directory
-fn calculate_dir_size(path: &std::path::Path) -> Result<u64> {
-    let mut total = 0;
-
-    for entry in fs::read_dir(path)? {
-        let entry = entry?;
-        let metadata = entry.metadata()?;
-
-        if metadata.is_file() {
-            total += metadata.len();
-        } else if metadata.is_dir() {
-            total += calculate_dir_size(&entry.path())?;
-        }
-    }
-
-    Ok(total)
-}
-
 /// Check if Frame CLI depends on a compiler version
 fn check_frame_dependency(config: &Config, version: &str) -> bool {
     // Only the active compiler version is a Frame dependency
@@ -118,15 +124,16 @@ fn check_frame_dependency(config: &Config, version: &str) -> bool {
 }
 
 /// Count and total-size of compiler versions that `cleen cleanup` would
-/// consider removable: every installed version that is neither the active
-/// version nor the one the active Frame CLI depends on. Returns `None`
+/// consider removable: every installed version that is not the active
+/// version, not the one the active Frame CLI depends on, and not used in
+/// the last [`RECENTLY_USED_DAYS`] days. Returns `None`
 /// when nothing is removable so callers can skip the post-install hint
 /// entirely rather than printing a noisy "0 versions to clean" line.
 pub fn compiler_cleanup_summary(config: &Config) -> Option<(usize, u64)> {
     let candidates = list_cleanup_candidates(config).ok()?;
     let removable: Vec<_> = candidates
         .into_iter()
-        .filter(|c| !c.is_active && !c.is_frame_dependency)
+        .filter(|c| !c.is_active && !c.is_frame_dependency && !c.is_recently_used)
         .collect();
     if removable.is_empty() {
         return None;
@@ -147,6 +154,7 @@ pub fn plugin_cleanup_summary(config: &Config) -> Option<(usize, u64)> {
 
     let mut count = 0usize;
     let mut total = 0u64;
+    let mut sizes = SizeCache::load(config);
 
     let entries = fs::read_dir(&plugins_dir).ok()?;
     for plugin_entry in entries.flatten() {
@@ -186,9 +194,10 @@ pub fn plugin_cleanup_summary(config: &Config) -> Option<(usize, u64)> {
                 continue;
             }
             count += 1;
-            total += calculate_dir_size(version_path).unwrap_or(0);
+            total += sizes.size_of(version_path);
         }
     }
+    sizes.save();
 
     if count == 0 {
         None
@@ -229,7 +238,7 @@ pub fn graveyard_summary(config: &Config) -> (usize, u64) {
             }
             if name.contains(".locked-") {
                 // Root-level graveyard — already counted above; just add bytes.
-                bytes += calculate_dir_size(&path).unwrap_or(0);
+                bytes += crate::utils::fs::dir_size(&path);
                 continue;
             }
             // Walk version graveyards inside this plugin dir.
@@ -242,7 +251,7 @@ pub fn graveyard_summary(config: &Config) -> (usize, u64) {
                         .unwrap_or_default();
                     if sub_name.contains(".locked-") {
                         count += 1;
-                        bytes += calculate_dir_size(&sub.path()).unwrap_or(0);
+                        bytes += crate::utils::fs::dir_size(&sub.path());
                     }
                 }
             }
@@ -304,23 +313,6 @@ pub fn cleanup_graveyards_execute() -> Result<()> {
     Ok(())
 }
 
-/// Format bytes as human-readable size
-pub fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} bytes", bytes)
-    }
-}
-
 /// Run cleanup in dry-run mode (just show what would be removed)
 pub fn cleanup_dry_run(keep_count: usize) -> Result<()> {
     let config = Config::load()?;
@@ -334,7 +326,7 @@ pub fn cleanup_dry_run(keep_count: usize) -> Result<()> {
     // Separate protected and removable versions
     let (protected, removable): (Vec<_>, Vec<_>) = candidates
         .into_iter()
-        .partition(|c| c.is_active || c.is_frame_dependency);
+        .partition(|c| c.is_active || c.is_frame_dependency || c.is_recently_used);
 
     // Keep the most recent N versions from removable
     let to_keep = if removable.len() > keep_count {
@@ -362,6 +354,11 @@ pub fn cleanup_dry_run(keep_count: usize) -> Result<()> {
                 } else {
                     None
                 },
+                if c.is_recently_used {
+                    Some("used recently")
+                } else {
+                    None
+                },
             ]
             .into_iter()
             .flatten()
@@ -415,7 +412,7 @@ pub fn cleanup_execute(keep_count: usize) -> Result<()> {
     // Separate protected and removable versions
     let (protected, removable): (Vec<_>, Vec<_>) = candidates
         .into_iter()
-        .partition(|c| c.is_active || c.is_frame_dependency);
+        .partition(|c| c.is_active || c.is_frame_dependency || c.is_recently_used);
 
     // Keep the most recent N versions from removable
     let to_keep_versions: Vec<String> = if removable.len() > keep_count {
@@ -495,6 +492,7 @@ pub fn cleanup_plugins_dry_run() -> Result<()> {
 
     let mut total_removable = 0u64;
     let mut found_any = false;
+    let mut sizes = SizeCache::load(&config);
 
     for plugin_entry in fs::read_dir(&plugins_dir)? {
         let plugin_entry = plugin_entry?;
@@ -526,7 +524,7 @@ pub fn cleanup_plugins_dry_run() -> Result<()> {
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
 
-            let size = calculate_dir_size(&version_path).unwrap_or(0);
+            let size = sizes.size_of(&version_path);
             versions.push((version, size));
         }
 
@@ -547,6 +545,8 @@ pub fn cleanup_plugins_dry_run() -> Result<()> {
         }
     }
 
+    sizes.save();
+
     if !found_any {
         println!("No plugins with multiple versions found.");
     } else {
@@ -630,7 +630,7 @@ pub fn cleanup_plugins_with_config(config: &Config) -> Result<()> {
                 continue;
             }
 
-            let size = calculate_dir_size(version_path).unwrap_or(0);
+            let size = crate::utils::fs::dir_size(version_path);
 
             print!("  Removing {}/{}... ", plugin_name, version);
 
@@ -1,11 +1,22 @@
 use crate::core::{
-    compatibility, config::Config, frame, shim::ShimManager, version::VersionManager,
+    binaries, compatibility,
+    config::Config,
+    frame,
+    shim::{ShimManager, ShimTarget},
+    version::{normalize, VersionManager, VersionRange},
 };
 use crate::error::{CleenError, Result};
 use std::env;
+use std::ffi::OsStr;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-pub fn check_environment(check_frame: bool) -> Result<()> {
+/// Check the environment and report problems. With `fix`, repairs that need
+/// no decision (missing directories, missing or broken shims, the bin
+/// directory missing from the shell's PATH config) are made on the spot,
+/// and the summary counts only what is still wrong afterwards.
+pub fn check_environment(check_frame: bool, fix: bool) -> Result<()> {
     println!("🔍 Clean Language Manager - Environment Check");
     println!();
 
@@ -17,32 +28,21 @@ pub fn check_environment(check_frame: bool) -> Result<()> {
 
     // Check cleen directories
     println!("📁 Directory Structure:");
-    let cleen_dir = &config.cleen_dir;
-    println!("  cleen directory: {cleen_dir:?}");
-
-    if cleen_dir.exists() {
-        println!("    ✅ exists");
-    } else {
-        println!("    ❌ missing");
-        issues_found += 1;
-    }
-
-    let versions_dir = config.get_versions_dir();
-    println!("  versions directory: {versions_dir:?}");
-    if versions_dir.exists() {
-        println!("    ✅ exists");
-    } else {
-        println!("    ❌ missing");
-        issues_found += 1;
-    }
-
-    let bin_dir = config.get_bin_dir();
-    println!("  bin directory: {bin_dir:?}");
-    if bin_dir.exists() {
-        println!("    ✅ exists");
-    } else {
-        println!("    ❌ missing");
-        issues_found += 1;
+    for (label, dir) in [
+        ("cleen directory", config.cleen_dir.clone()),
+        ("versions directory", config.get_versions_dir()),
+        ("bin directory", config.get_bin_dir()),
+    ] {
+        println!("  {label}: {dir:?}");
+        if dir.exists() {
+            println!("    ✅ exists");
+        } else {
+            println!("    ❌ missing");
+            issues_found += 1;
+            if fix && repair("Created it", || Ok(std::fs::create_dir_all(&dir)?)) {
+                issues_found -= 1;
+            }
+        }
     }
 
     println!();
@@ -80,13 +80,29 @@ pub fn check_environment(check_frame: bool) -> Result<()> {
             println!("  📁 Project version (.cleanlanguage/.cleanversion): {project_version}");
 
             // Verify project version is installed
-            if version_manager.is_version_installed(&project_version) {
-                println!("    ✅ Project version is installed");
-            } else {
-                println!(
-                    "    ❌ Project version not installed - run 'cleen install {project_version}'"
-                );
-                issues_found += 1;
+            match VersionRange::parse(&project_version) {
+                Ok(Some(_)) => match version_manager.resolve_version_spec(&project_version) {
+                    Ok(resolved) => {
+                        println!("    ✅ Range resolves to installed version {resolved}")
+                    }
+                    Err(e) => {
+                        println!("    ❌ {e}");
+                        issues_found += 1;
+                    }
+                },
+                Ok(None) if version_manager.is_version_installed(&project_version) => {
+                    println!("    ✅ Project version is installed");
+                }
+                Ok(None) => {
+                    println!(
+                        "    ❌ Project version not installed - run 'cleen install {project_version}'"
+                    );
+                    issues_found += 1;
+                }
+                Err(e) => {
+                    println!("    ❌ {e}");
+                    issues_found += 1;
+                }
             }
         } else {
             println!("  📁 Project version: none (.cleanlanguage/.cleanversion file not found)");
@@ -124,24 +140,105 @@ pub fn check_environment(check_frame: bool) -> Result<()> {
     let shim_path = config.get_shim_path();
     println!("  Shim path: {shim_path:?}");
 
-    if shim_path.exists() {
+    if let Some(problem) = broken_symlink(&shim_path) {
+        println!("    ❌ Shim is a broken symlink: {problem}");
+        issues_found += 1;
+        match config.get_effective_version() {
+            Some(version) => {
+                let shim_manager = ShimManager::new(config.clone());
+                if offer_fix(
+                    fix,
+                    &format!("Re-create the shim for {version}?"),
+                    &format!("Re-created the shim for {version}"),
+                    || shim_manager.create_shim(&version),
+                )? {
+                    issues_found -= 1;
+                } else {
+                    println!("      Run 'cleen use {version}' to re-create it");
+                }
+            }
+            None => println!("      Run 'cleen use <version>' to re-create it"),
+        }
+    } else if shim_path.exists() {
         println!("    ✅ Shim exists");
+        let shim_manager = ShimManager::new(config.clone());
+        let target = shim_manager.shim_target();
+        if target != ShimTarget::Trampoline {
+            println!("    ⚠️  Shim links straight to one version, so project pins are ignored");
+            if let Some(active) = &config.active_version {
+                println!("      Run 'cleen use {active}' to upgrade it");
+            }
+        }
+        if let Some(drift) = describe_shim_drift(&target, config.active_version.as_deref()) {
+            println!("    ❌ {drift}");
+            issues_found += 1;
+            if let Some(active) = &config.active_version {
+                if offer_fix(
+                    fix,
+                    &format!("Re-point the shim to {active}?"),
+                    &format!("Re-pointed the shim to {active}"),
+                    || shim_manager.create_shim(active),
+                )? {
+                    issues_found -= 1;
+                } else {
+                    println!("      Run 'cleen use {active}' to re-point it");
+                }
+            }
+        }
     } else {
         println!("    ❌ Shim missing");
         issues_found += 1;
+        if let Some(version) = config.get_effective_version() {
+            let shim_manager = ShimManager::new(config.clone());
+            if offer_fix(
+                fix,
+                &format!("Create the shim for {version}?"),
+                &format!("Created the shim for {version}"),
+                || shim_manager.create_shim(&version),
+            )? {
+                issues_found -= 1;
+            }
+        }
     }
 
     // Check PATH
     println!("  PATH check:");
     let bin_dir_binding = config.get_bin_dir();
     let bin_dir_str = bin_dir_binding.to_string_lossy();
-    if let Ok(path) = std::env::var("PATH") {
-        if path.contains(&*bin_dir_str) {
+    if let Some(path) = env::var_os("PATH") {
+        if path.to_string_lossy().contains(&*bin_dir_str) {
             println!("    ✅ cleen bin directory is in PATH");
         } else {
             println!("    ❌ cleen bin directory not in PATH");
-            println!("      Run 'cleen init' to fix this");
             issues_found += 1;
+            if fix
+                && repair(
+                    "Added it to your shell configuration; restart your terminal to pick it up",
+                    || {
+                        crate::utils::shell::add_to_path(&bin_dir_binding).map_err(|e| {
+                            CleenError::IoError {
+                                message: e.to_string(),
+                            }
+                        })
+                    },
+                )
+            {
+                issues_found -= 1;
+            } else {
+                println!("      Run 'cleen init' to fix this");
+            }
+        }
+
+        match find_on_path(binaries::CLN_SHIM, &path) {
+            Some(resolved) if !is_in_dir(&resolved, &bin_dir_binding) => {
+                let shadow_dir = resolved.parent().unwrap_or(&resolved);
+                println!("    ⚠️  'cln' resolves to {resolved:?}, which shadows cleen's shim");
+                println!("      'cleen use' has no effect until {bin_dir_binding:?} comes");
+                println!("      before {shadow_dir:?} in PATH (or that 'cln' is removed)");
+                issues_found += 1;
+            }
+            Some(_) => println!("    ✅ 'cln' resolves to cleen's shim"),
+            None => {}
         }
     } else {
         println!("    ❌ PATH environment variable not found");
@@ -254,7 +351,22 @@ pub fn check_environment(check_frame: bool) -> Result<()> {
 
                 // Check Frame shim
                 let frame_shim = config.get_frame_shim_path();
-                if frame_shim.exists() {
+                if let Some(problem) = broken_symlink(&frame_shim) {
+                    println!();
+                    println!("  Shim: {frame_shim:?}");
+                    println!("    ❌ Frame shim is a broken symlink: {problem}");
+                    issues_found += 1;
+                    if offer_fix(
+                        fix,
+                        &format!("Re-create the Frame shim for {active_frame}?"),
+                        &format!("Re-created the Frame shim for {active_frame}"),
+                        || frame::update_frame_symlink(&config, active_frame),
+                    )? {
+                        issues_found -= 1;
+                    } else {
+                        println!("      Run: cleen frame use {active_frame}");
+                    }
+                } else if frame_shim.exists() {
                     println!();
                     println!("  Shim: {frame_shim:?}");
                     println!("    ✅ Frame shim exists");
@@ -262,8 +374,17 @@ pub fn check_environment(check_frame: bool) -> Result<()> {
                     println!();
                     println!("  Shim: {frame_shim:?}");
                     println!("    ❌ Frame shim missing");
-                    println!("      Run: cleen frame use {active_frame}");
                     issues_found += 1;
+                    if fix
+                        && repair(
+                            &format!("Created the Frame shim for {active_frame}"),
+                            || frame::update_frame_symlink(&config, active_frame),
+                        )
+                    {
+                        issues_found -= 1;
+                    } else {
+                        println!("      Run: cleen frame use {active_frame}");
+                    }
                 }
             } else {
                 println!();
@@ -293,12 +414,12 @@ pub fn check_environment(check_frame: bool) -> Result<()> {
     } else if gy_count <= 10 {
         println!(
             "  ✅ {gy_count} found ({}) — within healthy range",
-            crate::commands::cleanup::format_size(gy_bytes)
+            crate::utils::fs::format_size(gy_bytes)
         );
     } else {
         println!(
             "  ⚠️  {gy_count} found ({})",
-            crate::commands::cleanup::format_size(gy_bytes)
+            crate::utils::fs::format_size(gy_bytes)
         );
         println!("     Run 'cleen cleanup --graveyards --confirm' to free space.");
         issues_found += 1;
@@ -327,6 +448,9 @@ pub fn check_environment(check_frame: bool) -> Result<()> {
         println!("⚠️  Found {issues_found} issue(s) that need attention.");
         println!();
         println!("💡 To fix issues:");
+        if !fix {
+            println!("  - Run 'cleen doctor --fix' to repair directories, shims and PATH");
+        }
         println!("  - Run 'cleen init' to set up shell configuration");
         println!("  - Run 'cleen install <version>' to install a version");
         println!("  - Run 'cleen use <version>' to set global version");
@@ -336,6 +460,113 @@ pub fn check_environment(check_frame: bool) -> Result<()> {
     Ok(())
 }
 
+/// Explain how the shim disagrees with `config.active_version`, if it does.
+/// A trampoline shim follows the config on every run, so it can't drift.
+fn describe_shim_drift(target: &ShimTarget, active: Option<&str>) -> Option<String> {
+    let active = active.map(normalize::to_clean_version);
+    match (target, active) {
+        (ShimTarget::Version(version), Some(active)) if *version != active => Some(format!(
+            "Shim runs {version} but the active version in config is {active}"
+        )),
+        (ShimTarget::Version(version), None) => Some(format!(
+            "Shim runs {version} but no active version is set in config"
+        )),
+        (ShimTarget::Other(path), _) => Some(format!(
+            "Shim points outside cleen's versions directory: {path:?}"
+        )),
+        _ => None,
+    }
+}
+
+/// Run `fix` straight away when `auto` (`--fix`) is set; otherwise ask
+/// `prompt` first. Only asks on an interactive terminal so scripted
+/// `cleen doctor` runs never block. Returns whether it fixed it.
+fn offer_fix(
+    auto: bool,
+    prompt: &str,
+    done: &str,
+    fix: impl FnOnce() -> Result<()>,
+) -> Result<bool> {
+    if auto {
+        return Ok(repair(done, fix));
+    }
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+
+    print!("      {prompt} (Y/n): ");
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim().to_lowercase();
+    if !(input.is_empty() || input == "y" || input == "yes") {
+        return Ok(false);
+    }
+
+    Ok(repair(done, fix))
+}
+
+/// Run `fix`, printing `done` or why it failed. Returns whether it worked.
+fn repair(done: &str, fix: impl FnOnce() -> Result<()>) -> bool {
+    match fix() {
+        Ok(()) => {
+            println!("      🔧 {done}");
+            true
+        }
+        Err(e) => {
+            println!("      ❌ Repair failed: {e}");
+            false
+        }
+    }
+}
+
+/// What's wrong with `shim` if it is a symlink whose target is missing or
+/// not executable. Regular files and missing paths aren't reported here.
+fn broken_symlink(shim: &Path) -> Option<String> {
+    let target = std::fs::read_link(shim).ok()?;
+    let target = match shim.parent() {
+        Some(dir) if target.is_relative() => dir.join(target),
+        _ => target,
+    };
+    if !target.exists() {
+        Some(format!("its target {target:?} does not exist"))
+    } else if !crate::utils::fs::is_executable(&target) {
+        Some(format!("its target {target:?} is not executable"))
+    } else {
+        None
+    }
+}
+
+/// Resolve `name` against a PATH value the way the shell would: the first
+/// directory holding an executable file of that name wins.
+fn find_on_path(name: &str, path: &OsStr) -> Option<PathBuf> {
+    let candidates: Vec<String> = if cfg!(windows) {
+        ["exe", "bat", "cmd"]
+            .iter()
+            .map(|ext| format!("{name}.{ext}"))
+            .collect()
+    } else {
+        vec![name.to_string()]
+    };
+
+    env::split_paths(path)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .flat_map(|dir| candidates.iter().map(move |file| dir.join(file)))
+        .find(|candidate| {
+            // Windows decides by extension, which the candidates already fix.
+            candidate.is_file() && (cfg!(windows) || crate::utils::fs::is_executable(candidate))
+        })
+}
+
+/// Whether `file` sits directly in `dir`, comparing canonical paths so a
+/// symlinked or differently spelled PATH entry still matches.
+fn is_in_dir(file: &Path, dir: &Path) -> bool {
+    let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    file.parent()
+        .map(|parent| canonical(parent) == canonical(dir))
+        .unwrap_or(false)
+}
+
 fn test_runtime_execution() -> Result<()> {
     // Create a simple test program
     let test_program = r#"start()
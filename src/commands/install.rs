@@ -1,92 +1,718 @@
 use crate::core::{
-    config::Config, download::Downloader, frame, github::GitHubClient, version::normalize,
+    binaries,
+    config::Config,
+    download::{self, ArchiveCache, Downloader},
+    frame,
+    github::{Asset, GitHubClient, Release},
+    report::InstallReport,
+    shim::ShimManager,
+    version::{self, normalize, NightlySpec},
 };
 use crate::error::{CleenError, Result};
+use crate::utils::{fs::format_size, timing};
 use dialoguer::Confirm;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
 
-pub fn install_version(version: &str, with_frame: bool, no_frame: bool) -> Result<()> {
-    println!("Installing Clean Language version: {version}");
+/// Install each of `versions`, downloading up to `jobs` at a time. A single
+/// version goes through [`install_version`] unchanged. With several, each
+/// reports on its own lines and a failure doesn't stop the others; a
+/// summary lists how each one went.
+pub fn install_versions(
+    versions: &[String],
+    jobs: usize,
+    with_frame: bool,
+    no_frame: bool,
+    force: bool,
+    keep_archive: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if let [version] = versions {
+        return install_version(version, with_frame, no_frame, force, keep_archive, dry_run);
+    }
 
     let config = Config::load()?;
-    let github_client = GitHubClient::new(config.github_api_token.clone());
-    let downloader = Downloader::new();
+    let versions = dedupe_install_specs(&config, versions);
+    let results: Vec<(String, Result<String>)> = if dry_run {
+        // Previews only talk to GitHub; keep their output in order.
+        versions
+            .iter()
+            .map(|version| {
+                println!();
+                let result = install_version(version, false, true, false, false, true)
+                    .map(|()| "previewed".to_string());
+                (version.clone(), result)
+            })
+            .collect()
+    } else {
+        let jobs = jobs.clamp(1, versions.len());
+        println!(
+            "Installing {} versions, {jobs} at a time: {}",
+            versions.len(),
+            versions.join(", ")
+        );
+        install_concurrently(&config, &versions, jobs, force, keep_archive)
+    };
 
-    // Resolve version (handle "latest") first and normalize to GitHub format
-    let github_version = if version == "latest" {
-        println!("Fetching latest release...");
-        match github_client.get_latest_release("Ivan-Pasco", "clean-language-compiler") {
-            Ok(release) => {
-                println!("Latest version: {}", release.tag_name);
-                release.tag_name
-            }
+    println!();
+    println!("Summary:");
+    let width = versions.iter().map(String::len).max().unwrap_or(0);
+    let mut failed = Vec::new();
+    for (version, result) in &results {
+        match result {
+            Ok(outcome) => println!("  ✅ {version:<width$}  {outcome}"),
             Err(e) => {
-                println!("⚠️  Unable to fetch latest version from GitHub: {e}");
-                println!("   This may be because the repository doesn't have releases yet.");
-                println!("   Please specify a specific version or check the repository:");
-                println!("   https://github.com/Ivan-Pasco/clean-language-compiler/releases");
-                return Ok(());
+                println!("  ❌ {version:<width$}  {e}");
+                failed.push(version.clone());
             }
         }
+    }
+    if dry_run {
+        return finish_multi_install(failed);
+    }
+
+    let config = Config::load()?;
+    if failed.len() < results.len() {
+        crate::core::heartbeat::send_install();
+        println!();
+        println!("To use one of these versions, run:");
+        println!("   cleen use <version>");
+    }
+    if with_frame && !no_frame && failed.len() < results.len() {
+        println!();
+        println!("Installing Frame CLI...");
+        if let Err(e) = frame::install_frame(None, false) {
+            eprintln!("⚠️  Failed to install Frame CLI: {e}");
+            eprintln!("   You can install it later with: cleen frame install");
+        }
+    }
+    if let Some(active) = &config.active_version {
+        // A forced reinstall of the active version needs its shim refreshed,
+        // as in the single-version path.
+        if force
+            && versions
+                .iter()
+                .any(|v| normalize::to_clean_version(v) == *active)
+        {
+            ShimManager::new(config.clone()).create_shim(active)?;
+        }
+    }
+    finish_multi_install(failed)
+}
+
+fn finish_multi_install(failed: Vec<String>) -> Result<()> {
+    if failed.is_empty() {
+        Ok(())
     } else {
-        normalize::to_github_version(version)
+        Err(CleenError::InstallFailed {
+            versions: failed.join(", "),
+        })
+    }
+}
+
+/// Drop repeated specs, resolving `latest` first when other versions are
+/// listed too so it can't race an explicit request for the same release.
+fn dedupe_install_specs(config: &Config, versions: &[String]) -> Vec<String> {
+    let latest = versions
+        .iter()
+        .any(|v| v == "latest")
+        .then(|| {
+            GitHubClient::from_config(config)
+                .get_latest_release("Ivan-Pasco", "clean-language-compiler")
+                .ok()
+        })
+        .flatten()
+        .map(|release| normalize::to_clean_version(&release.tag_name));
+
+    let mut unique: Vec<String> = Vec::new();
+    for version in versions {
+        let spec = match (&latest, version.as_str()) {
+            (Some(latest), "latest") => latest.clone(),
+            _ => normalize::to_clean_version(version),
+        };
+        if !unique.contains(&spec) {
+            unique.push(spec);
+        }
+    }
+    unique
+}
+
+/// Run [`install_compiler`] for every version on `jobs` worker threads,
+/// prefixing each progress line with its version. Downloads run curl
+/// without a progress bar, so workers never draw over each other's lines.
+/// Results come back in the order of `versions`, each with a one-line
+/// outcome.
+fn install_concurrently(
+    config: &Config,
+    versions: &[String],
+    jobs: usize,
+    force: bool,
+    keep_archive: bool,
+) -> Vec<(String, Result<String>)> {
+    let queue = Mutex::new(versions.iter().enumerate());
+    let results = Mutex::new(Vec::new());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let Some((index, version)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let mut progress = |line: &str| println!("[{version}] {line}");
+                let result = install_compiler(config, version, force, keep_archive, &mut progress)
+                    .map(|report| {
+                        if let Err(e) = validate_installed_binary(&report.binary_path) {
+                            progress(&format!("⚠️  Installed binary may have issues: {e}"));
+                        }
+                        let outcome = format!(
+                            "installed {} ({}, {:.1}s)",
+                            report.version,
+                            format_size(report.bytes),
+                            report.duration.as_secs_f64()
+                        );
+                        progress("✅ Done");
+                        outcome
+                    });
+                results
+                    .lock()
+                    .unwrap()
+                    .push((index, version.clone(), result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _, _)| *index);
+    results
+        .into_iter()
+        .map(|(_, version, result)| (version, result))
+        .collect()
+}
+
+pub fn install_version(
+    version: &str,
+    with_frame: bool,
+    no_frame: bool,
+    force: bool,
+    keep_archive: bool,
+    dry_run: bool,
+) -> Result<()> {
+    // Reject path-like input before it reaches any filesystem join.
+    validate_install_spec(version)?;
+
+    let config = Config::load()?;
+    let result = if dry_run {
+        println!("Previewing install of Clean Language version: {version}");
+        preview_install(&config, version).map(|()| None)
+    } else {
+        println!("Installing Clean Language version: {version}");
+        install_compiler(&config, version, force, keep_archive, &mut |line| {
+            println!("{line}")
+        })
+        .map(Some)
     };
+    let report = match result {
+        Ok(Some(report)) => report,
+        Ok(None) => return Ok(()),
+        Err(CleenError::GitHubError { message }) if version == "latest" => {
+            println!("⚠️  Unable to fetch latest version from GitHub: {message}");
+            println!("   This may be because the repository doesn't have releases yet.");
+            println!("   Please specify a specific version or check the repository:");
+            println!("   https://github.com/Ivan-Pasco/clean-language-compiler/releases");
+            return Ok(());
+        }
+        Err(e @ CleenError::VersionNotFound { .. }) => {
+            print_release_not_found_help(&config);
+            return Err(e);
+        }
+        Err(e) => return Err(e),
+    };
+    let clean_version = report.version.clone();
+    let binary_path = report.binary_path.clone();
 
-    // Normalize to clean version for local storage
-    let clean_version = normalize::to_clean_version(&github_version);
+    // Validate the installed binary works correctly
+    print!("🔍 Validating installation...");
+    if let Err(e) = timing::phase("validation", || validate_installed_binary(&binary_path)) {
+        println!();
+        eprintln!("⚠️  Warning: Installed binary may have issues: {e}");
+        eprintln!("   The binary was installed but may not function correctly.");
+        eprintln!("   You may need to use a different version or compile from source.");
+    } else {
+        println!(" ✅");
+    }
+
+    println!("✅ Successfully installed Clean Language version {clean_version}");
+    println!("   Binary location: {binary_path:?}");
+    if report.from_archive_cache {
+        println!(
+            "   Installed from cached archive in {:.1}s",
+            report.duration.as_secs_f64()
+        );
+    } else {
+        println!(
+            "   Downloaded {} in {:.1}s",
+            format_size(report.bytes),
+            report.duration.as_secs_f64()
+        );
+    }
+
+    if report.reinstalled && config.active_version.as_deref() == Some(clean_version.as_str()) {
+        // The shim already points at this version's directory; recreate it
+        // so a newly-shipped language server is linked as well.
+        ShimManager::new(config.clone()).create_shim(&clean_version)?;
+    } else {
+        println!();
+        println!("To use this version, run:");
+        println!("   cleen use {clean_version}");
+    }
+    // Signal the errors dashboard that this compiler version is now active
+    // locally. This is what advances open bugs from fix_released to
+    // fix_installed. Reads the active toolchain from config; prints any
+    // bugs the server advanced as a result. Silent on transport failure.
+    crate::core::heartbeat::send_install();
+
+    // Reload after install so the new version is counted as active when
+    // computing the cleanup hint below.
+    let config = Config::load()?;
+    if let Some((count, bytes)) = crate::commands::cleanup::compiler_cleanup_summary(&config) {
+        println!();
+        println!(
+            "💡 {count} inactive compiler version(s) using {} — run `cleen cleanup` to free space.",
+            format_size(bytes)
+        );
+    }
+
+    // Offer Frame CLI installation
+    if !no_frame && config.auto_offer_frame {
+        let should_install_frame = if with_frame {
+            true
+        } else {
+            // Interactive prompt
+            println!();
+            Confirm::new()
+                .with_prompt("Would you like to install Frame CLI as well?")
+                .default(true)
+                .interact()
+                .unwrap_or_default()
+        };
 
-    println!("Resolved version: {clean_version}");
+        if should_install_frame {
+            println!();
+            println!("Installing Frame CLI...");
+            match frame::install_frame(None, false) {
+                Ok(_) => {
+                    println!();
+                    println!("✅ Installation complete!");
+                    println!("   cln --version");
+                    println!("   frame --version");
+                }
+                Err(e) => {
+                    eprintln!();
+                    eprintln!("⚠️  Failed to install Frame CLI: {e}");
+                    eprintln!("   You can install it later with: cleen frame install");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve, download, and unpack a compiler version into `config`'s
+/// versions directory without any terminal interaction.
+///
+/// Human-readable progress goes to `progress` one line at a time: the CLI
+/// prints each line, [`crate::api`] discards them. Post-install steps that
+/// only make sense at a terminal (binary smoke test, shim hints, Frame
+/// prompt) are left to the caller.
+pub(crate) fn install_compiler(
+    config: &Config,
+    version: &str,
+    force: bool,
+    keep_archive: bool,
+    progress: &mut dyn FnMut(&str),
+) -> Result<InstallReport> {
+    validate_install_spec(version)?;
+
+    let started = Instant::now();
+    let github_client = GitHubClient::from_config(config);
+    let downloader = Downloader::new();
+
+    // Resolve the release tag (handling "latest" and nightly builds) and
+    // the name the version is stored under locally
+    let (github_version, clean_version) =
+        resolve_install_version(&github_client, version, progress)?;
+
+    progress(&format!("Resolved version: {clean_version}"));
 
     // Check if version is already installed (using clean version for storage)
     let version_dir = config.get_version_dir(&clean_version);
-    if version_dir.exists() {
+    let reinstall = check_existing_install(&version_dir, &clean_version, force, progress)?;
+
+    // A kept archive skips GitHub entirely, so a reinstall works offline.
+    let archive_cache = ArchiveCache::new(config.get_download_cache_dir());
+    let cached_archive = archive_cache.lookup(&clean_version);
+
+    let (asset_name, temp_dir, download_path) = match &cached_archive {
+        Some(cached) => {
+            progress(&format!("Using cached archive {}", cached.display()));
+            let size = std::fs::metadata(cached).map(|m| m.len()).unwrap_or(0);
+            crate::utils::fs::ensure_install_space(&version_dir, size)?;
+            let name = cached
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            (name, None, cached.clone())
+        }
+        None => {
+            let (asset, checksum) = timing::phase("github fetch", || {
+                fetch_compiler_asset(&github_client, &github_version, &clean_version, progress)
+            })?;
+
+            // Refuse early rather than leaving a half-extracted version directory
+            // behind when the disk fills up mid-install.
+            crate::utils::fs::ensure_install_space(&version_dir, asset.size)?;
+
+            let (temp_dir, download_path) = timing::phase("download", || {
+                download_compiler_asset(
+                    &downloader,
+                    &asset,
+                    checksum.as_ref(),
+                    &clean_version,
+                    progress,
+                )
+            })?;
+
+            if keep_archive || config.keep_archives {
+                match archive_cache.store(&clean_version, &asset.name, &download_path) {
+                    Ok(kept) => progress(&format!("Kept archive at {}", kept.display())),
+                    Err(e) => progress(&format!("⚠️  Could not keep archive: {e}")),
+                }
+            }
+            (asset.name, Some(temp_dir), download_path)
+        }
+    };
+
+    // Extract to version directory. A failure anywhere in here removes the
+    // half-populated directory so a retry doesn't trip VersionAlreadyInstalled.
+    // A forced reinstall builds the new tree alongside and swaps it in, so
+    // a failed download never costs the user the copy they already had.
+    let extracted = timing::phase("extraction", || {
+        if reinstall {
+            replace_version_dir(
+                &downloader,
+                &download_path,
+                &asset_name,
+                &version_dir,
+                progress,
+            )
+        } else {
+            populate_version_dir(
+                &downloader,
+                &download_path,
+                &asset_name,
+                &version_dir,
+                progress,
+            )
+        }
+    });
+    if extracted.is_err() {
+        // Drop the download, and don't keep serving an archive that won't
+        // extract, whether it came from the cache or was just stored there.
+        if let Some(temp_dir) = &temp_dir {
+            let _ = std::fs::remove_dir_all(temp_dir);
+        }
+        archive_cache.remove(&clean_version);
+    }
+    let binary_path = extracted?;
+
+    // Belt-and-braces strip for the direct-binary branch above where the
+    // archive extractor's recursive strip didn't run.
+    crate::utils::fs::strip_macos_xattrs_recursive(&version_dir);
+    version::VersionMetadata::record_install(&version_dir);
+
+    // compile-options.json is stored per-version in the version directory
+    // The extraction already placed it there, just verify and inform the user
+    let options_path = version_dir.join("compile-options.json");
+    if options_path.exists() {
+        progress(&format!(
+            "✓ Found compile-options.json for version {clean_version}"
+        ));
+    } else {
+        // This is just informational, not an error, since older releases may not have this file
+        progress("ℹ️  Note: compile-options.json not found in release package");
+        progress("   This is expected for compiler versions before dynamic options support.");
+    }
+
+    let bytes = match temp_dir {
+        Some(_) => std::fs::metadata(&download_path)
+            .map(|m| m.len())
+            .unwrap_or(0),
+        None => 0,
+    };
+
+    // Clean up temporary files
+    if let Some(temp_dir) = temp_dir {
+        std::fs::remove_dir_all(&temp_dir)?;
+    }
+
+    Ok(InstallReport {
+        version: clean_version,
+        binary_path,
+        already_installed: false,
+        from_archive_cache: cached_archive.is_some(),
+        asset_name: Some(asset_name),
+        bytes,
+        duration: started.elapsed(),
+        reinstalled: reinstall,
+    })
+}
+
+/// Show which asset an install of `version` would download for this
+/// platform, without creating a temp directory or writing any files.
+fn preview_install(config: &Config, version: &str) -> Result<()> {
+    let github_client = GitHubClient::from_config(config);
+    let mut progress = |line: &str| println!("{line}");
+    let (github_version, clean_version) =
+        resolve_install_version(&github_client, version, &mut progress)?;
+
+    let (asset, checksum) = match fetch_compiler_asset(
+        &github_client,
+        &github_version,
+        &clean_version,
+        &mut progress,
+    ) {
+        Ok(found) => found,
+        // The available assets have already been listed.
+        Err(CleenError::BinaryNotFound { name }) => {
+            println!();
+            println!("❌ No asset would be installed: {name} not found");
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    println!();
+    println!("🔍 Dry run - nothing was downloaded");
+    println!("   Version:  {clean_version}");
+    println!("   Asset:    {}", asset.name);
+    println!(
+        "   Size:     {} ({} bytes)",
+        format_size(asset.size),
+        asset.size
+    );
+    println!("   URL:      {}", asset.browser_download_url);
+    match checksum {
+        Some(checksum) => println!("   Checksum: {}", checksum.name),
+        None => println!("   Checksum: none published"),
+    }
+    if config.get_version_dir(&clean_version).exists() {
+        println!("   ℹ️  Version {clean_version} is already installed; use --force to reinstall");
+    }
+    Ok(())
+}
+
+/// `nightly` and `sha:<hash>` carry characters `validate_version` rejects
+/// and are checked by [`NightlySpec::parse`] instead.
+fn validate_install_spec(version: &str) -> Result<()> {
+    match NightlySpec::parse(version)? {
+        Some(_) => Ok(()),
+        None => version::validate_version(version),
+    }
+}
+
+/// Resolve `version` to the release tag to download and the version name
+/// it is stored under in `~/.cleen/versions`.
+fn resolve_install_version(
+    github_client: &GitHubClient,
+    version: &str,
+    progress: &mut dyn FnMut(&str),
+) -> Result<(String, String)> {
+    if let Some(spec) = NightlySpec::parse(version)? {
+        return resolve_nightly(github_client, &spec, progress);
+    }
+    let github_version = resolve_github_version(github_client, version, progress)?;
+    let clean_version = normalize::to_clean_version(&github_version);
+    Ok((github_version, clean_version))
+}
+
+/// Resolve a nightly request to its pre-release tag and the
+/// `nightly-<short-sha>` name it installs under, so it never shadows a
+/// tagged release.
+fn resolve_nightly(
+    github_client: &GitHubClient,
+    spec: &NightlySpec,
+    progress: &mut dyn FnMut(&str),
+) -> Result<(String, String)> {
+    let github_error = |e: anyhow::Error| CleenError::GitHubError {
+        message: e.to_string(),
+    };
+    let (release, sha) = match spec {
+        NightlySpec::Latest => {
+            progress("Fetching nightly release...");
+            let release = timing::phase("github fetch", || {
+                github_client.get_release_by_tag("Ivan-Pasco", "clean-language-compiler", "nightly")
+            })
+            .map_err(github_error)?;
+            // A nightly tag is usually moved onto each new build, so its
+            // release may name the branch rather than the commit.
+            let sha = if is_full_sha(&release.target_commitish) {
+                release.target_commitish.clone()
+            } else {
+                github_client
+                    .get_commit_sha("Ivan-Pasco", "clean-language-compiler", &release.tag_name)
+                    .map_err(github_error)?
+            };
+            (release, sha)
+        }
+        NightlySpec::Commit(hash) => {
+            progress(&format!("Looking for a pre-release build of {hash}..."));
+            let releases = timing::phase("github fetch", || {
+                github_client.get_releases("Ivan-Pasco", "clean-language-compiler")
+            })
+            .map_err(github_error)?;
+            find_commit_release(releases, hash).ok_or_else(|| CleenError::VersionNotFound {
+                version: format!("sha:{hash}"),
+            })?
+        }
+    };
+    let clean_version = version::nightly_version(&sha);
+    progress(&format!(
+        "Nightly build: {} ({clean_version})",
+        release.tag_name
+    ));
+    Ok((release.tag_name, clean_version))
+}
+
+/// The pre-release or draft built from commit `hash`, with the best-known
+/// SHA. A release targeting the commit wins over one that only carries
+/// its short SHA in the tag.
+fn find_commit_release(releases: Vec<Release>, hash: &str) -> Option<(Release, String)> {
+    let short = &hash[..hash.len().min(7)];
+    let mut candidates: Vec<Release> = releases
+        .into_iter()
+        .filter(|r| r.prerelease || r.draft)
+        .collect();
+    if let Some(index) = candidates.iter().position(|r| {
+        let target = r.target_commitish.to_ascii_lowercase();
+        is_full_sha(&target) && target.starts_with(hash)
+    }) {
+        let release = candidates.swap_remove(index);
+        let sha = release.target_commitish.to_ascii_lowercase();
+        return Some((release, sha));
+    }
+    candidates
+        .into_iter()
+        .find(|r| r.tag_name.to_ascii_lowercase().contains(short))
+        .map(|release| (release, hash.to_string()))
+}
+
+fn is_full_sha(reference: &str) -> bool {
+    reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Resolve `version` to a release tag, asking GitHub for `latest`.
+fn resolve_github_version(
+    github_client: &GitHubClient,
+    version: &str,
+    progress: &mut dyn FnMut(&str),
+) -> Result<String> {
+    if version != "latest" {
+        return Ok(normalize::to_github_version(version));
+    }
+    progress("Fetching latest release...");
+    let release = timing::phase("github fetch", || {
+        github_client.get_latest_release("Ivan-Pasco", "clean-language-compiler")
+    })
+    .map_err(|e| CleenError::GitHubError {
+        message: e.to_string(),
+    })?;
+    progress(&format!("Latest version: {}", release.tag_name));
+    Ok(release.tag_name)
+}
+
+/// Explain a failed release lookup and, best-effort, list recent versions
+/// so the user can pick one.
+fn print_release_not_found_help(config: &Config) {
+    println!("   This may be because:");
+    println!("   • The version does not exist");
+    println!("   • Network connectivity issues");
+    println!("   • GitHub API rate limiting");
+    println!();
+    // A failure here is informational.
+    let github_client = GitHubClient::from_config(config);
+    if let Ok(releases) = github_client.get_releases("Ivan-Pasco", "clean-language-compiler") {
+        if !releases.is_empty() {
+            println!("Available versions (recent):");
+            for r in &releases {
+                println!("  • {}", normalize::to_clean_version(&r.tag_name));
+            }
+            println!();
+        }
+    }
+    println!("   Please check the repository manually:");
+    println!("   https://github.com/Ivan-Pasco/clean-language-compiler/releases");
+}
+
+/// Decide what to do about an existing `version_dir`: without `force` it is
+/// an error, with `force` the caller replaces it. Returns `true` when the
+/// install is a reinstall over an existing directory.
+fn check_existing_install(
+    version_dir: &Path,
+    clean_version: &str,
+    force: bool,
+    progress: &mut dyn FnMut(&str),
+) -> Result<bool> {
+    if !version_dir.exists() {
+        return Ok(false);
+    }
+    if !force {
         return Err(CleenError::VersionAlreadyInstalled {
-            version: clean_version.clone(),
+            version: clean_version.to_string(),
         });
     }
+    progress(&format!(
+        "♻️  Version {clean_version} is already installed, reinstalling (--force)"
+    ));
+    Ok(true)
+}
 
+/// Fetch the release for `github_version` and pick the asset for this
+/// platform. Lookup failures surface as `VersionNotFound` after a one-line
+/// explanation on `progress`.
+pub(crate) fn fetch_compiler_asset(
+    github_client: &GitHubClient,
+    github_version: &str,
+    clean_version: &str,
+    progress: &mut dyn FnMut(&str),
+) -> Result<(Asset, Option<Asset>)> {
     // Fetch the pinned tag directly via /releases/tags/<tag>. This endpoint
     // returns a single Release object (not paginated) and is not affected by
     // the "invalid type: map" failure mode when GitHub returns an error
     // response. See CLEEN-FRAME-INSTALL-BROKEN.
-    println!("Fetching release {github_version}...");
+    progress(&format!("Fetching release {github_version}..."));
     let release = match github_client.get_release_by_tag(
         "Ivan-Pasco",
         "clean-language-compiler",
-        &github_version,
+        github_version,
     ) {
         Ok(release) => release,
         Err(e) => {
-            println!("⚠️  Unable to fetch release {github_version} from GitHub: {e}");
-            println!("   This may be because:");
-            println!("   • The version does not exist");
-            println!("   • Network connectivity issues");
-            println!("   • GitHub API rate limiting");
-            println!();
-            // Best-effort: show a recent version list to help the user pick
-            // one. A failure here is informational.
-            if let Ok(releases) =
-                github_client.get_releases("Ivan-Pasco", "clean-language-compiler")
-            {
-                if !releases.is_empty() {
-                    println!("Available versions (recent):");
-                    for r in &releases {
-                        println!("  • {}", normalize::to_clean_version(&r.tag_name));
-                    }
-                    println!();
-                }
-            }
-            println!("   Please check the repository manually:");
-            println!("   https://github.com/Ivan-Pasco/clean-language-compiler/releases");
+            progress(&format!(
+                "⚠️  Unable to fetch release {github_version} from GitHub: {e}"
+            ));
             return Err(CleenError::VersionNotFound {
-                version: clean_version.clone(),
+                version: clean_version.to_string(),
             });
         }
     };
 
     // Find appropriate asset for current platform
     let platform_suffix = get_platform_suffix();
-    println!("Looking for asset matching platform: {platform_suffix}");
+    progress(&format!(
+        "Looking for asset matching platform: {platform_suffix}"
+    ));
 
     // PRIORITY 1: Find tarball/zip for the platform (contains binary + compile-options.json)
     let asset = release
@@ -97,7 +723,7 @@ pub fn install_version(version: &str, with_frame: bool, no_frame: bool) -> Resul
             let matches_platform = name_lower.contains(&platform_suffix.to_lowercase())
                 || name_lower.contains("universal")
                 || name_lower.contains("any");
-            let is_archive = name_lower.ends_with(".tar.gz") || name_lower.ends_with(".zip");
+            let is_archive = download::is_archive(&name_lower);
             matches_platform && is_archive
         })
         // PRIORITY 2: Fallback to direct binary (for backward compatibility)
@@ -107,54 +733,88 @@ pub fn install_version(version: &str, with_frame: bool, no_frame: bool) -> Resul
                 let matches_platform = name_lower.contains(&platform_suffix.to_lowercase())
                     || name_lower.contains("universal")
                     || name_lower.contains("any");
-                let is_binary = name_lower.contains("cln") && !name_lower.ends_with(".json");
+                let is_binary = name_lower.contains("cln")
+                    && !name_lower.ends_with(".json")
+                    && !download::is_checksum_asset(&name_lower);
                 matches_platform && is_binary
             })
-        })
-        .ok_or_else(|| {
-            println!("Available assets:");
-            for asset in &release.assets {
-                println!("  • {}", asset.name);
-            }
-            CleenError::BinaryNotFound {
-                name: format!("Asset for platform {platform_suffix} (or universal binary)"),
-            }
-        })?;
+        });
+
+    let Some(asset) = asset else {
+        progress("Available assets:");
+        for asset in &release.assets {
+            progress(&format!("  • {}", asset.name));
+        }
+        return Err(CleenError::BinaryNotFound {
+            name: format!("Asset for platform {platform_suffix} (or universal binary)"),
+        });
+    };
 
-    println!("Found asset: {}", asset.name);
+    progress(&format!("Found asset: {}", asset.name));
+    let checksum = download::checksum_asset(&release.assets, &asset.name).cloned();
+    Ok((asset.clone(), checksum))
+}
 
+/// Download `asset` into a fresh per-version temp directory and verify it
+/// against `checksum` when the release publishes one. Returns the temp
+/// directory (for the caller to remove) and the downloaded file.
+pub(crate) fn download_compiler_asset(
+    downloader: &Downloader,
+    asset: &Asset,
+    checksum: Option<&Asset>,
+    clean_version: &str,
+    progress: &mut dyn FnMut(&str),
+) -> Result<(PathBuf, PathBuf)> {
     // Create temporary download directory
     let temp_dir = std::env::temp_dir().join(format!("cleen-{clean_version}"));
     std::fs::create_dir_all(&temp_dir)?;
 
     // Download the asset
     let download_path = temp_dir.join(&asset.name);
-    println!("Downloading {}...", asset.name);
+    progress(&format!("Downloading {}...", asset.name));
     downloader
-        .download_file(&asset.browser_download_url, &download_path)
+        .download_file(&asset.browser_download_url, &download_path, progress)
         .map_err(|_e| CleenError::DownloadError {
             url: asset.browser_download_url.clone(),
         })?;
 
-    // Extract to version directory
-    std::fs::create_dir_all(&version_dir)?;
+    if let Err(e) = download::verify_checksum(downloader, checksum, &download_path, progress) {
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        return Err(e);
+    }
+
+    Ok((temp_dir, download_path))
+}
+
+/// Extract (or copy) the downloaded asset into `version_dir` and return the
+/// path to the executable `cln` inside it. `version_dir` is removed again if
+/// any step fails.
+fn populate_version_dir(
+    downloader: &Downloader,
+    download_path: &Path,
+    asset_name: &str,
+    version_dir: &Path,
+    progress: &mut dyn FnMut(&str),
+) -> Result<PathBuf> {
+    let guard = crate::utils::fs::PartialDirGuard::new(version_dir);
+    std::fs::create_dir_all(version_dir)?;
 
-    if asset.name.ends_with(".tar.gz") || asset.name.ends_with(".zip") {
-        println!("Extracting archive...");
+    if download::is_archive(asset_name) {
+        progress("Extracting archive...");
         downloader
-            .extract_archive(&download_path, &version_dir)
-            .map_err(|_e| CleenError::ExtractionError {
-                path: download_path.clone(),
+            .extract_archive(download_path, version_dir, progress)
+            .map_err(|e| {
+                progress(&format!("Extraction failed: {e}"));
+                CleenError::extraction_failed(e, download_path)
             })?;
     } else {
         // Assume it's a direct binary
-        let binary_name = if cfg!(windows) { "cln.exe" } else { "cln" };
-        let target_path = version_dir.join(binary_name);
-        std::fs::copy(&download_path, &target_path)?;
+        let target_path = version_dir.join(binaries::compiler());
+        std::fs::copy(download_path, &target_path)?;
     }
 
     // Find the extracted binary and ensure it's executable
-    let binary_path = find_binary_in_dir(&version_dir)?;
+    let binary_path = find_binary_in_dir(version_dir)?;
 
     #[cfg(unix)]
     {
@@ -164,95 +824,86 @@ pub fn install_version(version: &str, with_frame: bool, no_frame: bool) -> Resul
         std::fs::set_permissions(&binary_path, perms)?;
     }
 
-    // Belt-and-braces strip for the direct-binary branch above where the
-    // archive extractor's recursive strip didn't run.
-    crate::utils::fs::strip_macos_xattrs_recursive(&version_dir);
-
-    // compile-options.json is stored per-version in the version directory
-    // The extraction already placed it there, just verify and inform the user
-    let options_path = version_dir.join("compile-options.json");
-    if options_path.exists() {
-        println!("✓ Found compile-options.json for version {clean_version}");
-    } else {
-        // This is just informational, not an error, since older releases may not have this file
-        println!("ℹ️  Note: compile-options.json not found in release package");
-        println!("   This is expected for compiler versions before dynamic options support.");
-    }
+    verify_extracted_layout(&binary_path, version_dir, progress)?;
 
-    // Clean up temporary files
-    std::fs::remove_dir_all(&temp_dir)?;
+    guard.disarm();
+    Ok(binary_path)
+}
 
-    // Validate the installed binary works correctly
-    print!("🔍 Validating installation...");
-    if let Err(e) = validate_installed_binary(&binary_path) {
-        println!();
-        eprintln!("⚠️  Warning: Installed binary may have issues: {e}");
-        eprintln!("   The binary was installed but may not function correctly.");
-        eprintln!("   You may need to use a different version or compile from source.");
-    } else {
-        println!(" ✅");
+/// Sanity-check a freshly populated version directory before the install
+/// is reported as successful: the binary must be executable. A malformed
+/// `compile-options.json` only warns, since the compiler falls back to its
+/// built-in options. Oversized archives are stopped during extraction
+/// (see [`download::MAX_EXTRACTION_RATIO`]).
+fn verify_extracted_layout(
+    binary_path: &Path,
+    version_dir: &Path,
+    progress: &mut dyn FnMut(&str),
+) -> Result<()> {
+    if !crate::utils::fs::is_executable(binary_path) {
+        return Err(CleenError::ValidationError {
+            message: format!("{} is not executable", binary_path.display()),
+        });
     }
 
-    println!("✅ Successfully installed Clean Language version {clean_version}");
-    println!("   Binary location: {binary_path:?}");
-    println!();
-    println!("To use this version, run:");
-    println!("   cleen use {clean_version}");
+    let options_path = version_dir.join("compile-options.json");
+    if let Ok(content) = std::fs::read_to_string(&options_path) {
+        if serde_json::from_str::<serde_json::Value>(&content).is_err() {
+            progress("⚠️  compile-options.json in this release is not valid JSON");
+        }
+    }
 
-    // Signal the errors dashboard that this compiler version is now active
-    // locally. This is what advances open bugs from fix_released to
-    // fix_installed. Reads the active toolchain from config; prints any
-    // bugs the server advanced as a result. Silent on transport failure.
-    crate::core::heartbeat::send_install();
+    Ok(())
+}
 
-    // Reload after install so the new version is counted as active when
-    // computing the cleanup hint below.
-    let config = Config::load()?;
-    if let Some((count, bytes)) = crate::commands::cleanup::compiler_cleanup_summary(&config) {
-        println!();
-        println!(
-            "💡 {count} inactive compiler version(s) using {} — run `cleen cleanup` to free space.",
-            crate::commands::cleanup::format_size(bytes)
-        );
-    }
+/// Rebuild an existing `version_dir` from a downloaded asset without ever
+/// leaving it missing or half-written.
+///
+/// The asset is populated into a hidden sibling first; only once that
+/// produced a usable binary is the old directory moved aside and the new
+/// one renamed into place. Shims point at the version directory by path, so
+/// they keep resolving across the swap.
+pub(crate) fn replace_version_dir(
+    downloader: &Downloader,
+    download_path: &Path,
+    asset_name: &str,
+    version_dir: &Path,
+    progress: &mut dyn FnMut(&str),
+) -> Result<PathBuf> {
+    let parent = version_dir.parent().ok_or_else(|| CleenError::IoError {
+        message: format!("path has no parent: {}", version_dir.display()),
+    })?;
+    let name = version_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("version");
+    let pid = std::process::id();
+    let staging = parent.join(format!(".{name}.staging-{pid}"));
+    let backup = parent.join(format!(".{name}.old-{pid}"));
+    crate::utils::fs::remove_path_if_exists(&staging)?;
+    crate::utils::fs::remove_path_if_exists(&backup)?;
 
-    // Offer Frame CLI installation
-    if !no_frame && config.auto_offer_frame {
-        let should_install_frame = if with_frame {
-            true
-        } else {
-            // Interactive prompt
-            println!();
-            Confirm::new()
-                .with_prompt("Would you like to install Frame CLI as well?")
-                .default(true)
-                .interact()
-                .unwrap_or_default()
-        };
+    populate_version_dir(downloader, download_path, asset_name, &staging, progress)?;
 
-        if should_install_frame {
-            println!();
-            println!("Installing Frame CLI...");
-            match frame::install_frame(None, false) {
-                Ok(_) => {
-                    println!();
-                    println!("✅ Installation complete!");
-                    println!("   cln --version");
-                    println!("   frame --version");
-                }
-                Err(e) => {
-                    eprintln!();
-                    eprintln!("⚠️  Failed to install Frame CLI: {e}");
-                    eprintln!("   You can install it later with: cleen frame install");
-                }
-            }
+    if version_dir.exists() {
+        if let Err(e) = std::fs::rename(version_dir, &backup) {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e.into());
         }
     }
+    if let Err(e) = std::fs::rename(&staging, version_dir) {
+        let _ = std::fs::rename(&backup, version_dir);
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(e.into());
+    }
+    let _ = std::fs::remove_dir_all(&backup);
 
-    Ok(())
+    find_binary_in_dir(version_dir)
 }
 
-fn validate_installed_binary(binary_path: &std::path::Path) -> std::result::Result<(), String> {
+pub(crate) fn validate_installed_binary(
+    binary_path: &std::path::Path,
+) -> std::result::Result<(), String> {
     use std::process::Command;
 
     // Test 1: Check if binary exists and is executable
@@ -286,13 +937,22 @@ fn validate_installed_binary(binary_path: &std::path::Path) -> std::result::Resu
     let test_program = r#"start()
 	print("test")"#;
 
-    // Create a temporary test file
-    let temp_dir = std::env::temp_dir();
-    let test_file = temp_dir.join("cleen_test.cln");
-    let test_wasm = temp_dir.join("cleen_test.wasm");
+    // Create the test file in a scratch directory of its own, so installs
+    // validating at the same time don't overwrite or delete each other's
+    static SCRATCH_ID: AtomicUsize = AtomicUsize::new(0);
+    let scratch_dir = std::env::temp_dir().join(format!(
+        "cleen-validate-{}-{}",
+        std::process::id(),
+        SCRATCH_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    let test_file = scratch_dir.join("cleen_test.cln");
+    let test_wasm = scratch_dir.join("cleen_test.wasm");
 
     // Write test program
-    if let Err(e) = std::fs::write(&test_file, test_program) {
+    if let Err(e) = std::fs::create_dir_all(&scratch_dir)
+        .and_then(|()| std::fs::write(&test_file, test_program))
+    {
+        let _ = std::fs::remove_dir_all(&scratch_dir);
         return Err(format!("Failed to create test file: {e}"));
     }
 
@@ -306,8 +966,7 @@ fn validate_installed_binary(binary_path: &std::path::Path) -> std::result::Resu
         .output();
 
     // Clean up test files
-    let _ = std::fs::remove_file(&test_file);
-    let _ = std::fs::remove_file(&test_wasm);
+    let _ = std::fs::remove_dir_all(&scratch_dir);
 
     match compile_result {
         Ok(output) => {
@@ -347,10 +1006,10 @@ fn get_platform_suffix() -> String {
 }
 
 fn find_binary_in_dir(dir: &Path) -> Result<std::path::PathBuf> {
-    let binary_name = if cfg!(windows) { "cln.exe" } else { "cln" };
+    let binary_name = binaries::compiler();
 
     // Look for binary in the root directory first
-    let direct_path = dir.join(binary_name);
+    let direct_path = dir.join(&binary_name);
     if direct_path.exists() {
         return Ok(direct_path);
     }
@@ -364,7 +1023,7 @@ fn find_binary_in_dir(dir: &Path) -> Result<std::path::PathBuf> {
             if let Ok(found) = find_binary_in_dir(&path) {
                 return Ok(found);
             }
-        } else if path.file_name().and_then(|n| n.to_str()) == Some(binary_name) {
+        } else if path.file_name().and_then(|n| n.to_str()) == Some(binary_name.as_str()) {
             return Ok(path);
         }
     }
@@ -373,3 +1032,397 @@ fn find_binary_in_dir(dir: &Path) -> Result<std::path::PathBuf> {
         name: binary_name.to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn release(tag: &str, target: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            name: tag.to_string(),
+            prerelease,
+            draft: false,
+            target_commitish: target.to_string(),
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn commit_release_matches_prerelease_target_or_tag() {
+        let sha = "1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d";
+        let releases = vec![
+            release("v0.30.0", sha, false),
+            release("build-1a2b3c4", "main", true),
+            release("build-9f8e7d6", sha, true),
+        ];
+
+        let (found, full) = find_commit_release(releases.clone(), "1a2b3c4d").unwrap();
+        assert_eq!(found.tag_name, "build-9f8e7d6");
+        assert_eq!(full, sha);
+
+        let (found, short) = find_commit_release(releases[..2].to_vec(), "1a2b3c4").unwrap();
+        assert_eq!(found.tag_name, "build-1a2b3c4");
+        assert_eq!(version::nightly_version(&short), "nightly-1a2b3c4");
+
+        assert!(find_commit_release(releases, "ffffffff").is_none());
+    }
+
+    #[test]
+    fn failed_extraction_removes_version_dir() {
+        let tmp = TempDir::new().unwrap();
+        let download_path = tmp.path().join("cln-linux-x86_64.tar.gz");
+        std::fs::write(&download_path, b"not a gzip stream").unwrap();
+        let version_dir = tmp.path().join("versions").join("0.30.0");
+
+        let err = populate_version_dir(
+            &Downloader::new(),
+            &download_path,
+            "cln-linux-x86_64.tar.gz",
+            &version_dir,
+            &mut |_| {},
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, CleenError::ExtractionError { .. }));
+        assert!(!version_dir.exists(), "partial version dir must be removed");
+    }
+
+    #[test]
+    fn direct_binary_install_keeps_version_dir() {
+        let tmp = TempDir::new().unwrap();
+        let download_path = tmp.path().join("cln-any.bin");
+        std::fs::write(&download_path, b"#!/bin/sh\n").unwrap();
+        let version_dir = tmp.path().join("versions").join("0.30.0");
+
+        let binary = populate_version_dir(
+            &Downloader::new(),
+            &download_path,
+            "cln-any.bin",
+            &version_dir,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert!(binary.exists());
+        assert!(version_dir.exists(), "successful install keeps the dir");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn replace_version_dir_swaps_corrupt_binary_for_working_one() {
+        let tmp = TempDir::new().unwrap();
+        let version_dir = tmp.path().join("versions").join("0.30.0");
+        std::fs::create_dir_all(&version_dir).unwrap();
+        std::fs::write(version_dir.join("cln"), b"\0\0truncated").unwrap();
+
+        // Stands in for the re-downloaded release asset.
+        let download_path = tmp.path().join("cln-any");
+        std::fs::write(
+            &download_path,
+            "#!/bin/sh\necho 'Clean Language Compiler 0.30.0'\n",
+        )
+        .unwrap();
+
+        let binary = replace_version_dir(
+            &Downloader::new(),
+            &download_path,
+            "cln-any",
+            &version_dir,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(binary, version_dir.join("cln"));
+        let output = std::process::Command::new(&binary)
+            .arg("version")
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&output.stdout).contains("Clean Language Compiler"));
+
+        // Neither the staging dir nor the backup survives the swap.
+        let leftovers: Vec<_> = std::fs::read_dir(version_dir.parent().unwrap())
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(leftovers, vec!["0.30.0".to_string()]);
+    }
+
+    #[test]
+    fn replace_version_dir_keeps_original_when_download_is_bad() {
+        let tmp = TempDir::new().unwrap();
+        let version_dir = tmp.path().join("versions").join("0.30.0");
+        std::fs::create_dir_all(&version_dir).unwrap();
+        std::fs::write(version_dir.join("cln"), b"original").unwrap();
+
+        let download_path = tmp.path().join("cln-linux-x86_64.tar.gz");
+        std::fs::write(&download_path, b"not a gzip stream").unwrap();
+
+        assert!(replace_version_dir(
+            &Downloader::new(),
+            &download_path,
+            "cln-linux-x86_64.tar.gz",
+            &version_dir,
+            &mut |_| {},
+        )
+        .is_err());
+        assert_eq!(
+            std::fs::read(version_dir.join("cln")).unwrap(),
+            b"original".to_vec()
+        );
+    }
+
+    #[test]
+    fn existing_version_requires_force() {
+        let tmp = TempDir::new().unwrap();
+        let version_dir = tmp.path().join("0.30.0");
+
+        // Nothing installed yet: a plain install proceeds.
+        assert!(!check_existing_install(&version_dir, "0.30.0", false, &mut |_| {}).unwrap());
+
+        std::fs::create_dir_all(&version_dir).unwrap();
+        let err = check_existing_install(&version_dir, "0.30.0", false, &mut |_| {}).unwrap_err();
+        assert!(matches!(err, CleenError::VersionAlreadyInstalled { .. }));
+
+        assert!(check_existing_install(&version_dir, "0.30.0", true, &mut |_| {}).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn force_reinstall_over_existing_version_succeeds() {
+        let tmp = TempDir::new().unwrap();
+        let version_dir = tmp.path().join("versions").join("0.30.0");
+        std::fs::create_dir_all(&version_dir).unwrap();
+        std::fs::write(version_dir.join("cln"), b"stale").unwrap();
+        std::fs::write(version_dir.join("leftover.txt"), b"partial").unwrap();
+
+        let download_path = tmp.path().join("cln-any");
+        std::fs::write(&download_path, "#!/bin/sh\necho fresh\n").unwrap();
+
+        assert!(check_existing_install(&version_dir, "0.30.0", true, &mut |_| {}).unwrap());
+        replace_version_dir(
+            &Downloader::new(),
+            &download_path,
+            "cln-any",
+            &version_dir,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(version_dir.join("cln")).unwrap(),
+            "#!/bin/sh\necho fresh\n"
+        );
+        assert!(
+            !version_dir.join("leftover.txt").exists(),
+            "reinstall must not keep files from the old tree"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cached_archive_is_reused_without_network() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            cleen_dir: tmp.path().to_path_buf(),
+            // An unusable token makes any accidental GitHub call fail loudly.
+            github_api_token: Some("invalid".to_string()),
+            ..Config::default()
+        };
+
+        // What a first `cleen install 1.0.0 --keep-archive` leaves behind.
+        let archive = tmp.path().join("cln-any");
+        std::fs::write(&archive, "#!/bin/sh\necho cached\n").unwrap();
+        ArchiveCache::new(config.get_download_cache_dir())
+            .store("1.0.0", "cln-any", &archive)
+            .unwrap();
+
+        let first = install_compiler(&config, "1.0.0", false, false, &mut |_| {}).unwrap();
+        assert!(first.from_archive_cache);
+        assert_eq!(first.bytes, 0);
+        assert_eq!(
+            std::fs::read_to_string(config.get_version_binary("1.0.0")).unwrap(),
+            "#!/bin/sh\necho cached\n"
+        );
+
+        let second = install_compiler(&config, "v1.0.0", true, false, &mut |_| {}).unwrap();
+        assert!(second.from_archive_cache);
+        assert!(second.reinstalled);
+        assert!(ArchiveCache::new(config.get_download_cache_dir())
+            .lookup("1.0.0")
+            .is_some());
+    }
+
+    #[test]
+    fn cached_archive_that_fails_to_extract_is_dropped() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            cleen_dir: tmp.path().to_path_buf(),
+            // An unusable token makes any accidental GitHub call fail loudly.
+            github_api_token: Some("invalid".to_string()),
+            ..Config::default()
+        };
+        let cache = ArchiveCache::new(config.get_download_cache_dir());
+        let archive = tmp.path().join("cln-linux-x86_64.tar.gz");
+        std::fs::write(&archive, b"not a gzip stream").unwrap();
+        cache
+            .store("1.0.0", "cln-linux-x86_64.tar.gz", &archive)
+            .unwrap();
+
+        let err = install_compiler(&config, "1.0.0", false, false, &mut |_| {}).unwrap_err();
+        assert!(matches!(err, CleenError::ExtractionError { .. }), "{err}");
+        assert!(cache.lookup("1.0.0").is_none());
+        assert!(!config.get_version_dir("1.0.0").exists());
+    }
+
+    #[test]
+    fn concurrent_installs_report_each_version_independently() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            cleen_dir: tmp.path().to_path_buf(),
+            // An unusable token makes any accidental GitHub call fail loudly.
+            github_api_token: Some("invalid".to_string()),
+            ..Config::default()
+        };
+        let cache = ArchiveCache::new(config.get_download_cache_dir());
+        let archive = tmp.path().join("cln-any");
+        std::fs::write(&archive, "#!/bin/sh\necho cached\n").unwrap();
+        for version in ["1.0.0", "1.1.0"] {
+            cache.store(version, "cln-any", &archive).unwrap();
+        }
+        std::fs::create_dir_all(config.get_version_dir("0.9.0")).unwrap();
+
+        let versions = dedupe_install_specs(
+            &config,
+            &[
+                "v1.0.0".into(),
+                "0.9.0".into(),
+                "1.0.0".into(),
+                "1.1.0".into(),
+            ],
+        );
+        assert_eq!(versions, ["1.0.0", "0.9.0", "1.1.0"]);
+
+        let results = install_concurrently(&config, &versions, 2, false, false);
+        let order: Vec<&str> = results.iter().map(|(v, _)| v.as_str()).collect();
+        assert_eq!(order, ["1.0.0", "0.9.0", "1.1.0"]);
+        assert!(results[0]
+            .1
+            .as_ref()
+            .unwrap()
+            .starts_with("installed 1.0.0"));
+        assert!(matches!(
+            results[1].1,
+            Err(CleenError::VersionAlreadyInstalled { .. })
+        ));
+        assert!(results[2].1.is_ok());
+        assert!(config.get_version_binary("1.1.0").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn concurrent_validations_use_separate_test_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // A compiler that holds on to its input for a while, then fails if
+        // it has been removed or replaced in the meantime.
+        let tmp = TempDir::new().unwrap();
+        let binary = tmp.path().join("cln");
+        std::fs::write(
+            &binary,
+            "#!/bin/sh\n\
+             if [ \"$1\" = version ]; then echo 'Clean Language Compiler 1.0.0'; exit 0; fi\n\
+             grep -q start \"$2\" || exit 1\n\
+             sleep 0.3\n\
+             grep -q start \"$2\" || exit 1\n\
+             echo wasm > \"$3\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let results: Vec<_> = std::thread::scope(|scope| {
+            // The second starts while the first is still compiling, and
+            // finishes after the first has cleaned up.
+            let handles: Vec<_> = (0..2u64)
+                .map(|i| {
+                    let binary = &binary;
+                    scope.spawn(move || {
+                        std::thread::sleep(std::time::Duration::from_millis(150 * i));
+                        validate_installed_binary(binary)
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(results, [Ok(()), Ok(())]);
+    }
+
+    #[test]
+    fn archive_with_parent_path_entry_is_rejected_and_cleaned_up() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let tmp = TempDir::new().unwrap();
+        let download_path = tmp.path().join("cln-linux-x86_64.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            std::fs::File::create(&download_path).unwrap(),
+            Compression::default(),
+        ));
+        let body = b"pwned";
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..11].copy_from_slice(b"../evil.txt");
+        header.set_size(body.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, &body[..]).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let version_dir = tmp.path().join("versions").join("0.30.0");
+        let err = populate_version_dir(
+            &Downloader::new(),
+            &download_path,
+            "cln-linux-x86_64.tar.gz",
+            &version_dir,
+            &mut |_| {},
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, CleenError::UnsafeArchive { .. }));
+        assert!(!version_dir.exists());
+        assert!(!tmp.path().join("versions").join("evil.txt").exists());
+    }
+
+    #[test]
+    fn oversized_extraction_is_rejected_and_cleaned_up() {
+        use flate2::{write::GzEncoder, Compression};
+
+        // A few MiB of zeros compresses to a few KiB, far past the ratio.
+        let tmp = TempDir::new().unwrap();
+        let download_path = tmp.path().join("cln-linux-x86_64.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            std::fs::File::create(&download_path).unwrap(),
+            Compression::best(),
+        ));
+        let body = vec![0u8; 4 * 1024 * 1024];
+        let mut header = tar::Header::new_gnu();
+        header.set_size(body.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, "cln", &body[..]).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let version_dir = tmp.path().join("versions").join("0.30.0");
+        let err = populate_version_dir(
+            &Downloader::new(),
+            &download_path,
+            "cln-linux-x86_64.tar.gz",
+            &version_dir,
+            &mut |_| {},
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, CleenError::ArchiveTooLarge { .. }), "{err}");
+        assert!(!version_dir.exists());
+    }
+}
@@ -1,10 +1,33 @@
 use crate::commands::update;
 use crate::core::{config::Config, frame, version::VersionManager};
 use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// One compiler version in `cleen list --json`.
+#[derive(Debug, Serialize)]
+struct ListedVersion {
+    version: String,
+    active: bool,
+    valid: bool,
+    binary_path: PathBuf,
+}
+
+/// One Frame CLI version in `cleen list --frame --json`.
+#[derive(Debug, Serialize)]
+struct ListedFrameVersion {
+    version: String,
+    active: bool,
+}
 
-pub fn list_versions(show_frame: bool) -> Result<()> {
+pub fn list_versions(show_frame: bool, verbose: bool, json: bool) -> Result<()> {
     let config = Config::load()?;
 
+    if json {
+        return print_json(&config, show_frame);
+    }
+
     if show_frame {
         // List Frame CLI versions only
         let frame_versions = frame::list_frame_versions(&config)?;
@@ -63,6 +86,18 @@ pub fn list_versions(show_frame: bool) -> Result<()> {
         if !version_info.is_valid {
             println!("    Binary not found: {:?}", version_info.binary_path);
         }
+
+        if verbose {
+            let metadata = &version_info.metadata;
+            println!(
+                "    Installed: {}",
+                format_timestamp(metadata.installed_at.as_ref())
+            );
+            println!(
+                "    Last used: {}",
+                format_timestamp(metadata.last_used_at(&version_info.binary_path).as_ref())
+            );
+        }
     }
 
     println!();
@@ -102,3 +137,46 @@ pub fn list_versions(show_frame: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Print installed versions as a JSON array and nothing else, so the output
+/// can be piped straight into `jq`.
+fn print_json(config: &Config, show_frame: bool) -> Result<()> {
+    let json = if show_frame {
+        let versions: Vec<ListedFrameVersion> = frame::list_frame_versions(config)?
+            .into_iter()
+            .map(|version| ListedFrameVersion {
+                active: config.frame_version.as_deref() == Some(version.as_str()),
+                version,
+            })
+            .collect();
+        serde_json::to_string_pretty(&versions)?
+    } else {
+        let versions: Vec<ListedVersion> = VersionManager::new(config.clone())
+            .list_installed_versions()?
+            .into_iter()
+            .map(|info| ListedVersion {
+                version: info.version,
+                active: info.is_active,
+                valid: info.is_valid,
+                binary_path: info.binary_path,
+            })
+            .collect();
+        serde_json::to_string_pretty(&versions)?
+    };
+    println!("{json}");
+    Ok(())
+}
+
+/// Render a metadata timestamp for `list --verbose`, with a relative age.
+fn format_timestamp(timestamp: Option<&DateTime<Utc>>) -> String {
+    let Some(timestamp) = timestamp else {
+        return "unknown".to_string();
+    };
+    let days = (Utc::now() - *timestamp).num_days();
+    let age = match days {
+        d if d <= 0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        d => format!("{d} days ago"),
+    };
+    format!("{} ({age})", timestamp.format("%Y-%m-%d %H:%M UTC"))
+}
@@ -1,20 +1,30 @@
-use crate::core::{config::Config, version::VersionManager};
+use crate::core::{
+    config::Config,
+    version::{self, normalize, VersionManager, VersionMetadata, VersionRange},
+};
 use crate::error::{CleenError, Result};
 use std::env;
 
 pub fn set_local_version(version: &str) -> Result<()> {
+    // A range is pinned as written; anything else must be a safe version
+    let is_range = VersionRange::parse(version)?.is_some();
+    if !is_range {
+        version::validate_version(version)?;
+    }
+
     let config = Config::load()?;
     let version_manager = VersionManager::new(config.clone());
 
-    // Validate version format
-    version_manager.validate_version(version)?;
-
-    // Check if version is installed
-    if !version_manager.is_version_installed(version) {
+    // Check if version is installed (for a range, that something satisfies it)
+    let resolved = if is_range {
+        version_manager.resolve_version_spec(version)?
+    } else if version_manager.is_version_installed(version) {
+        version.to_string()
+    } else {
         return Err(CleenError::VersionNotFound {
             version: version.to_string(),
         });
-    }
+    };
 
     // Get current directory for display
     let current_dir = env::current_dir()?;
@@ -27,6 +37,7 @@ pub fn set_local_version(version: &str) -> Result<()> {
 
     // Create .cleanlanguage/.cleanversion file
     config.set_project_version(version)?;
+    VersionMetadata::record_use(&config.get_version_dir(&normalize::to_clean_version(&resolved)));
 
     println!();
     println!("💡 Usage:");
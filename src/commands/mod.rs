@@ -6,8 +6,10 @@ pub mod install;
 pub mod list;
 pub mod local;
 pub mod plugin;
+pub mod repair;
 pub mod sync;
 pub mod test;
 pub mod uninstall;
 pub mod update;
 pub mod use_version;
+pub mod which;
@@ -1,11 +1,12 @@
-use crate::core::config::{read_active_version, Config};
+use crate::core::config::Config;
 use crate::error::{CleenError, Result};
 use crate::plugin::manifest::PluginManifest;
 use crate::plugin::registry;
 use crate::plugin::scaffold;
 use crate::plugin::{
-    activate_plugin_version_root, get_plugin_versions, is_plugin_installed, list_installed_plugins,
-    parse_plugin_specifier, remove_plugin,
+    activate_plugin_version_root, check_plugin_compatibility, get_plugin_versions,
+    is_plugin_installed, list_installed_plugins, parse_plugin_specifier, remove_plugin,
+    resolve_plugin_version, validate_plugin_name,
 };
 use std::env;
 use std::path::Path;
@@ -15,7 +16,7 @@ use std::process::Command;
 pub fn install_plugin(specifier: &str) -> Result<()> {
     let mut config = Config::load()?;
 
-    let (name, version) = parse_plugin_specifier(specifier);
+    let (name, version) = parse_plugin_specifier(specifier)?;
 
     // Check if already installed
     if let Some(v) = &version {
@@ -28,6 +29,57 @@ pub fn install_plugin(specifier: &str) -> Result<()> {
     registry::install_from_registry(&mut config, &name, version.as_deref())
 }
 
+/// Install several plugins in sequence, continuing past individual
+/// failures. `local` treats every argument as a plugin directory.
+///
+/// A single argument behaves exactly like the one-plugin install; with more
+/// than one, a per-plugin summary is printed at the end and the command
+/// fails if any of them did.
+pub fn install_plugins(specifiers: &[String], local: bool) -> Result<()> {
+    let install_one = |spec: &str| {
+        if local {
+            install_local_plugin(Path::new(spec))
+        } else {
+            install_plugin(spec)
+        }
+    };
+
+    if let [spec] = specifiers {
+        return install_one(spec);
+    }
+
+    let mut results: Vec<(&str, Result<()>)> = Vec::new();
+    for spec in specifiers {
+        println!("📦 {spec}");
+        let result = install_one(spec);
+        if let Err(e) = &result {
+            eprintln!("❌ {spec}: {e}");
+        }
+        println!();
+        results.push((spec, result));
+    }
+
+    println!("Plugin install summary:");
+    let mut failed = Vec::new();
+    for (spec, result) in &results {
+        match result {
+            Ok(()) => println!("  ✅ {spec}"),
+            Err(e) => {
+                println!("  ❌ {spec}: {e}");
+                failed.push(spec.to_string());
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(CleenError::PluginInstallFailed {
+            names: failed.join(", "),
+        })
+    }
+}
+
 /// Install a plugin from a local directory
 pub fn install_local_plugin(path: &Path) -> Result<()> {
     let mut config = Config::load()?;
@@ -53,6 +105,8 @@ pub fn list_plugins() -> Result<()> {
     println!("Installed plugins:");
     println!();
 
+    let cwd = env::current_dir().ok();
+
     // Group by plugin name
     let mut current_name = String::new();
     for plugin in &plugins {
@@ -64,7 +118,7 @@ pub fn list_plugins() -> Result<()> {
             current_name = plugin.name.clone();
         }
 
-        let active = read_active_version(&config, &plugin.name);
+        let active = resolve_plugin_version(&config, &plugin.name, cwd.as_deref());
         let marker = if active.as_deref() == Some(plugin.version.as_str()) {
             "* "
         } else {
@@ -88,20 +142,7 @@ pub fn list_plugins() -> Result<()> {
 /// Create a new plugin project
 pub fn create_plugin(name: &str) -> Result<()> {
     // Validate the name
-    if name.is_empty() {
-        return Err(CleenError::PluginManifestError {
-            message: "Plugin name cannot be empty".to_string(),
-        });
-    }
-
-    if !name
-        .chars()
-        .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
-    {
-        return Err(CleenError::PluginManifestError {
-            message: "Plugin name can only contain alphanumeric characters, dots, hyphens, and underscores".to_string(),
-        });
-    }
+    validate_plugin_name(name)?;
 
     scaffold::create_plugin_project(name, None)
 }
@@ -177,6 +218,38 @@ pub fn build_plugin() -> Result<()> {
     }
 }
 
+/// Search the registry for plugins matching `query`
+pub fn search_plugins(query: &str) -> Result<()> {
+    let client = registry::RegistryClient::new();
+    let matches = match client.search(query) {
+        Ok(matches) => matches,
+        Err(e) => {
+            println!("Note: Plugin registry is not yet available.");
+            println!("Installed plugins are listed by: cleen plugin list");
+            println!();
+            return Err(e);
+        }
+    };
+
+    if matches.is_empty() {
+        println!("No plugins found matching '{query}'");
+        return Ok(());
+    }
+
+    let name_width = matches.iter().map(|p| p.name.len()).max().unwrap_or(0);
+    let version_width = matches.iter().map(|p| p.version.len()).max().unwrap_or(0);
+    for plugin in &matches {
+        let line = format!(
+            "{:<name_width$}  {:<version_width$}  {}",
+            plugin.name,
+            plugin.version,
+            plugin.description.as_deref().unwrap_or("")
+        );
+        println!("{}", line.trim_end());
+    }
+    Ok(())
+}
+
 /// Publish a plugin to the registry
 pub fn publish_plugin() -> Result<()> {
     let current_dir = env::current_dir()?;
@@ -209,6 +282,7 @@ pub fn publish_plugin() -> Result<()> {
 
 /// Remove a plugin
 pub fn remove_plugin_command(name: &str) -> Result<()> {
+    validate_plugin_name(name)?;
     let config = Config::load()?;
 
     // Check if plugin exists
@@ -229,8 +303,59 @@ pub fn remove_plugin_command(name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Use a specific version of a plugin
-pub fn use_plugin_version(name: &str, version: &str) -> Result<()> {
+/// Check an installed plugin version against the active compiler before
+/// activating it. Incompatibility is a warning unless `strict` is set, in
+/// which case the `PluginIncompatible` or `PluginCompilerTooNew` error is
+/// returned.
+fn verify_plugin_compatibility(
+    config: &Config,
+    name: &str,
+    version: &str,
+    strict: bool,
+) -> Result<()> {
+    let manifest_path = config.get_plugin_manifest_path(name, version);
+    let manifest = PluginManifest::load(&manifest_path)?;
+
+    match check_plugin_compatibility(config, &manifest) {
+        Ok(()) => Ok(()),
+        Err(CleenError::NoCompilerForPlugin) => {
+            println!("⚠️  No compiler is active; skipping compatibility check for {name}");
+            Ok(())
+        }
+        Err(
+            e @ (CleenError::PluginIncompatible { .. } | CleenError::PluginCompilerTooNew { .. }),
+        ) if strict => Err(e),
+        Err(CleenError::PluginIncompatible { .. } | CleenError::PluginCompilerTooNew { .. }) => {
+            let compat = &manifest.compatibility;
+            let range = match (&compat.min_compiler_version, &compat.max_compiler_version) {
+                (Some(min), Some(max)) => format!(">= {min}, <= {max}"),
+                (Some(min), None) => format!(">= {min}"),
+                (None, Some(max)) => format!("<= {max}"),
+                (None, None) => "any".to_string(),
+            };
+            let current = config.active_version.as_deref().unwrap_or("none");
+            println!("⚠️  {name} {version} requires compiler {range} (active: {current})");
+            println!("   Activating anyway; builds using this plugin may fail.");
+            println!("   Use --strict to refuse incompatible versions.");
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Use a specific version of a plugin, globally or (with `local`) for the
+/// project in the current directory
+pub fn use_plugin_version(name: &str, version: &str, strict: bool, local: bool) -> Result<()> {
+    // `cleen plugin use frame.web@1.0.0 ...` reads naturally but puts the
+    // version in the wrong argument; say so instead of "invalid name".
+    if name.contains('@') {
+        return Err(CleenError::InvalidPluginSpecifier {
+            specifier: name.to_string(),
+            reason: "pass the plugin name and version as separate arguments".to_string(),
+        });
+    }
+    validate_plugin_name(name)?;
+    crate::core::version::validate_version(version)?;
     let config = Config::load()?;
 
     // Check if version is installed
@@ -255,6 +380,22 @@ pub fn use_plugin_version(name: &str, version: &str) -> Result<()> {
         }
     }
 
+    verify_plugin_compatibility(&config, name, version, strict)?;
+
+    if local {
+        let file = config.set_project_plugin_version(&env::current_dir()?, name, version)?;
+        println!("Now using {} version {} in this project", name, version);
+        println!("   Pinned in {}", file.display());
+        if let Some(global) =
+            crate::core::config::read_active_version(&config, name).filter(|v| v != version)
+        {
+            println!(
+                "   Note: the compiler doesn't read plugins.toml yet, so builds still use {name} {global}"
+            );
+        }
+        return Ok(());
+    }
+
     // `activate_plugin_version_root` writes `.active-version` — the single
     // source of truth for plugin pins (see HOST_BRIDGE.md "Plugin Pin
     // Resolution").
@@ -264,3 +405,82 @@ pub fn use_plugin_version(name: &str, version: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn test_config(cleen_dir: &Path, active_version: Option<&str>) -> Config {
+        Config {
+            active_version: active_version.map(str::to_string),
+            cleen_dir: cleen_dir.to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    fn install_plugin_requiring(config: &Config, name: &str, version: &str, min: &str) {
+        let dir = config.get_plugin_version_dir(name, version);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("plugin.toml"),
+            format!(
+                "[plugin]\nname = \"{name}\"\nversion = \"{version}\"\n\n\
+                 [compatibility]\nmin_compiler_version = \"{min}\"\n"
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("plugin.wasm"), b"\0asm").unwrap();
+    }
+
+    #[test]
+    fn use_rejects_name_with_version_attached() {
+        for name in ["frame.web@1.0.0", "frame.web@", "@1.0.0"] {
+            assert!(
+                matches!(
+                    use_plugin_version(name, "1.0.0", false, false),
+                    Err(CleenError::InvalidPluginSpecifier { .. })
+                ),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn incompatible_plugin_warns_by_default_and_errors_when_strict() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(tmp.path(), Some("0.10.0"));
+        install_plugin_requiring(&config, "frame.web", "2.0.0", "0.15.0");
+
+        assert!(verify_plugin_compatibility(&config, "frame.web", "2.0.0", false).is_ok());
+
+        match verify_plugin_compatibility(&config, "frame.web", "2.0.0", true) {
+            Err(CleenError::PluginIncompatible {
+                required, current, ..
+            }) => {
+                assert_eq!(required, "0.15.0");
+                assert_eq!(current, "0.10.0");
+            }
+            other => panic!("expected PluginIncompatible, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn compatible_plugin_passes_strict_check() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(tmp.path(), Some("0.20.0"));
+        install_plugin_requiring(&config, "frame.web", "2.0.0", "0.15.0");
+
+        assert!(verify_plugin_compatibility(&config, "frame.web", "2.0.0", true).is_ok());
+    }
+
+    #[test]
+    fn missing_compiler_skips_check() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(tmp.path(), None);
+        install_plugin_requiring(&config, "frame.web", "2.0.0", "0.15.0");
+
+        assert!(verify_plugin_compatibility(&config, "frame.web", "2.0.0", true).is_ok());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::commands::install;
+use crate::core::{
+    config::Config,
+    download::Downloader,
+    github::GitHubClient,
+    shim::ShimManager,
+    version::{self, normalize, VersionManager, VersionMetadata},
+};
+use crate::error::{CleenError, Result};
+
+/// Re-download and re-extract an installed compiler version in place.
+///
+/// The version directory is swapped atomically (see
+/// [`install::replace_version_dir`]) so an interrupted repair never leaves
+/// the version missing, and the active shim is re-pointed afterwards if the
+/// repaired version is the active one.
+pub fn repair_version(version: &str) -> Result<()> {
+    version::validate_version(version)?;
+
+    let config = Config::load()?;
+    let clean_version = normalize::to_clean_version(version);
+    let version_dir = config.get_version_dir(&clean_version);
+
+    if !version_dir.exists() {
+        return Err(CleenError::VersionNotFound {
+            version: clean_version,
+        });
+    }
+    if version::is_nightly(&clean_version) {
+        return Err(nightly_repair_error(&clean_version));
+    }
+
+    println!("🔧 Repairing Clean Language version {clean_version}");
+
+    // A repair is not a fresh install; carry the dates over the swap.
+    let metadata = VersionMetadata::load(&version_dir);
+
+    let github_client = GitHubClient::from_config(&config);
+    let downloader = Downloader::new();
+    let github_version = normalize::to_github_version(&clean_version);
+
+    let progress = &mut |line: &str| println!("{line}");
+
+    let (asset, checksum) =
+        install::fetch_compiler_asset(&github_client, &github_version, &clean_version, progress)?;
+    crate::utils::fs::ensure_install_space(&version_dir, asset.size)?;
+    let (temp_dir, download_path) = install::download_compiler_asset(
+        &downloader,
+        &asset,
+        checksum.as_ref(),
+        &clean_version,
+        progress,
+    )?;
+
+    let result = install::replace_version_dir(
+        &downloader,
+        &download_path,
+        &asset.name,
+        &version_dir,
+        progress,
+    );
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    let binary_path = result?;
+
+    crate::utils::fs::strip_macos_xattrs_recursive(&version_dir);
+    let _ = metadata.save(&version_dir);
+
+    if config.active_version.as_deref() == Some(clean_version.as_str()) {
+        ShimManager::new(config.clone()).create_shim(&clean_version)?;
+    }
+
+    print!("🔍 Validating installation...");
+    if let Err(e) = install::validate_installed_binary(&binary_path) {
+        println!();
+        eprintln!("⚠️  Warning: Repaired binary may still have issues: {e}");
+    } else {
+        println!(" ✅");
+    }
+
+    println!("✅ Repaired Clean Language version {clean_version}");
+    Ok(())
+}
+
+/// Nightly builds are stored by commit, not by release tag, so there is no
+/// tag to re-download them from; point at the install that recreates one.
+fn nightly_repair_error(version: &str) -> CleenError {
+    CleenError::NightlyRepairUnsupported {
+        version: version.to_string(),
+        commit: version
+            .strip_prefix(version::NIGHTLY_PREFIX)
+            .unwrap_or(version)
+            .to_string(),
+    }
+}
+
+/// Repair every installed version that `cleen list` reports as invalid.
+pub fn repair_all() -> Result<()> {
+    let config = Config::load()?;
+    let invalid: Vec<String> = VersionManager::new(config)
+        .list_installed_versions()?
+        .into_iter()
+        .filter(|v| !v.is_valid)
+        .map(|v| v.version)
+        .collect();
+
+    let (nightly, invalid): (Vec<String>, Vec<String>) =
+        invalid.into_iter().partition(|v| version::is_nightly(v));
+    for version in &nightly {
+        println!("⚠️  Skipping {version}: {}", nightly_repair_error(version));
+    }
+
+    if invalid.is_empty() {
+        if nightly.is_empty() {
+            println!("✅ All installed versions are valid, nothing to repair");
+        }
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    for version in &invalid {
+        if let Err(e) = repair_version(version) {
+            eprintln!("❌ Failed to repair {version}: {e}");
+            failed.push(version.clone());
+        }
+        println!();
+    }
+
+    if failed.is_empty() {
+        println!("✅ Repaired {} version(s)", invalid.len());
+        Ok(())
+    } else {
+        Err(CleenError::RepairFailed {
+            versions: failed.join(", "),
+        })
+    }
+}
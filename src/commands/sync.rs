@@ -1,5 +1,8 @@
 use crate::commands::install;
-use crate::core::{config::Config, version::VersionManager};
+use crate::core::{
+    config::Config,
+    version::{VersionManager, VersionRange},
+};
 use crate::error::{CleenError, Result};
 use std::env;
 
@@ -24,6 +27,28 @@ pub fn sync_project_version() -> Result<()> {
         Some(project_version) => {
             println!("📋 Found .cleanlanguage/.cleanversion file specifying: {project_version}");
 
+            // A range can't be installed directly; it's satisfied by an
+            // installed version or reported with what was considered.
+            if VersionRange::parse(&project_version)?.is_some() {
+                match version_manager.resolve_version_spec(&project_version) {
+                    Ok(resolved) => {
+                        println!("✅ Range is satisfied by installed version {resolved}");
+                        println!();
+                        println!("🎉 Project is ready to use!");
+                        println!("   Run 'cln --version' to verify");
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        println!("❌ {e}");
+                        println!();
+                        println!("💡 Install a version that satisfies the range:");
+                        println!("  cleen available    # Check available versions");
+                        println!("  cleen install <version>");
+                        return Err(e);
+                    }
+                }
+            }
+
             // Check if version is already installed
             if version_manager.is_version_installed(&project_version) {
                 println!("✅ Version {project_version} is already installed");
@@ -35,7 +60,7 @@ pub fn sync_project_version() -> Result<()> {
                 println!();
 
                 // Install the version (skip Frame prompt during sync)
-                match install::install_version(&project_version, false, true) {
+                match install::install_version(&project_version, false, true, false, false, false) {
                     Ok(_) => {
                         println!();
                         println!("🎉 Successfully synced project version!");
@@ -1,9 +1,18 @@
-use crate::core::{compatibility, config::Config, frame};
+use crate::core::{
+    compatibility,
+    config::Config,
+    frame,
+    version::{self, VersionManager},
+};
 use crate::error::{CleenError, Result};
+use crate::utils::fs::{self, format_size};
 use dialoguer::Confirm;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 
 pub fn uninstall_version(version: &str, is_frame: bool, force: bool) -> Result<()> {
+    // A path-like version would point `remove_dir_all` outside ~/.cleen.
+    version::validate_version(version)?;
+
     if is_frame {
         // Uninstall Frame CLI
         return frame::uninstall_frame_version(version);
@@ -125,3 +134,92 @@ pub fn uninstall_version(version: &str, is_frame: bool, force: bool) -> Result<(
 
     Ok(())
 }
+
+/// Remove every installed compiler version. The active version is kept
+/// unless `force` is set; the prompt is skipped with `yes` or when stdout
+/// isn't a terminal.
+pub fn uninstall_all_versions(force: bool, yes: bool) -> Result<()> {
+    let mut config = Config::load()?;
+    let installed: Vec<String> = VersionManager::new(config.clone())
+        .list_installed_versions()?
+        .into_iter()
+        .map(|v| v.version)
+        .collect();
+
+    if installed.is_empty() {
+        println!("No Clean Language versions installed.");
+        return Ok(());
+    }
+
+    let active = config.active_version.clone();
+    let (to_remove, kept): (Vec<_>, Vec<_>) = installed
+        .into_iter()
+        .partition(|v| force || active.as_deref() != Some(v.as_str()));
+
+    for version in &kept {
+        println!("Skipping active version {version} (use --force to remove it too)");
+    }
+    if to_remove.is_empty() {
+        println!("No versions to remove.");
+        return Ok(());
+    }
+
+    let sized: Vec<(String, u64)> = to_remove
+        .into_iter()
+        .map(|v| {
+            let size = fs::dir_size(&config.get_version_dir(&v));
+            (v, size)
+        })
+        .collect();
+    let total: u64 = sized.iter().map(|(_, size)| size).sum();
+
+    println!(
+        "Uninstalling {} version(s) ({}):",
+        sized.len(),
+        format_size(total)
+    );
+    for (version, size) in &sized {
+        println!("  • {version} ({})", format_size(*size));
+    }
+    println!();
+
+    if !yes && io::stdout().is_terminal() {
+        let confirmed = Confirm::new()
+            .with_prompt("Uninstall these versions?")
+            .default(false)
+            .interact()
+            .unwrap_or_default();
+        if !confirmed {
+            println!("Uninstall cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut removed = Vec::new();
+    let mut freed_bytes = 0u64;
+    for (version, size) in sized {
+        match std::fs::remove_dir_all(config.get_version_dir(&version)) {
+            Ok(()) => {
+                freed_bytes += size;
+                removed.push(version);
+            }
+            Err(e) => println!("⚠️  Failed to remove {version}: {e}"),
+        }
+    }
+
+    if active.as_ref().is_some_and(|a| removed.contains(a)) {
+        config.clear_active_version()?;
+        println!("Cleared active version setting.");
+    }
+
+    println!(
+        "✅ Removed {} version(s), freed {}",
+        removed.len(),
+        format_size(freed_bytes)
+    );
+    for version in &removed {
+        println!("  • {version}");
+    }
+
+    Ok(())
+}
@@ -1,11 +1,15 @@
-use crate::core::{config::Config, download::Downloader, github::GitHubClient};
+use crate::core::{
+    config::Config,
+    download::{Downloader, DEFAULT_DOWNLOAD_ATTEMPTS},
+    github::GitHubClient,
+};
 use crate::error::{CleenError, Result};
 use std::{env, fs, path::Path};
 
 pub fn update_self_auto() -> Result<()> {
     println!("🔄 Checking for cleen updates...");
 
-    let github = GitHubClient::new(None);
+    let github = GitHubClient::from_config(&Config::load()?);
     let releases = github.get_releases("Ivan-Pasco", "clean-language-manager")?;
 
     if releases.is_empty() {
@@ -62,16 +66,26 @@ fn perform_auto_update(release: &crate::core::github::Release) -> Result<()> {
 
     // Download new version
     let temp_dir = env::temp_dir().join(format!("cleen-update-{}", release.tag_name));
+    // Start from an empty dir: resume state is only meaningful within a
+    // single run, and a leftover file from an earlier crash may be stale.
+    let _ = fs::remove_dir_all(&temp_dir);
     fs::create_dir_all(&temp_dir)?;
 
-    // Ensure temp dir is cleaned up on any error
+    // Ensure temp dir is cleaned up on any error. Retries happen inside
+    // this closure so the partial download in `temp_dir` survives between
+    // attempts and each retry resumes instead of starting over.
     let result = (|| -> Result<()> {
         let downloader = Downloader::new();
         let download_path = temp_dir.join(&asset.name);
 
         println!("⬇️  Downloading {}...", asset.name);
         downloader
-            .download_file(&asset.browser_download_url, &download_path)
+            .download_file_with_retry(
+                &asset.browser_download_url,
+                &download_path,
+                DEFAULT_DOWNLOAD_ATTEMPTS,
+                &mut |line| println!("{line}"),
+            )
             .map_err(|e| CleenError::UpdateError {
                 message: format!("Failed to download update: {}", e),
             })?;
@@ -186,11 +200,11 @@ fn prepare_new_binary(
 ) -> Result<std::path::PathBuf> {
     let binary_name = if cfg!(windows) { "cleen.exe" } else { "cleen" };
 
-    if asset_name.ends_with(".tar.gz") || asset_name.ends_with(".zip") {
+    if crate::core::download::is_archive(asset_name) {
         println!("📦 Extracting archive...");
         let downloader = Downloader::new();
         downloader
-            .extract_archive(download_path, temp_dir)
+            .extract_archive(download_path, temp_dir, &mut |line| println!("{line}"))
             .map_err(|_| CleenError::UpdateError {
                 message: "Failed to extract archive".to_string(),
             })?;
@@ -249,28 +263,33 @@ fn replace_current_binary(
 
     #[cfg(windows)]
     {
-        // On Windows, we can't replace a running executable directly
-        // We need to use a different approach
-        let temp_name = format!("{}.old", current_exe.to_string_lossy());
-        let temp_path = Path::new(&temp_name);
+        // The running image is locked on Windows: it can be renamed but
+        // not overwritten or deleted while this process is alive, and a
+        // copy over it fails outright. Stage the new binary next to the
+        // target and hand the swap to a detached batch script that waits
+        // for this process to exit. See `windows_swap_script`.
+        let staged = windows_staged_path(current_exe);
+        fs::copy(new_binary, &staged).map_err(|e| CleenError::UpdateError {
+            message: format!("Failed to stage new binary: {}", e),
+        })?;
 
-        // Move current exe to temp location
-        fs::rename(current_exe, temp_path)?;
+        let script_path =
+            env::temp_dir().join(format!("cleen-self-update-{}.bat", std::process::id()));
+        let script = windows_swap_script(current_exe, &staged, std::process::id());
+        fs::write(&script_path, script)?;
+
+        std::process::Command::new("cmd")
+            .args(["/C", "start", "", "/MIN"])
+            .arg(&script_path)
+            .spawn()
+            .map_err(|e| {
+                let _ = fs::remove_file(&staged);
+                CleenError::UpdateError {
+                    message: format!("Failed to schedule binary replacement: {}", e),
+                }
+            })?;
 
-        // Copy new binary to current location
-        match fs::copy(new_binary, current_exe) {
-            Ok(_) => {
-                // Success - remove old binary
-                let _ = fs::remove_file(temp_path);
-            }
-            Err(e) => {
-                // Failed - restore original
-                let _ = fs::rename(temp_path, current_exe);
-                return Err(CleenError::UpdateError {
-                    message: format!("Failed to replace binary: {}", e),
-                });
-            }
-        }
+        println!("⏳ The new binary will be swapped in once this process exits");
     }
 
     #[cfg(unix)]
@@ -296,6 +315,52 @@ fn replace_current_binary(
     Ok(())
 }
 
+/// Path the new binary is staged at before the Windows swap script moves
+/// it into place: `cleen.exe` → `cleen.exe.new`.
+pub fn windows_staged_path(current_exe: &Path) -> std::path::PathBuf {
+    let mut name = current_exe.as_os_str().to_owned();
+    name.push(".new");
+    std::path::PathBuf::from(name)
+}
+
+/// Build the batch script that completes a Windows self-update.
+///
+/// The script polls `tasklist` until `parent_pid` has exited, moves the
+/// old executable to `<exe>.old`, moves `staged` into place, and deletes
+/// `.old`. If the second move fails the old executable is restored so the
+/// user is never left without a `cleen`. On success it relaunches the new
+/// binary with `--version` as a visible confirmation, then deletes
+/// itself. Any `.old` left behind by the previous rename-based updater is
+/// removed first.
+pub fn windows_swap_script(current_exe: &Path, staged: &Path, parent_pid: u32) -> String {
+    let target = current_exe.display();
+    let staged = staged.display();
+    let old = format!("{}.old", current_exe.display());
+    format!(
+        r#"@echo off
+setlocal
+del /F /Q "{old}" >NUL 2>&1
+:wait
+tasklist /FI "PID eq {parent_pid}" 2>NUL | find "{parent_pid}" >NUL
+if not errorlevel 1 (
+    ping -n 2 127.0.0.1 >NUL
+    goto wait
+)
+move /Y "{target}" "{old}" >NUL
+if errorlevel 1 goto cleanup
+move /Y "{staged}" "{target}" >NUL
+if errorlevel 1 (
+    move /Y "{old}" "{target}" >NUL
+    goto cleanup
+)
+del /F /Q "{old}" >NUL 2>&1
+"{target}" --version
+:cleanup
+(goto) 2>NUL & del "%~f0"
+"#
+    )
+}
+
 fn validate_new_binary(binary_path: &Path) -> Result<()> {
     use std::process::Command;
 
@@ -350,7 +415,8 @@ fn validate_new_binary(binary_path: &Path) -> Result<()> {
 pub fn check_for_updates() -> Result<()> {
     println!("🔄 Checking for Clean Language compiler updates...");
 
-    let github = GitHubClient::new(None);
+    let config = Config::load()?;
+    let github = GitHubClient::from_config(&config);
     let releases = github.get_releases("Ivan-Pasco", "clean-language-compiler")?;
 
     if releases.is_empty() {
@@ -358,7 +424,6 @@ pub fn check_for_updates() -> Result<()> {
         return Ok(());
     }
 
-    let config = Config::load()?;
     let latest_release = &releases[0];
 
     match &config.active_version {
@@ -408,3 +473,37 @@ pub fn check_updates_if_needed() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_staged_path_appends_new_suffix() {
+        let staged = windows_staged_path(Path::new(r"C:\Users\me\.cleen\bin\cleen.exe"));
+        assert_eq!(staged, Path::new(r"C:\Users\me\.cleen\bin\cleen.exe.new"));
+    }
+
+    #[test]
+    fn windows_swap_script_waits_swaps_and_cleans_up() {
+        let exe = Path::new(r"C:\bin\cleen.exe");
+        let staged = Path::new(r"C:\bin\cleen.exe.new");
+        let script = windows_swap_script(exe, staged, 4242);
+
+        let wait = script.find(r#"tasklist /FI "PID eq 4242""#).unwrap();
+        let swap_out = script
+            .find(r#"move /Y "C:\bin\cleen.exe" "C:\bin\cleen.exe.old""#)
+            .unwrap();
+        let swap_in = script
+            .find(r#"move /Y "C:\bin\cleen.exe.new" "C:\bin\cleen.exe""#)
+            .unwrap();
+        let relaunch = script.find(r#""C:\bin\cleen.exe" --version"#).unwrap();
+
+        // Order matters: never touch the exe before the parent has exited.
+        assert!(wait < swap_out && swap_out < swap_in && swap_in < relaunch);
+        // A failed swap must restore the original binary.
+        assert!(script.contains(r#"move /Y "C:\bin\cleen.exe.old" "C:\bin\cleen.exe""#));
+        assert!(script.contains(r#"del /F /Q "C:\bin\cleen.exe.old""#));
+        assert!(script.contains(r#"del "%~f0""#));
+    }
+}
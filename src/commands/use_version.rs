@@ -2,11 +2,14 @@ use crate::core::{
     config::Config,
     frame,
     shim::ShimManager,
-    version::{normalize, VersionManager},
+    version::{self, normalize, VersionManager},
 };
 use crate::error::{CleenError, Result};
 
 pub fn use_version(version: &str, is_frame: bool) -> Result<()> {
+    // Validate version format before it is used as a path component
+    version::validate_version(version)?;
+
     if is_frame {
         // Switch Frame CLI version
         return frame::use_frame_version(version);
@@ -19,9 +22,6 @@ pub fn use_version(version: &str, is_frame: bool) -> Result<()> {
     // Normalize the version to clean format
     let clean_version = normalize::to_clean_version(version);
 
-    // Validate version format
-    version_manager.validate_version(&clean_version)?;
-
     // Check if version is installed (using clean version)
     if !version_manager.is_version_installed(&clean_version) {
         return Err(CleenError::VersionNotFound {
@@ -0,0 +1,38 @@
+use crate::core::{config::Config, shim};
+use crate::error::{CleenError, Result};
+use std::env;
+use std::path::PathBuf;
+
+/// Print the absolute path of the binary `cln` (or, with `frame`, `frame`)
+/// would run in the current directory, and nothing else.
+pub fn which_binary(frame: bool) -> Result<()> {
+    let config = Config::load()?;
+    let binary = if frame {
+        frame_binary(&config)?
+    } else {
+        compiler_binary(&config)?
+    };
+    println!("{}", binary.canonicalize().unwrap_or(binary).display());
+    Ok(())
+}
+
+/// The compiler the shim resolves from here: the project's pin, else the
+/// active version.
+fn compiler_binary(config: &Config) -> Result<PathBuf> {
+    let cwd = env::current_dir().ok();
+    shim::resolve_shim_target(config, cwd.as_deref())
+}
+
+fn frame_binary(config: &Config) -> Result<PathBuf> {
+    let version = config
+        .frame_version
+        .as_ref()
+        .ok_or(CleenError::NoActiveFrameVersion)?;
+    let binary = config.get_frame_version_binary(version);
+    if !binary.exists() {
+        return Err(CleenError::FrameVersionNotFound {
+            frame_version: version.clone(),
+        });
+    }
+    Ok(binary)
+}
@@ -0,0 +1,97 @@
+//! Names of the executables cleen installs, links and looks for.
+//!
+//! Every path helper goes through these functions rather than spelling out
+//! `cln`/`cln.exe` itself, so a release that renames its binary only needs
+//! the constant (or the `CLEEN_CLN_BINARY` override) to change.
+
+/// Environment variable overriding the compiler binary name inside a
+/// version directory, e.g. `CLEEN_CLN_BINARY=clean` for a release that
+/// ships `clean` instead of `cln`. Give the bare name; `.exe` is added on
+/// Windows. The user-facing `cln` shim keeps its name either way.
+pub const CLN_BINARY_ENV: &str = "CLEEN_CLN_BINARY";
+
+/// Compiler binary shipped in compiler releases.
+pub const CLN: &str = "cln";
+/// Name of the compiler shim in `~/.cleen/bin`.
+pub const CLN_SHIM: &str = "cln";
+/// Language server shipped alongside the compiler.
+pub const LSP: &str = "clean-language-server";
+/// Frame CLI binary shipped in framework releases.
+pub const FRAME: &str = "frame";
+/// Framework toolchain binary the Frame commands delegate to.
+pub const FRAME_CLI: &str = "frame-cli";
+/// Server runtime used by `frame serve`.
+pub const FRAME_RUNTIME: &str = "frame-runtime";
+
+/// Platform file name for an executable stem.
+pub fn executable(stem: &str) -> String {
+    if cfg!(windows) {
+        format!("{stem}.exe")
+    } else {
+        stem.to_string()
+    }
+}
+
+/// File name of the compiler binary inside a version directory, honouring
+/// [`CLN_BINARY_ENV`]. An override that isn't a plain file name is ignored.
+pub fn compiler() -> String {
+    let stem = std::env::var(CLN_BINARY_ENV)
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| is_plain_name(name))
+        .unwrap_or_else(|| CLN.to_string());
+    executable(&stem)
+}
+
+/// File name of the `cln` shim in `~/.cleen/bin`.
+pub fn compiler_shim() -> String {
+    executable(CLN_SHIM)
+}
+
+pub fn language_server() -> String {
+    executable(LSP)
+}
+
+pub fn frame() -> String {
+    executable(FRAME)
+}
+
+pub fn frame_cli() -> String {
+    executable(FRAME_CLI)
+}
+
+pub fn frame_runtime() -> String {
+    executable(FRAME_RUNTIME)
+}
+
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', ':'])
+        && !name.chars().any(|c| c.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn executable_adds_platform_suffix() {
+        let name = executable(CLN);
+        if cfg!(windows) {
+            assert_eq!(name, "cln.exe");
+        } else {
+            assert_eq!(name, "cln");
+        }
+    }
+
+    #[test]
+    fn override_must_be_a_plain_file_name() {
+        assert!(is_plain_name("clean"));
+        assert!(is_plain_name("cln-next"));
+        for bad in ["", ".", "..", "../cln", "bin/cln", "C:cln", "a\\b"] {
+            assert!(!is_plain_name(bad), "{bad:?} should be rejected");
+        }
+    }
+}
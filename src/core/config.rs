@@ -1,8 +1,32 @@
+use crate::core::binaries;
 use crate::error::{CleenError, Result};
 use crate::utils::fs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Project-level plugin pins, stored beside `.cleanversion` in
+/// `.cleanlanguage/`.
+pub const PROJECT_PLUGINS_FILE: &str = "plugins.toml";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProjectPlugins {
+    #[serde(default)]
+    plugins: BTreeMap<String, String>,
+}
+
+impl ProjectPlugins {
+    fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| CleenError::ConfigError {
+            message: format!("Invalid {}: {e}", path.display()),
+        })
+    }
+}
 
 // Note: the legacy `active_plugins` map was removed. `.active-version`
 // files under each plugin dir are now the single source of truth (see
@@ -26,6 +50,19 @@ pub struct Config {
     pub auto_offer_frame: bool,
     pub last_update_check: Option<String>,
     pub last_self_update_check: Option<String>,
+    /// Let the `cln` shim honour a project's `.cleanlanguage/.cleanversion`
+    /// pin. When off, `cln` always runs the global active version.
+    #[serde(default = "default_true")]
+    pub shim_project_version: bool,
+    /// Keep every downloaded compiler archive under `~/.cleen/cache/downloads`,
+    /// as if `--keep-archive` were always passed.
+    #[serde(default)]
+    pub keep_archives: bool,
+    /// GitHub API endpoint releases are fetched from, e.g.
+    /// `https://github.example.com/api/v3` for a GitHub Enterprise mirror.
+    /// `None` uses the public API.
+    #[serde(default)]
+    pub github_api_base_url: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -47,6 +84,9 @@ impl Default for Config {
             auto_offer_frame: true,
             last_update_check: None,
             last_self_update_check: None,
+            shim_project_version: true,
+            keep_archives: false,
+            github_api_base_url: None,
         }
     }
 }
@@ -66,6 +106,9 @@ impl Config {
             auto_offer_frame: true,
             last_update_check: None,
             last_self_update_check: None,
+            shim_project_version: true,
+            keep_archives: false,
+            github_api_base_url: None,
         })
     }
 
@@ -117,7 +160,9 @@ impl Config {
     pub fn get_effective_version(&self) -> Option<String> {
         // First, check for project-specific version file
         if let Some(project_version) = self.get_project_version() {
-            return Some(project_version);
+            let resolved = crate::core::version::VersionManager::new(self.clone())
+                .resolve_version_spec(&project_version);
+            return Some(resolved.unwrap_or(project_version));
         }
 
         // Fall back to global active version
@@ -130,7 +175,7 @@ impl Config {
     }
 
     /// Recursively search for .cleanlanguage/.cleanversion file in current directory and parents
-    fn find_version_file_in_tree(&self, start_dir: &std::path::Path) -> Option<String> {
+    pub fn find_version_file_in_tree(&self, start_dir: &std::path::Path) -> Option<String> {
         let mut current_dir = start_dir.to_path_buf();
 
         loop {
@@ -157,6 +202,39 @@ impl Config {
         None
     }
 
+    /// Find the project pin for `plugin` in the nearest
+    /// `.cleanlanguage/plugins.toml` that lists it, searching upward from
+    /// `start_dir` the same way as `.cleanversion`.
+    pub fn find_project_plugin_version(&self, start_dir: &Path, plugin: &str) -> Option<String> {
+        start_dir.ancestors().find_map(|dir| {
+            let file = dir.join(".cleanlanguage").join(PROJECT_PLUGINS_FILE);
+            let version = ProjectPlugins::load(&file).ok()?.plugins.remove(plugin)?;
+            let version = version.trim().to_string();
+            (!version.is_empty()).then_some(version)
+        })
+    }
+
+    /// Pin `plugin` to `version` in `<dir>/.cleanlanguage/plugins.toml`,
+    /// keeping any other plugins already pinned there.
+    pub fn set_project_plugin_version(
+        &self,
+        dir: &Path,
+        plugin: &str,
+        version: &str,
+    ) -> Result<PathBuf> {
+        let clean_dir = dir.join(".cleanlanguage");
+        let file = clean_dir.join(PROJECT_PLUGINS_FILE);
+        let mut pins = ProjectPlugins::load(&file)?;
+        pins.plugins.insert(plugin.to_string(), version.to_string());
+
+        let content = toml::to_string_pretty(&pins).map_err(|e| CleenError::ConfigError {
+            message: format!("Failed to serialize {}: {e}", file.display()),
+        })?;
+        std::fs::create_dir_all(&clean_dir)?;
+        std::fs::write(&file, content)?;
+        Ok(file)
+    }
+
     /// Create a .cleanlanguage/.cleanversion file in the current directory
     pub fn set_project_version(&self, version: &str) -> Result<()> {
         let current_dir = env::current_dir()?;
@@ -178,6 +256,16 @@ impl Config {
         self.cleen_dir.join("versions")
     }
 
+    /// Directory holding archives kept by `cleen install --keep-archive`
+    pub fn get_download_cache_dir(&self) -> PathBuf {
+        self.cleen_dir.join("cache").join("downloads")
+    }
+
+    /// Directory for logs of backgrounded servers (~/.cleen/logs/)
+    pub fn get_logs_dir(&self) -> PathBuf {
+        self.cleen_dir.join("logs")
+    }
+
     pub fn get_bin_dir(&self) -> PathBuf {
         self.cleen_dir.join("bin")
     }
@@ -187,8 +275,7 @@ impl Config {
     }
 
     pub fn get_version_binary(&self, version: &str) -> PathBuf {
-        let binary_name = if cfg!(windows) { "cln.exe" } else { "cln" };
-        self.get_version_dir(version).join(binary_name)
+        self.get_version_dir(version).join(binaries::compiler())
     }
 
     #[allow(dead_code)]
@@ -197,26 +284,16 @@ impl Config {
     }
 
     pub fn get_shim_path(&self) -> PathBuf {
-        let binary_name = if cfg!(windows) { "cln.exe" } else { "cln" };
-        self.get_bin_dir().join(binary_name)
+        self.get_bin_dir().join(binaries::compiler_shim())
     }
 
     pub fn get_version_lsp_binary(&self, version: &str) -> PathBuf {
-        let binary_name = if cfg!(windows) {
-            "clean-language-server.exe"
-        } else {
-            "clean-language-server"
-        };
-        self.get_version_dir(version).join(binary_name)
+        self.get_version_dir(version)
+            .join(binaries::language_server())
     }
 
     pub fn get_lsp_shim_path(&self) -> PathBuf {
-        let binary_name = if cfg!(windows) {
-            "clean-language-server.exe"
-        } else {
-            "clean-language-server"
-        };
-        self.get_bin_dir().join(binary_name)
+        self.get_bin_dir().join(binaries::language_server())
     }
 
     pub fn should_check_updates(&self) -> bool {
@@ -299,14 +376,12 @@ impl Config {
 
     /// Get the binary path for a specific Frame CLI version
     pub fn get_frame_version_binary(&self, version: &str) -> PathBuf {
-        let binary_name = if cfg!(windows) { "frame.exe" } else { "frame" };
-        self.get_frame_version_dir(version).join(binary_name)
+        self.get_frame_version_dir(version).join(binaries::frame())
     }
 
     /// Get the Frame CLI shim path
     pub fn get_frame_shim_path(&self) -> PathBuf {
-        let binary_name = if cfg!(windows) { "frame.exe" } else { "frame" };
-        self.get_bin_dir().join(binary_name)
+        self.get_bin_dir().join(binaries::frame())
     }
 
     // Plugin management methods
@@ -1,11 +1,39 @@
+use crate::core::github::Asset;
+use crate::error::CleenError;
 use crate::utils::fs as cleen_fs;
 use anyhow::Result;
 use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
 use std::fs::File;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tar::Archive;
+use xz2::read::XzDecoder;
 use zip::ZipArchive;
 
+/// Archive extensions [`Downloader::extract_archive`] knows how to unpack.
+const ARCHIVE_EXTENSIONS: [&str; 4] = [".tar.gz", ".tgz", ".tar.xz", ".zip"];
+
+/// Whether a release asset is an archive that needs extracting, as opposed
+/// to a bare binary.
+pub fn is_archive(name: &str) -> bool {
+    let name = name.to_lowercase();
+    ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Largest ratio of extracted bytes to archive bytes an extraction accepts.
+/// Release archives compress a few times over; anything far past that is
+/// not a release archive.
+pub const MAX_EXTRACTION_RATIO: u64 = 25;
+
+/// Attempts made by [`Downloader::download_file_with_retry`] before the
+/// last error is surfaced to the caller.
+pub const DEFAULT_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Base delay between download attempts. Multiplied by the attempt number
+/// so a flaky connection gets a little more breathing room each time.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
 pub struct Downloader;
 
 impl Default for Downloader {
@@ -19,8 +47,15 @@ impl Downloader {
         Self
     }
 
-    pub fn download_file(&self, url: &str, destination: &Path) -> Result<()> {
-        println!("Downloading from {url}...");
+    /// Download `url` to `destination` with curl running silently. Status
+    /// lines go to `progress`, never straight to stdout.
+    pub fn download_file(
+        &self,
+        url: &str,
+        destination: &Path,
+        progress: &mut dyn FnMut(&str),
+    ) -> Result<()> {
+        progress(&format!("Downloading from {url}..."));
 
         // Ensure parent directory exists
         if let Some(parent) = destination.parent() {
@@ -49,24 +84,101 @@ impl Downloader {
         // file so downstream extract/copy operations start from a clean slate.
         cleen_fs::strip_macos_xattrs(destination);
 
-        println!("Downloaded to {destination:?}");
+        progress(&format!("Downloaded to {destination:?}"));
         Ok(())
     }
 
-    pub fn extract_archive(&self, archive_path: &Path, destination: &Path) -> Result<()> {
-        println!("Extracting {archive_path:?} to {destination:?}");
+    /// Download `url` to `destination` with a progress bar, resuming from
+    /// whatever is already on disk at `destination`.
+    ///
+    /// Resume relies on curl's `-C -`, which asks the server for the byte
+    /// range after the existing partial file. `--fail` keeps an HTTP error
+    /// page from being written (and later resumed) as if it were the asset.
+    pub fn download_file_resumable(&self, url: &str, destination: &Path) -> Result<()> {
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let resume = std::fs::metadata(destination)
+            .map(|m| m.len() > 0)
+            .unwrap_or(false);
+
+        let mut cmd = std::process::Command::new("curl");
+        cmd.arg("-L") // Follow redirects
+            .arg("--fail")
+            .arg("--progress-bar")
+            .arg("-H")
+            .arg(concat!("User-Agent: cleen/", env!("CARGO_PKG_VERSION")))
+            .arg("-o")
+            .arg(destination);
+        if resume {
+            cmd.arg("-C").arg("-");
+        }
+        let status = cmd.arg(url).status()?;
+
+        if !status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to download file: curl exited with status {:?}",
+                status.code()
+            ));
+        }
+
+        cleen_fs::strip_macos_xattrs(destination);
+        Ok(())
+    }
+
+    /// Download with progress, retrying up to `attempts` times on failure.
+    ///
+    /// The partial file at `destination` is kept between attempts so each
+    /// retry resumes where the previous one stopped instead of starting
+    /// over. Callers own `destination`'s parent and are expected to clean
+    /// it up once they are done with the download.
+    pub fn download_file_with_retry(
+        &self,
+        url: &str,
+        destination: &Path,
+        attempts: u32,
+        progress: &mut dyn FnMut(&str),
+    ) -> Result<()> {
+        progress(&format!("Downloading from {url}..."));
+        retry_download(attempts, RETRY_BASE_DELAY, progress, |_| {
+            self.download_file_resumable(url, destination)
+        })?;
+        progress(&format!("Downloaded to {destination:?}"));
+        Ok(())
+    }
+
+    pub fn extract_archive(
+        &self,
+        archive_path: &Path,
+        destination: &Path,
+        progress: &mut dyn FnMut(&str),
+    ) -> Result<()> {
+        progress(&format!("Extracting {archive_path:?} to {destination:?}"));
 
         std::fs::create_dir_all(destination)?;
+        let mut budget = ExtractionBudget::for_archive(std::fs::metadata(archive_path)?.len());
 
         let file_name = archive_path
             .file_name()
             .and_then(|name| name.to_str())
             .ok_or_else(|| anyhow::anyhow!("Invalid archive file name"))?;
 
+        let file_name = file_name.to_lowercase();
         if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
-            self.extract_tar_gz(archive_path, destination)?;
+            extract_tar(
+                GzDecoder::new(File::open(archive_path)?),
+                destination,
+                &mut budget,
+            )?;
+        } else if file_name.ends_with(".tar.xz") {
+            extract_tar(
+                XzDecoder::new(File::open(archive_path)?),
+                destination,
+                &mut budget,
+            )?;
         } else if file_name.ends_with(".zip") {
-            self.extract_zip(archive_path, destination)?;
+            self.extract_zip(archive_path, destination, &mut budget)?;
         } else {
             return Err(anyhow::anyhow!("Unsupported archive format: {}", file_name));
         }
@@ -75,27 +187,27 @@ impl Downloader {
         // Strip recursively so the installed version dir is clean.
         cleen_fs::strip_macos_xattrs_recursive(destination);
 
-        println!("Extraction completed");
-        Ok(())
-    }
-
-    fn extract_tar_gz(&self, archive_path: &Path, destination: &Path) -> Result<()> {
-        let file = File::open(archive_path)?;
-        let decoder = GzDecoder::new(file);
-        let mut archive = Archive::new(decoder);
-        archive.unpack(destination)?;
+        progress("Extraction completed");
         Ok(())
     }
 
-    fn extract_zip(&self, archive_path: &Path, destination: &Path) -> Result<()> {
+    fn extract_zip(
+        &self,
+        archive_path: &Path,
+        destination: &Path,
+        budget: &mut ExtractionBudget,
+    ) -> Result<()> {
         let file = File::open(archive_path)?;
         let mut archive = ZipArchive::new(file)?;
 
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)?;
             let outpath = match file.enclosed_name() {
-                Some(path) => destination.join(path),
-                None => continue,
+                Some(path) => {
+                    ensure_within(destination, &path)?;
+                    destination.join(path)
+                }
+                None => return Err(unsafe_entry(file.name())),
             };
 
             if file.name().ends_with('/') {
@@ -107,7 +219,13 @@ impl Downloader {
                     }
                 }
                 let mut outfile = File::create(&outpath)?;
-                std::io::copy(&mut file, &mut outfile)?;
+                // The declared size can't be trusted, so count what is
+                // actually decompressed and stop one byte past the budget.
+                let written = std::io::copy(
+                    &mut std::io::Read::take(&mut file, budget.remaining().saturating_add(1)),
+                    &mut outfile,
+                )?;
+                budget.spend(written)?;
             }
 
             #[cfg(unix)]
@@ -121,3 +239,686 @@ impl Downloader {
         Ok(())
     }
 }
+
+fn unsafe_entry(entry: impl std::fmt::Display) -> anyhow::Error {
+    CleenError::UnsafeArchive {
+        entry: entry.to_string(),
+    }
+    .into()
+}
+
+/// Running total of bytes written by one extraction, capped at
+/// [`MAX_EXTRACTION_RATIO`] times the archive's size.
+struct ExtractionBudget {
+    archive_size: u64,
+    limit: u64,
+    used: u64,
+}
+
+impl ExtractionBudget {
+    fn for_archive(archive_size: u64) -> Self {
+        Self {
+            archive_size,
+            limit: archive_size.saturating_mul(MAX_EXTRACTION_RATIO),
+            used: 0,
+        }
+    }
+
+    fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+
+    /// Count `bytes` as written, failing once the total passes the limit.
+    fn spend(&mut self, bytes: u64) -> Result<()> {
+        self.used = self.used.saturating_add(bytes);
+        if self.used > self.limit {
+            return Err(CleenError::ArchiveTooLarge {
+                archive_size: self.archive_size,
+                limit: self.limit,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Unpack a tar stream, whatever its compression, into `destination`.
+///
+/// Each entry's size is charged to `budget` before it is written; the tar
+/// reader never yields more data for an entry than its header declares.
+fn extract_tar(
+    reader: impl std::io::Read,
+    destination: &Path,
+    budget: &mut ExtractionBudget,
+) -> Result<()> {
+    let mut archive = Archive::new(reader);
+
+    // Check every entry ourselves rather than relying on `unpack`, which
+    // silently skips escaping paths: a release archive containing one is
+    // broken or hostile, and the install should fail loudly either way.
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        if !is_contained_path(&path) {
+            return Err(unsafe_entry(path.display()));
+        }
+        if let Some(target) = entry.link_name()? {
+            if !is_contained_path(&path.parent().unwrap_or(Path::new("")).join(&target)) {
+                return Err(unsafe_entry(format_args!(
+                    "{} -> {}",
+                    path.display(),
+                    target.display()
+                )));
+            }
+        }
+        ensure_within(destination, &path)?;
+        budget.spend(entry.size())?;
+        entry.unpack_in(destination)?;
+    }
+    Ok(())
+}
+
+/// Check where `entry` would actually land once symlinks already written by
+/// earlier entries are followed. The lexical check alone misses an archive
+/// that first plants `lib -> /tmp` and then writes `lib/payload`.
+fn ensure_within(destination: &Path, entry: &Path) -> Result<()> {
+    let root = destination.canonicalize()?;
+    let target = destination.join(entry);
+
+    // Resolve the deepest part of the target that already exists; anything
+    // below it is created fresh by the extraction.
+    let mut existing = target.as_path();
+    while existing.symlink_metadata().is_err() {
+        match existing.parent() {
+            Some(parent) => existing = parent,
+            None => break,
+        }
+    }
+    match existing.canonicalize() {
+        Ok(resolved) if resolved.starts_with(&root) => Ok(()),
+        // A dangling symlink cannot be resolved, and writing through it
+        // would create its target wherever it points.
+        _ => Err(unsafe_entry(entry.display())),
+    }
+}
+
+/// Whether an archive entry path stays inside the extraction directory:
+/// relative, and never climbing above its starting point via `..`.
+fn is_contained_path(path: &Path) -> bool {
+    use std::path::Component;
+
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => return false,
+            Component::ParentDir => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+        }
+    }
+    true
+}
+
+/// Suffix of the checksum file published next to each release asset.
+pub const CHECKSUM_SUFFIX: &str = ".sha256";
+
+/// The `<asset>.sha256` published alongside `asset_name` in a release.
+pub fn checksum_asset<'a>(assets: &'a [Asset], asset_name: &str) -> Option<&'a Asset> {
+    let wanted = format!("{asset_name}{CHECKSUM_SUFFIX}");
+    assets.iter().find(|asset| asset.name == wanted)
+}
+
+/// Whether a release asset is a checksum file rather than something to install.
+pub fn is_checksum_asset(name: &str) -> bool {
+    name.to_lowercase().ends_with(CHECKSUM_SUFFIX)
+}
+
+/// Check `file` against its published SHA-256 before it is extracted.
+///
+/// Without a checksum asset the download is accepted with a warning. On a
+/// mismatch `file` is deleted so a corrupt download is never installed or
+/// cached.
+pub fn verify_checksum(
+    downloader: &Downloader,
+    checksum: Option<&Asset>,
+    file: &Path,
+    progress: &mut dyn FnMut(&str),
+) -> crate::error::Result<()> {
+    let Some(checksum) = checksum else {
+        progress("⚠️  No checksum published for this asset; skipping verification");
+        return Ok(());
+    };
+
+    let sums_path = file.with_file_name(&checksum.name);
+    downloader
+        .download_file(&checksum.browser_download_url, &sums_path, progress)
+        .map_err(|_e| CleenError::DownloadError {
+            url: checksum.browser_download_url.clone(),
+        })?;
+    let sums = std::fs::read_to_string(&sums_path);
+    let _ = std::fs::remove_file(&sums_path);
+
+    let asset_name = file.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let expected = sums
+        .ok()
+        .and_then(|content| parse_checksum(&content, asset_name))
+        .ok_or_else(|| CleenError::ValidationError {
+            message: format!("{} does not contain a SHA-256 checksum", checksum.name),
+        })?;
+    let actual = sha256_file(file)?;
+
+    if actual != expected {
+        let _ = std::fs::remove_file(file);
+        return Err(CleenError::ChecksumMismatch { expected, actual });
+    }
+    progress("Verified SHA-256 checksum");
+    Ok(())
+}
+
+/// Read the digest for `asset_name` from a `.sha256` file: either a bare
+/// hex digest or `sha256sum` output (`<digest>  <file>` lines).
+fn parse_checksum(content: &str, asset_name: &str) -> Option<String> {
+    let is_digest = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    let mut lines = content.lines().filter_map(|line| {
+        let mut fields = line.split_whitespace();
+        let digest = fields.next()?;
+        let name = fields.next().map(|n| n.trim_start_matches('*'));
+        is_digest(digest).then(|| (digest.to_ascii_lowercase(), name))
+    });
+
+    let (first_digest, first_name) = lines.next()?;
+    if first_name.is_none_or(|name| name == asset_name) {
+        return Some(first_digest);
+    }
+    lines
+        .find(|(_, name)| *name == Some(asset_name))
+        .map(|(digest, _)| digest)
+}
+
+/// Lowercase hex SHA-256 of a file's contents.
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Release archives kept for offline reinstalls, one directory per version:
+/// `~/.cleen/cache/downloads/<version>/<asset>`. Filled by
+/// `cleen install --keep-archive` (or `keep_archives` in config.json) and
+/// consulted by every install before it downloads.
+pub struct ArchiveCache {
+    dir: PathBuf,
+}
+
+impl ArchiveCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cached archive for `version`, if one was kept.
+    pub fn lookup(&self, version: &str) -> Option<PathBuf> {
+        let mut archives: Vec<PathBuf> = std::fs::read_dir(self.dir.join(version))
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && !path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with('.'))
+            })
+            .collect();
+        // One archive per version is the norm; sort so a stray extra file
+        // never makes the pick depend on read_dir order.
+        archives.sort();
+        archives.into_iter().next()
+    }
+
+    /// Copy `archive` into the cache for `version` under `asset_name`. The
+    /// copy goes through a dot-file and a rename so an interrupted store
+    /// never leaves a truncated archive for [`Self::lookup`] to find.
+    pub fn store(&self, version: &str, asset_name: &str, archive: &Path) -> Result<PathBuf> {
+        let version_dir = self.dir.join(version);
+        std::fs::create_dir_all(&version_dir)?;
+        let target = version_dir.join(asset_name);
+        let partial = version_dir.join(format!(".{asset_name}.partial"));
+        std::fs::copy(archive, &partial)?;
+        std::fs::rename(&partial, &target)?;
+        Ok(target)
+    }
+
+    /// Drop the cached archive for `version`, e.g. after it failed to
+    /// extract, so the next install downloads a fresh copy.
+    pub fn remove(&self, version: &str) {
+        let _ = std::fs::remove_dir_all(self.dir.join(version));
+    }
+}
+
+/// Run `op` up to `attempts` times, sleeping `base_delay * attempt` between
+/// failures and reporting each retry to `progress`. `op` receives the
+/// 1-based attempt number. Returns the last error when every attempt fails.
+fn retry_download<F>(
+    attempts: u32,
+    base_delay: Duration,
+    progress: &mut dyn FnMut(&str),
+    mut op: F,
+) -> Result<()>
+where
+    F: FnMut(u32) -> Result<()>,
+{
+    let attempts = attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= attempts => return Err(e),
+            Err(e) => {
+                progress(&format!(
+                    "⚠️  Download attempt {attempt}/{attempts} failed: {e}"
+                ));
+                std::thread::sleep(base_delay * attempt);
+                attempt += 1;
+                progress(&format!(
+                    "Retrying download (attempt {attempt}/{attempts})..."
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SHA-256 of `b"cln"`.
+    const CLN_SHA256: &str = "36fa131132e2ff4f72b4a4dae9b79760ed1b5df7501c544ae712797019729568";
+
+    #[test]
+    fn sha256_file_and_checksum_parsing() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("cln-linux-x86_64.tar.gz");
+        std::fs::write(&file, b"cln").unwrap();
+        let digest = sha256_file(&file).unwrap();
+        assert_eq!(digest, CLN_SHA256);
+
+        let upper = digest.to_uppercase();
+        assert_eq!(
+            parse_checksum(&format!("{upper}\n"), "x").as_deref(),
+            Some(digest.as_str())
+        );
+        let sums = format!(
+            "{}  cln-macos-aarch64.tar.gz\n{digest} *cln-linux-x86_64.tar.gz\n",
+            "0".repeat(64)
+        );
+        assert_eq!(
+            parse_checksum(&sums, "cln-linux-x86_64.tar.gz").as_deref(),
+            Some(digest.as_str())
+        );
+        assert_eq!(parse_checksum(&sums, "cln-windows-x86_64.zip"), None);
+        assert_eq!(parse_checksum("not a checksum", "x"), None);
+    }
+
+    #[test]
+    fn checksum_asset_is_matched_by_exact_name() {
+        let asset = |name: &str| Asset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.invalid/{name}"),
+            size: 0,
+        };
+        let assets = vec![
+            asset("cln-linux-x86_64.tar.gz"),
+            asset("cln-linux-x86_64.tar.gz.sha256"),
+            asset("cln-macos-aarch64.tar.gz"),
+        ];
+        assert_eq!(
+            checksum_asset(&assets, "cln-linux-x86_64.tar.gz").map(|a| a.name.as_str()),
+            Some("cln-linux-x86_64.tar.gz.sha256")
+        );
+        assert!(checksum_asset(&assets, "cln-macos-aarch64.tar.gz").is_none());
+        assert!(is_checksum_asset("cln-linux-x86_64.tar.gz.SHA256"));
+    }
+
+    #[test]
+    fn verify_checksum_without_published_digest_warns_and_continues() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("cln.tar.gz");
+        std::fs::write(&file, b"cln").unwrap();
+
+        let mut lines = Vec::new();
+        verify_checksum(&Downloader::new(), None, &file, &mut |l| {
+            lines.push(l.to_string())
+        })
+        .unwrap();
+        assert!(lines[0].contains("No checksum"));
+        assert!(file.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn verify_checksum_rejects_and_deletes_mismatched_download() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let published = tmp.path().join("published");
+        std::fs::create_dir_all(&published).unwrap();
+        let sums = published.join("cln.tar.gz.sha256");
+        let checksum = Asset {
+            name: "cln.tar.gz.sha256".to_string(),
+            browser_download_url: format!("file://{}", sums.display()),
+            size: 0,
+        };
+        let downloads = tmp.path().join("downloads");
+        std::fs::create_dir_all(&downloads).unwrap();
+        let file = downloads.join("cln.tar.gz");
+
+        std::fs::write(&sums, format!("{CLN_SHA256}  cln.tar.gz\n")).unwrap();
+        std::fs::write(&file, b"cln").unwrap();
+        verify_checksum(&Downloader::new(), Some(&checksum), &file, &mut |_| {}).unwrap();
+        assert!(file.exists());
+        assert!(!downloads.join("cln.tar.gz.sha256").exists());
+
+        std::fs::write(&file, b"corrupted").unwrap();
+        let err =
+            verify_checksum(&Downloader::new(), Some(&checksum), &file, &mut |_| {}).unwrap_err();
+        assert!(matches!(
+            err,
+            CleenError::ChecksumMismatch { ref expected, .. } if expected == CLN_SHA256
+        ));
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn contained_path_rejects_absolute_and_parent_escapes() {
+        assert!(is_contained_path(Path::new("bin/cln")));
+        assert!(is_contained_path(Path::new("./cln-1.0/bin/../cln")));
+        assert!(!is_contained_path(Path::new("../evil")));
+        assert!(!is_contained_path(Path::new("bin/../../evil")));
+        assert!(!is_contained_path(Path::new("/etc/passwd")));
+    }
+
+    #[test]
+    fn extract_unpacks_tar_xz_archive() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let archive_path = tmp.path().join("cln-linux-x86_64.tar.xz");
+        let mut builder = tar::Builder::new(xz2::write::XzEncoder::new(
+            File::create(&archive_path).unwrap(),
+            6,
+        ));
+        for (path, body) in [("cln", &b"#!/bin/sh\n"[..]), ("lib/runtime.wasm", b"\0asm")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o755);
+            builder.append_data(&mut header, path, body).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let destination = tmp.path().join("versions").join("1.0.0");
+        Downloader::new()
+            .extract_archive(&archive_path, &destination, &mut |_| {})
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read(destination.join("cln")).unwrap(),
+            b"#!/bin/sh\n"
+        );
+        assert_eq!(
+            std::fs::read(destination.join("lib/runtime.wasm")).unwrap(),
+            b"\0asm"
+        );
+        assert!(is_archive("cln-linux-x86_64.tar.xz"));
+        assert!(is_archive("CLN-MACOS.TGZ"));
+        assert!(!is_archive("cln-linux-x86_64"));
+    }
+
+    #[test]
+    fn extract_rejects_tar_entry_with_parent_path() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let archive_path = tmp.path().join("cln-linux-x86_64.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(&archive_path).unwrap(),
+            Compression::default(),
+        ));
+        // `Header::set_path` refuses `..`, so write the name bytes directly
+        // the way a hand-crafted archive would.
+        let body = b"pwned";
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..11].copy_from_slice(b"../evil.txt");
+        header.set_size(body.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, &body[..]).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let destination = tmp.path().join("versions").join("1.0.0");
+        let err = Downloader::new()
+            .extract_archive(&archive_path, &destination, &mut |_| {})
+            .unwrap_err();
+
+        assert!(
+            matches!(
+                err.downcast_ref::<CleenError>(),
+                Some(CleenError::UnsafeArchive { .. })
+            ),
+            "{err}"
+        );
+        assert!(!tmp.path().join("versions").join("evil.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_rejects_entry_written_through_symlink_chain() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let archive_path = tmp.path().join("cln-linux-x86_64.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(&archive_path).unwrap(),
+            Compression::default(),
+        ));
+        // Each link looks harmless on its own, but `a/b` resolves through
+        // `a -> .` to the parent of the install directory.
+        for (path, target) in [("a", "."), ("a/b", "..")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            header.set_mode(0o777);
+            builder.append_link(&mut header, path, target).unwrap();
+        }
+        let body = b"pwned";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(body.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "a/b/evil.txt", &body[..])
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let destination = tmp.path().join("versions").join("1.0.0");
+        let err = Downloader::new()
+            .extract_archive(&archive_path, &destination, &mut |_| {})
+            .unwrap_err();
+
+        assert!(
+            matches!(
+                err.downcast_ref::<CleenError>(),
+                Some(CleenError::UnsafeArchive { .. })
+            ),
+            "{err}"
+        );
+        assert!(!tmp.path().join("versions").join("evil.txt").exists());
+    }
+
+    #[test]
+    fn extract_rejects_zip_entry_with_parent_path() {
+        use std::io::Write;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let archive_path = tmp.path().join("cln-windows-x86_64.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        writer
+            .start_file("../evil.txt", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"pwned").unwrap();
+        writer.finish().unwrap();
+
+        let destination = tmp.path().join("versions").join("1.0.0");
+        let err = Downloader::new()
+            .extract_archive(&archive_path, &destination, &mut |_| {})
+            .unwrap_err();
+
+        assert!(
+            matches!(
+                err.downcast_ref::<CleenError>(),
+                Some(CleenError::UnsafeArchive { .. })
+            ),
+            "{err}"
+        );
+        assert!(!tmp.path().join("versions").join("evil.txt").exists());
+    }
+
+    #[test]
+    fn extract_stops_zip_that_expands_past_the_ratio() {
+        use std::io::Write;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let archive_path = tmp.path().join("cln-windows-x86_64.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        writer
+            .start_file("cln.exe", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(&vec![0u8; 4 * 1024 * 1024]).unwrap();
+        writer.finish().unwrap();
+        let limit = std::fs::metadata(&archive_path).unwrap().len() * MAX_EXTRACTION_RATIO;
+
+        let destination = tmp.path().join("versions").join("1.0.0");
+        let err = Downloader::new()
+            .extract_archive(&archive_path, &destination, &mut |_| {})
+            .unwrap_err();
+
+        assert!(
+            matches!(
+                err.downcast_ref::<CleenError>(),
+                Some(CleenError::ArchiveTooLarge { .. })
+            ),
+            "{err}"
+        );
+        // Writing stopped at the limit instead of finishing the entry.
+        let written = std::fs::metadata(destination.join("cln.exe"))
+            .unwrap()
+            .len();
+        assert!(written <= limit + 1, "{written} > {limit}");
+    }
+
+    #[test]
+    fn archive_cache_stores_looks_up_and_removes_per_version() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cache = ArchiveCache::new(tmp.path().join("downloads"));
+        let archive = tmp.path().join("cln-linux-x86_64.tar.gz");
+        std::fs::write(&archive, b"archive").unwrap();
+
+        assert!(cache.lookup("1.0.0").is_none());
+        let stored = cache
+            .store("1.0.0", "cln-linux-x86_64.tar.gz", &archive)
+            .unwrap();
+        assert_eq!(cache.lookup("1.0.0"), Some(stored.clone()));
+        assert_eq!(std::fs::read(&stored).unwrap(), b"archive");
+        assert!(cache.lookup("2.0.0").is_none());
+
+        // A leftover partial copy is never served.
+        std::fs::write(stored.with_file_name(".x.partial"), b"half").unwrap();
+        assert_eq!(cache.lookup("1.0.0"), Some(stored));
+
+        cache.remove("1.0.0");
+        assert!(cache.lookup("1.0.0").is_none());
+    }
+
+    #[test]
+    fn retry_download_succeeds_after_transient_failures() {
+        let mut calls = Vec::new();
+        let mut lines = Vec::new();
+        let result = retry_download(
+            3,
+            Duration::ZERO,
+            &mut |line| lines.push(line.to_string()),
+            |attempt| {
+                calls.push(attempt);
+                if attempt < 3 {
+                    Err(anyhow::anyhow!("connection reset"))
+                } else {
+                    Ok(())
+                }
+            },
+        );
+        assert!(result.is_ok());
+        assert_eq!(calls, vec![1, 2, 3]);
+        assert_eq!(
+            lines,
+            vec![
+                "⚠️  Download attempt 1/3 failed: connection reset",
+                "Retrying download (attempt 2/3)...",
+                "⚠️  Download attempt 2/3 failed: connection reset",
+                "Retrying download (attempt 3/3)...",
+            ]
+        );
+    }
+
+    #[test]
+    fn retry_download_surfaces_last_error_when_exhausted() {
+        let mut calls = 0;
+        let err = retry_download(2, Duration::ZERO, &mut |_| {}, |attempt| {
+            calls += 1;
+            Err(anyhow::anyhow!("attempt {attempt} failed"))
+        })
+        .unwrap_err();
+        assert_eq!(calls, 2);
+        assert_eq!(err.to_string(), "attempt 2 failed");
+    }
+
+    #[test]
+    fn retry_download_keeps_partial_file_between_attempts() {
+        // Resume state is the partial file itself: each attempt must see
+        // what the previous one left behind rather than a fresh file.
+        let tmp = std::env::temp_dir().join(format!("cleen-dl-resume-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&tmp);
+        std::fs::create_dir_all(&tmp).unwrap();
+        let partial = tmp.join("cleen.tar.gz");
+
+        let mut seen_sizes = Vec::new();
+        retry_download(3, Duration::ZERO, &mut |_| {}, |attempt| {
+            let existing = std::fs::metadata(&partial).map(|m| m.len()).unwrap_or(0);
+            seen_sizes.push(existing);
+            let mut f = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&partial)?;
+            std::io::Write::write_all(&mut f, b"chunk")?;
+            if attempt < 3 {
+                Err(anyhow::anyhow!("connection reset"))
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap();
+
+        assert_eq!(seen_sizes, vec![0, 5, 10]);
+        assert_eq!(std::fs::metadata(&partial).unwrap().len(), 15);
+
+        std::fs::remove_dir_all(&tmp).unwrap();
+    }
+
+    #[test]
+    fn retry_download_treats_zero_attempts_as_one() {
+        let mut calls = 0;
+        let _ = retry_download(0, Duration::ZERO, &mut |_| {}, |_| {
+            calls += 1;
+            Err(anyhow::anyhow!("fail"))
+        });
+        assert_eq!(calls, 1);
+    }
+}
@@ -1,14 +1,40 @@
-use crate::core::{compatibility, config::Config, download::Downloader, github::GitHubClient};
+use crate::core::{
+    binaries, compatibility,
+    config::Config,
+    download::{self, Downloader},
+    github::GitHubClient,
+    report::BuildReport,
+    version::{normalize, VersionManager},
+};
 use crate::error::{CleenError, Result};
+use crate::utils::{compiler_output, fs::format_size, gitignore, timing};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
 
 /// PID file location for the server
 fn get_pid_file_path() -> PathBuf {
     std::env::temp_dir().join("cleen-frame-server.pid")
 }
 
+/// Where `serve` compiles to when no `--out` path is given.
+fn get_scratch_wasm_path() -> PathBuf {
+    std::env::temp_dir().join("cleen-serve-app.wasm")
+}
+
+/// Where `serve --detach` sends the runtime's stdout and stderr.
+fn get_server_log_path(config: &Config) -> PathBuf {
+    config.get_logs_dir().join("frame-server.log")
+}
+
+/// Where `serve --profile` asks the runtime to write request timings.
+fn get_profile_path(config: &Config) -> PathBuf {
+    config.get_logs_dir().join("frame-profile.jsonl")
+}
+
 const FRAME_REPO_OWNER: &str = "Ivan-Pasco";
 const FRAME_REPO_NAME: &str = "cleen-framework";
 
@@ -71,7 +97,7 @@ fn reactivate_frame_plugins(config: &Config, frame_version: &str) -> Result<Vec<
 pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> Result<()> {
     let config = Config::load()?;
 
-    let github_client = GitHubClient::new(config.github_api_token.clone());
+    let github_client = GitHubClient::from_config(&config);
 
     // Determine version to install. Resolve "latest" via /releases/latest
     // rather than /releases[0] — the paginated list endpoint returns
@@ -210,7 +236,7 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
         let matches_platform = name_lower.contains(&platform_suffix.to_lowercase())
             || name_lower.contains("universal")
             || name_lower.contains("any");
-        let is_archive = name_lower.ends_with(".tar.gz") || name_lower.ends_with(".zip");
+        let is_archive = download::is_archive(&name_lower);
         matches_platform && is_archive
     });
 
@@ -235,6 +261,8 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
 
     println!("Found asset: {}", asset.name);
 
+    crate::utils::fs::ensure_install_space(&version_dir, asset.size)?;
+
     // Create temporary download directory
     let temp_dir = std::env::temp_dir().join(format!("cleen-frame-{frame_version}"));
     std::fs::create_dir_all(&temp_dir)?;
@@ -245,10 +273,24 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
 
     let downloader = Downloader::new();
     downloader
-        .download_file(&asset.browser_download_url, &download_path)
+        .download_file(&asset.browser_download_url, &download_path, &mut |line| {
+            println!("{line}")
+        })
         .map_err(|_e| CleenError::DownloadError {
             url: asset.browser_download_url.clone(),
         })?;
+    let checksum = download::checksum_asset(&release.assets, &asset.name);
+    if let Err(e) = download::verify_checksum(&downloader, checksum, &download_path, &mut |line| {
+        println!("{line}")
+    }) {
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        return Err(e);
+    }
+
+    // Removes a half-written frame version dir if anything below bails
+    // out before the install is recorded, so a retry isn't short-circuited
+    // by the "already installed" check above.
+    let version_dir_guard = crate::utils::fs::PartialDirGuard::new(&version_dir);
 
     if is_plugin_tarball {
         // Plugin tarball: extract to a temp staging directory first
@@ -257,10 +299,8 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
 
         println!("Extracting plugins...");
         downloader
-            .extract_archive(&download_path, &staging_dir)
-            .map_err(|_e| CleenError::ExtractionError {
-                path: download_path.clone(),
-            })?;
+            .extract_archive(&download_path, &staging_dir, &mut |line| println!("{line}"))
+            .map_err(|e| CleenError::extraction_failed(e, &download_path))?;
 
         let plugins_dir = config.get_plugins_dir();
         std::fs::create_dir_all(&plugins_dir)?;
@@ -297,31 +337,36 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
 
             // Read plugin.toml to get the canonical plugin name and version, and
             // to check whether the active compiler satisfies the plugin's
-            // declared min_compiler_version.
+            // declared min/max_compiler_version.
             let manifest_path = src_path.join("plugin.toml");
             let (plugin_name, plugin_version) = if manifest_path.exists() {
                 match crate::plugin::manifest::PluginManifest::load(&manifest_path) {
                     Ok(manifest) => {
-                        if let Err(e) =
-                            crate::plugin::check_plugin_compatibility(&config, &manifest)
-                        {
-                            if let crate::error::CleenError::PluginIncompatible {
+                        match crate::plugin::check_plugin_compatibility(&config, &manifest) {
+                            Ok(()) => {}
+                            Err(CleenError::PluginIncompatible {
                                 name,
                                 required,
                                 current,
-                            } = &e
-                            {
-                                incompatible_plugins.push((
-                                    name.clone(),
-                                    manifest.plugin.version.clone(),
-                                    required.clone(),
-                                    current.clone(),
-                                ));
-                            } else {
-                                eprintln!(
-                                    "Warning: compatibility check failed for {folder_name}: {e}"
-                                );
-                            }
+                            }) => incompatible_plugins.push((
+                                name,
+                                manifest.plugin.version.clone(),
+                                format!(">= {required}"),
+                                current,
+                            )),
+                            Err(CleenError::PluginCompilerTooNew {
+                                name,
+                                maximum,
+                                current,
+                            }) => incompatible_plugins.push((
+                                name,
+                                manifest.plugin.version.clone(),
+                                format!("<= {maximum}"),
+                                current,
+                            )),
+                            Err(e) => eprintln!(
+                                "Warning: compatibility check failed for {folder_name}: {e}"
+                            ),
                         }
                         (manifest.plugin.name, manifest.plugin.version)
                     }
@@ -337,6 +382,15 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
                 (folder_name.clone(), frame_version.clone())
             };
 
+            // The manifest name becomes a path under ~/.cleen/plugins; a
+            // crafted bundle must not be able to escape it.
+            if let Err(e) = crate::plugin::validate_plugin_name(&plugin_name)
+                .and_then(|_| crate::core::version::validate_version(&plugin_version))
+            {
+                failed_plugins.push((folder_name.clone(), e.to_string()));
+                continue;
+            }
+
             // Install this plugin in a closure so a fatal error on one
             // plugin (e.g. an unevictable kernel pin on its root dir
             // entry) does not abort the whole frame install — the
@@ -461,6 +515,7 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
         // `cleen frame use`, `cleen frame uninstall`, and the re-entry
         // path of `cleen frame install` can re-assert per-plugin pins.
         write_frame_plugins_manifest(&version_dir, &installed_plugin_versions)?;
+        version_dir_guard.disarm();
 
         // Plugin files were copied (not atomically replaced) into per-plugin
         // version dirs and the activation root. Strip macOS provenance so the
@@ -488,7 +543,7 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
         if total_pruned > 0 {
             println!(
                 "  Pruned {total_pruned} eviction graveyard(s), freed {}",
-                crate::commands::cleanup::format_size(total_freed)
+                format_size(total_freed)
             );
         }
 
@@ -526,43 +581,34 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
         if !incompatible_plugins.is_empty() {
             eprintln!();
             eprintln!("⚠️  Compiler / plugin version mismatch:");
-            for (name, plugin_v, required, current) in &incompatible_plugins {
+            for (name, plugin_v, requirement, current) in &incompatible_plugins {
                 eprintln!(
-                    "   • {name} v{plugin_v} requires compiler >= {required} (active: {current})"
+                    "   • {name} v{plugin_v} requires compiler {requirement} (active: {current})"
                 );
             }
             eprintln!();
             eprintln!("   `cln build` will fail with PLUGIN-REGISTRY-DRIFT until either:");
-            eprintln!("     - the compiler is upgraded:   cleen install latest");
+            eprintln!("     - a compatible compiler is active:   cleen install latest, or cleen use <version>");
             eprintln!("     - an older Frame version is activated:   cleen frame use <version>");
         }
     } else {
         // Platform binary: extract to frame-versions dir
         std::fs::create_dir_all(&version_dir)?;
 
-        if asset.name.ends_with(".tar.gz") || asset.name.ends_with(".zip") {
+        if download::is_archive(&asset.name) {
             println!("Extracting archive...");
             downloader
-                .extract_archive(&download_path, &version_dir)
-                .map_err(|_e| CleenError::ExtractionError {
-                    path: download_path.clone(),
-                })?;
+                .extract_archive(&download_path, &version_dir, &mut |line| println!("{line}"))
+                .map_err(|e| CleenError::extraction_failed(e, &download_path))?;
         } else {
-            let binary_name = if cfg!(windows) { "frame.exe" } else { "frame" };
-            let target_path = version_dir.join(binary_name);
+            let target_path = version_dir.join(binaries::frame());
             std::fs::copy(&download_path, &target_path)?;
         }
 
-        // Find the extracted binary and ensure it's executable
-        let binary_path = find_frame_binary_in_dir(&version_dir)?;
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = std::fs::metadata(&binary_path)?.permissions();
-            perms.set_mode(0o755);
-            std::fs::set_permissions(&binary_path, perms)?;
-        }
+        // Bring `frame` and its companions to the version root so `serve`
+        // finds the runtime without a recursive search.
+        let binary_path = flatten_frame_binaries(&version_dir)?;
+        version_dir_guard.disarm();
 
         // Belt-and-braces strip for the direct-binary branch above.
         crate::utils::fs::strip_macos_xattrs_recursive(&version_dir);
@@ -609,7 +655,7 @@ pub fn install_frame(version: Option<&str>, skip_compatibility_check: bool) -> R
     if let Some((count, bytes)) = crate::commands::cleanup::plugin_cleanup_summary(&config) {
         println!(
             "💡 {count} inactive plugin version(s) using {} — run `cleen cleanup --plugins` to free space.",
-            crate::commands::cleanup::format_size(bytes)
+            format_size(bytes)
         );
         println!();
     }
@@ -644,6 +690,7 @@ pub fn list_frame_versions(config: &Config) -> Result<Vec<String>> {
 
 /// Switch to a specific Frame CLI version
 pub fn use_frame_version(version: &str) -> Result<()> {
+    crate::core::version::validate_version(version)?;
     let mut config = Config::load()?;
 
     // Check if version is installed
@@ -699,6 +746,7 @@ pub fn use_frame_version(version: &str) -> Result<()> {
 
 /// Uninstall a specific Frame CLI version
 pub fn uninstall_frame_version(version: &str) -> Result<()> {
+    crate::core::version::validate_version(version)?;
     let mut config = Config::load()?;
 
     // Check if version is installed
@@ -769,7 +817,7 @@ pub fn uninstall_frame_version(version: &str) -> Result<()> {
 }
 
 /// Update Frame CLI symlink to point to the specified version
-fn update_frame_symlink(config: &Config, version: &str) -> Result<()> {
+pub(crate) fn update_frame_symlink(config: &Config, version: &str) -> Result<()> {
     let binary_path = get_frame_binary_path(config, version);
     let shim_path = config.get_frame_shim_path();
 
@@ -797,37 +845,136 @@ fn get_frame_version_dir(config: &Config, version: &str) -> PathBuf {
 
 /// Get the binary path for a specific Frame CLI version
 fn get_frame_binary_path(config: &Config, version: &str) -> PathBuf {
-    let binary_name = if cfg!(windows) { "frame.exe" } else { "frame" };
-    get_frame_version_dir(config, version).join(binary_name)
+    get_frame_version_dir(config, version).join(binaries::frame())
 }
 
-/// Find Frame binary in a directory
-fn find_frame_binary_in_dir(dir: &Path) -> Result<PathBuf> {
-    let binary_name = if cfg!(windows) { "frame.exe" } else { "frame" };
+/// Find a binary in an extracted release.
+///
+/// Archives may nest the binary (`frame-1.2/bin/frame`) or ship other
+/// files with the same name deeper in the tree, so every regular file named
+/// `binary_name` is collected and ranked: fewest directories other than
+/// `bin/` first, then fewest directories overall, then by path. The result
+/// never depends on `read_dir` order.
+fn find_binary_in_dir(dir: &Path, binary_name: &str) -> Result<PathBuf> {
+    let mut candidates = Vec::new();
+    collect_named_files(dir, binary_name, &mut candidates);
+
+    candidates
+        .into_iter()
+        .min_by_key(|path| {
+            let dirs: Vec<_> = path
+                .strip_prefix(dir)
+                .unwrap_or(path)
+                .parent()
+                .map(|p| p.components().collect())
+                .unwrap_or_default();
+            let non_bin = dirs.iter().filter(|c| c.as_os_str() != "bin").count();
+            (non_bin, dirs.len(), path.clone())
+        })
+        .ok_or_else(|| CleenError::BinaryNotFound {
+            name: binary_name.to_string(),
+        })
+}
 
-    // Look for binary in the root directory first
-    let direct_path = dir.join(binary_name);
-    if direct_path.exists() {
-        return Ok(direct_path);
+/// Move the Frame toolchain binaries an archive may nest
+/// (`frame-1.2/bin/frame`, `frame-1.2/bin/frame-runtime`, ...) up to the
+/// version root, where `serve` and the shims look for them, and make each
+/// one executable. Binaries the release doesn't ship are skipped; a
+/// missing `frame` is an error. Returns the path of `frame`.
+///
+/// An archive laid out as `frame/bin/frame` nests the binary inside a
+/// directory with its own name, so each binary is first moved to a
+/// temporary name. The directory in the way is removed once the binaries
+/// are out of it, provided nothing but empty directories is left.
+fn flatten_frame_binaries(version_dir: &Path) -> Result<PathBuf> {
+    let names = [
+        binaries::frame(),
+        binaries::frame_runtime(),
+        binaries::frame_cli(),
+    ];
+
+    let mut staged = Vec::new();
+    for name in &names {
+        let root_path = version_dir.join(name);
+        if root_path.is_file() {
+            continue;
+        }
+        if let Ok(nested) = find_binary_in_dir(version_dir, name) {
+            let temp_path = version_dir.join(format!(".{name}.flatten"));
+            std::fs::rename(&nested, &temp_path)?;
+            staged.push((temp_path, root_path));
+        }
+    }
+    for (temp_path, root_path) in staged {
+        if root_path.is_dir() {
+            remove_empty_dirs(&root_path)?;
+        }
+        std::fs::rename(&temp_path, &root_path)?;
+    }
+
+    #[cfg(unix)]
+    for name in &names {
+        let root_path = version_dir.join(name);
+        if root_path.is_file() {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(&root_path)?.permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&root_path, perms)?;
+        }
     }
 
-    // Search recursively for the binary
+    let binary_path = version_dir.join(binaries::frame());
+    if !binary_path.is_file() {
+        return Err(CleenError::BinaryNotFound {
+            name: binaries::frame(),
+        });
+    }
+    if !crate::utils::fs::is_executable(&binary_path) {
+        return Err(CleenError::ValidationError {
+            message: format!("{} is not executable", binary_path.display()),
+        });
+    }
+    Ok(binary_path)
+}
+
+/// Remove `dir` if it holds nothing but (possibly nested) empty
+/// directories. Anything else in it is an error, so
+/// [`flatten_frame_binaries`] never deletes files a release shipped.
+fn remove_empty_dirs(dir: &Path) -> Result<()> {
     for entry in std::fs::read_dir(dir)? {
         let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            if let Ok(found) = find_frame_binary_in_dir(&path) {
-                return Ok(found);
-            }
-        } else if path.file_name().and_then(|n| n.to_str()) == Some(binary_name) {
-            return Ok(path);
+        if entry.file_type()?.is_dir() {
+            remove_empty_dirs(&entry.path())?;
+        } else {
+            return Err(CleenError::ValidationError {
+                message: format!(
+                    "{} is in the way of a Frame binary of the same name",
+                    dir.display()
+                ),
+            });
         }
     }
+    std::fs::remove_dir(dir)?;
+    Ok(())
+}
 
-    Err(CleenError::BinaryNotFound {
-        name: binary_name.to_string(),
-    })
+/// Collect regular files (or symlinks to them) named `name` under `dir`.
+/// Symlinked directories are not followed.
+fn collect_named_files(dir: &Path, name: &str, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_named_files(&path, name, found);
+        } else if entry.file_name() == name && path.metadata().is_ok_and(|m| m.is_file()) {
+            found.push(path);
+        }
+    }
 }
 
 /// Validate that the Frame CLI binary works
@@ -892,7 +1039,23 @@ fn get_platform_suffix() -> String {
 /// This function:
 /// 1. Compiles the .cln source file to WASM using the Clean Language compiler
 /// 2. Starts the frame-runtime with the compiled WASM file
-pub fn serve_application(input: &str, port: u16, host: &str, debug: bool) -> Result<()> {
+/// 3. With `watch`, recompiles and restarts the runtime when sources change
+/// 4. With `detach`, leaves the runtime running in the background, logging to
+///    `~/.cleen/logs/frame-server.log` (see [`show_server_logs`])
+/// 5. With `profile`, asks the runtime to record request timings and
+///    summarizes them once the server stops (see [`PROFILE_ENV`])
+#[allow(clippy::too_many_arguments)]
+pub fn serve_application(
+    input: &str,
+    port: u16,
+    host: &str,
+    debug: bool,
+    compiler: Option<&str>,
+    out: Option<&Path>,
+    watch: bool,
+    detach: bool,
+    profile: bool,
+) -> Result<()> {
     let config = Config::load()?;
 
     // Check if a server is already running
@@ -933,51 +1096,70 @@ pub fn serve_application(input: &str, port: u16, host: &str, debug: bool) -> Res
         });
     }
 
-    // Find the Clean Language compiler
-    let cln_path = config.get_shim_path();
-    if !cln_path.exists() {
+    // Find the Clean Language compiler, honouring the project's pin
+    let Some(cln_path) = resolve_project_compiler(&config, input_path, compiler)? else {
         println!("⚠️  Clean Language compiler not found");
         println!("   Install it with: cleen install latest");
         return Err(CleenError::NoActiveVersion);
-    }
+    };
 
     // Find the frame-runtime
     // It should be installed alongside Frame CLI or in the framework's runtime
     let runtime_path = find_frame_runtime(&config)?;
 
-    // Create output WASM path in temp directory
-    let wasm_path = std::env::temp_dir().join("cleen-serve-app.wasm");
+    // Compile to the requested path, or a scratch file in the temp directory
+    let wasm_path = match out {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            path.to_path_buf()
+        }
+        None => get_scratch_wasm_path(),
+    };
 
-    // Compile the source file
-    println!("📦 Compiling {}...", input);
-    let compile_output = Command::new(&cln_path)
-        .args(["compile", input, "-o"])
-        .arg(&wasm_path)
-        .arg("--plugins")
-        .output()
-        .map_err(|e| CleenError::CompilationFailed {
-            message: format!("Failed to run compiler: {e}"),
-        })?;
+    let server = DevServer {
+        cln_path,
+        runtime_path,
+        input,
+        wasm_path,
+        report_wasm_path: out.is_some(),
+        port,
+        host,
+        debug,
+        pid_file,
+        log_file: detach.then(|| get_server_log_path(&config)),
+        profile_file: profile.then(|| get_profile_path(&config)),
+    };
 
-    if !compile_output.status.success() {
-        let stderr = String::from_utf8_lossy(&compile_output.stderr);
-        println!("❌ Compilation failed:");
-        println!("{stderr}");
-        return Err(CleenError::CompilationFailed {
-            message: stderr.to_string(),
-        });
+    // Start each profiling session from an empty file; watch-mode restarts
+    // keep appending to it.
+    if let Some(profile_file) = &server.profile_file {
+        let _ = std::fs::remove_file(profile_file);
     }
 
-    println!("✅ Compilation successful");
+    if watch {
+        server.watch(input_path)?;
+        server.report_profile();
+        return Ok(());
+    }
 
-    // Set environment variables for the server
-    let mut cmd = Command::new(&runtime_path);
-    cmd.arg(&wasm_path);
-    cmd.env("FRAME_PORT", port.to_string());
-    cmd.env("FRAME_HOST", host);
+    server.compile()?;
 
-    if debug {
-        cmd.env("RUST_LOG", "debug");
+    if let Some(log_file) = &server.log_file {
+        let child = server.spawn()?;
+        println!();
+        println!(
+            "🚀 Frame server running in the background (PID: {})",
+            child.id()
+        );
+        println!("   Listening on http://{}:{}", host, port);
+        println!(
+            "   Logs: {} (cleen frame logs --follow)",
+            log_file.display()
+        );
+        println!("   Stop it with: cleen frame stop");
+        return Ok(());
     }
 
     println!();
@@ -988,13 +1170,18 @@ pub fn serve_application(input: &str, port: u16, host: &str, debug: bool) -> Res
     println!();
 
     // Run the server in foreground (blocks until Ctrl+C)
-    let mut child = cmd.spawn().map_err(|e| CleenError::ServerStartFailed {
-        message: format!("Failed to start frame-runtime: {e}"),
-    })?;
+    let mut child = server.spawn()?;
+    let pid_file = &server.pid_file;
 
-    // Save PID
-    let pid = child.id();
-    std::fs::write(&pid_file, pid.to_string())?;
+    // The runtime has its own process group, so the terminal's Ctrl+C or
+    // hangup doesn't reach it; pass it on to the whole group instead.
+    #[cfg(unix)]
+    {
+        let pid = child.id();
+        let _ = ctrlc::set_handler(move || {
+            let _ = signal_process_group(pid);
+        });
+    }
 
     // Wait for the process (this blocks)
     let status = child.wait().map_err(|e| CleenError::ServerStartFailed {
@@ -1002,22 +1189,454 @@ pub fn serve_application(input: &str, port: u16, host: &str, debug: bool) -> Res
     })?;
 
     // Clean up PID file
-    let _ = std::fs::remove_file(&pid_file);
+    let _ = std::fs::remove_file(pid_file);
 
     if !status.success() {
         println!("⚠️  Server exited with status: {:?}", status.code());
     } else {
         println!("Server stopped");
     }
+    server.report_profile();
 
     Ok(())
 }
 
+/// How long `serve --watch` waits for file events to settle before
+/// recompiling, so a save that touches several files rebuilds once.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How often `serve --watch` checks whether the runtime has exited.
+const WATCH_POLL: Duration = Duration::from_millis(500);
+
+/// How long a runtime gets to exit after `SIGTERM` before it is killed.
+const STOP_GRACE: Duration = Duration::from_secs(5);
+
+/// What `serve_application` needs to (re)compile and (re)start the runtime.
+struct DevServer<'a> {
+    cln_path: PathBuf,
+    runtime_path: PathBuf,
+    input: &'a str,
+    wasm_path: PathBuf,
+    report_wasm_path: bool,
+    port: u16,
+    host: &'a str,
+    debug: bool,
+    pid_file: PathBuf,
+    /// Send the runtime's output here, detached from this terminal.
+    log_file: Option<PathBuf>,
+    /// Ask the runtime to record request timings here.
+    profile_file: Option<PathBuf>,
+}
+
+enum WatchEvent {
+    Changed,
+    Stop,
+}
+
+/// Files that mark a project's root directory (see [`project_config_dir`]).
+const PROJECT_CONFIG_FILES: [&str; 2] = ["config.cln", "frame.toml"];
+
+impl DevServer<'_> {
+    /// Compile the entry file to `wasm_path`, printing grouped compiler
+    /// errors on failure.
+    fn compile(&self) -> Result<()> {
+        match compiler_version(&self.cln_path) {
+            Some(version) => println!("📦 Compiling {} with cln {version}...", self.input),
+            None => println!("📦 Compiling {}...", self.input),
+        }
+        let compile_output = timing::phase("compile", || {
+            Command::new(&self.cln_path)
+                .args(["compile", self.input, "-o"])
+                .arg(&self.wasm_path)
+                .arg("--plugins")
+                .output()
+        })
+        .map_err(|e| CleenError::CompilationFailed {
+            message: format!("Failed to run compiler: {e}"),
+        })?;
+
+        if !compile_output.status.success() {
+            let stderr = String::from_utf8_lossy(&compile_output.stderr);
+            println!("❌ Compilation failed:");
+            println!("{}", compiler_output::render(&stderr));
+            return Err(CleenError::CompilationFailed {
+                message: stderr.to_string(),
+            });
+        }
+
+        println!("✅ Compilation successful");
+        if self.report_wasm_path {
+            println!("   WASM written to {}", self.wasm_path.display());
+        }
+        Ok(())
+    }
+
+    /// Start frame-runtime on the compiled WASM and record its PID.
+    fn spawn(&self) -> Result<Child> {
+        let mut cmd = Command::new(&self.runtime_path);
+        cmd.arg(&self.wasm_path);
+        cmd.env("FRAME_PORT", self.port.to_string());
+        cmd.env("FRAME_HOST", self.host);
+
+        if self.debug {
+            cmd.env("RUST_LOG", "debug");
+        }
+
+        if let Some(profile_file) = &self.profile_file {
+            if let Some(dir) = profile_file.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            cmd.env(PROFILE_ENV, "1");
+            cmd.env(PROFILE_OUTPUT_ENV, profile_file);
+        }
+
+        if let Some(log_file) = &self.log_file {
+            if let Some(dir) = log_file.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let log = std::fs::File::create(log_file)?;
+            cmd.stdin(Stdio::null())
+                .stdout(log.try_clone()?)
+                .stderr(log);
+        }
+
+        // Give the runtime its own process group, led by its PID, so `frame
+        // stop` and watch restarts can signal it together with anything it
+        // spawned, and a detached runtime survives the terminal that
+        // started it closing. The terminal's Ctrl+C and hangup no longer
+        // reach the group; the foreground and watch handlers forward them.
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+
+        let child = cmd.spawn().map_err(|e| CleenError::ServerStartFailed {
+            message: format!("Failed to start frame-runtime: {e}"),
+        })?;
+        std::fs::write(&self.pid_file, child.id().to_string())?;
+        Ok(child)
+    }
+
+    /// Compile and spawn, reporting a failure instead of returning it so
+    /// watch mode keeps waiting for the next change.
+    fn restart(&self) -> Option<Child> {
+        if self.compile().is_err() {
+            println!("   Waiting for changes...");
+            return None;
+        }
+        match self.spawn() {
+            Ok(child) => {
+                println!("🚀 Serving on http://{}:{}", self.host, self.port);
+                Some(child)
+            }
+            Err(e) => {
+                println!("❌ {e}");
+                None
+            }
+        }
+    }
+
+    /// Serve, recompiling and restarting the runtime whenever a file in the
+    /// project changes: everything under the nearest directory above the
+    /// entry with a `frame.toml` or `config.cln`, or under the entry's own
+    /// directory when there is none. Ends on Ctrl+C, or when `cleen frame
+    /// stop` removes the PID file and stops the runtime.
+    fn watch(&self, input_path: &Path) -> Result<()> {
+        let entry_dir = build_project_dir(&input_path.to_string_lossy()).to_path_buf();
+        let entry_dir = entry_dir.canonicalize().unwrap_or(entry_dir);
+        let root = project_config_dir(&entry_dir);
+        let wasm_path = self
+            .wasm_path
+            .canonicalize()
+            .unwrap_or_else(|_| self.wasm_path.clone());
+
+        let (tx, rx) = mpsc::channel();
+        let stop = tx.clone();
+        ctrlc::set_handler(move || {
+            let _ = stop.send(WatchEvent::Stop);
+        })
+        .map_err(|e| CleenError::ServerStartFailed {
+            message: format!("Failed to install Ctrl+C handler: {e}"),
+        })?;
+
+        let watch_root = root.clone();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                if is_watched_change(&event, &watch_root, &wasm_path) {
+                    let _ = tx.send(WatchEvent::Changed);
+                }
+            }
+        })
+        .and_then(|mut watcher| {
+            watcher.watch(&root, RecursiveMode::Recursive)?;
+            Ok(watcher)
+        })
+        .map_err(|e| CleenError::ServerStartFailed {
+            message: format!("Failed to watch {}: {e}", root.display()),
+        })?;
+
+        println!("👀 Watching {} for changes", root.display());
+        println!("   Press Ctrl+C to stop the server");
+        println!();
+        let mut child = self.restart();
+
+        loop {
+            match rx.recv_timeout(WATCH_POLL) {
+                Ok(WatchEvent::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+                Ok(WatchEvent::Changed) => {
+                    // Let a burst of events settle before rebuilding.
+                    let mut stopped = false;
+                    loop {
+                        match rx.recv_timeout(WATCH_DEBOUNCE) {
+                            Ok(WatchEvent::Changed) => continue,
+                            Ok(WatchEvent::Stop) => stopped = true,
+                            Err(RecvTimeoutError::Timeout) => {}
+                            Err(RecvTimeoutError::Disconnected) => stopped = true,
+                        }
+                        break;
+                    }
+                    if stopped {
+                        break;
+                    }
+                    println!();
+                    if let Some(running) = child.take() {
+                        self.stop(running);
+                    }
+                    println!("🔄 Change detected, rebuilding...");
+                    child = self.restart();
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let exited = child
+                        .as_mut()
+                        .is_some_and(|c| c.try_wait().ok().flatten().is_some());
+                    if exited {
+                        child = None;
+                        // `cleen frame stop` removes the PID file as it
+                        // stops the runtime; that ends watch mode too.
+                        if !self.pid_file.exists() {
+                            break;
+                        }
+                        println!("⚠️  Server exited; waiting for changes...");
+                    }
+                }
+            }
+        }
+
+        drop(watcher);
+        if let Some(running) = child.take() {
+            self.stop(running);
+        }
+        let _ = std::fs::remove_file(&self.pid_file);
+        println!("Server stopped");
+        Ok(())
+    }
+
+    /// Summarize what the runtime recorded under `serve --profile`.
+    fn report_profile(&self) {
+        let Some(profile_file) = &self.profile_file else {
+            return;
+        };
+        println!();
+        match std::fs::read_to_string(profile_file) {
+            Ok(content) if !content.trim().is_empty() => {
+                println!("⏱️  Request timings ({})", profile_file.display());
+                print!("{}", profile_summary(&content));
+            }
+            _ => {
+                println!("⏱️  The runtime recorded no request timings");
+                println!(
+                    "   It may not support {PROFILE_ENV} yet; expected JSON lines in {}",
+                    profile_file.display()
+                );
+            }
+        }
+    }
+
+    /// Stop the runtime along with anything it spawned, then reap it. A
+    /// runtime still running after [`STOP_GRACE`] is killed.
+    fn stop(&self, mut child: Child) {
+        #[cfg(unix)]
+        if signal_process_group(child.id()).unwrap_or(false) {
+            let deadline = std::time::Instant::now() + STOP_GRACE;
+            while std::time::Instant::now() < deadline {
+                if child.try_wait().ok().flatten().is_some() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+        let _ = std::fs::remove_file(&self.pid_file);
+    }
+}
+
+/// Set to `1` by `serve --profile`. A runtime that supports profiling
+/// appends one JSON object per handled request to the file named by
+/// [`PROFILE_OUTPUT_ENV`]:
+///
+/// ```text
+/// {"method":"GET","path":"/api/users","status":200,"duration_ms":1.8}
+/// ```
+///
+/// `method`, `path` and `duration_ms` are required; `status` is optional.
+/// Lines that don't fit are skipped when the timings are summarized.
+pub const PROFILE_ENV: &str = "FRAME_PROFILE";
+
+/// The file the runtime writes request timings to under [`PROFILE_ENV`].
+pub const PROFILE_OUTPUT_ENV: &str = "FRAME_PROFILE_OUTPUT";
+
+/// One request timing written by the runtime under [`PROFILE_ENV`].
+#[derive(Debug, serde::Deserialize)]
+struct RequestTiming {
+    method: String,
+    path: String,
+    duration_ms: f64,
+}
+
+/// Per-route request count, mean and slowest time, slowest total first.
+fn profile_summary(content: &str) -> String {
+    let mut routes: BTreeMap<(String, String), Vec<f64>> = BTreeMap::new();
+    let mut skipped = 0;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<RequestTiming>(line) {
+            Ok(timing) => routes
+                .entry((timing.method, timing.path))
+                .or_default()
+                .push(timing.duration_ms),
+            Err(_) => skipped += 1,
+        }
+    }
+
+    let mut rows: Vec<(String, usize, f64, f64, f64)> = routes
+        .into_iter()
+        .map(|((method, path), times)| {
+            let total: f64 = times.iter().sum();
+            let max = times.iter().copied().fold(0.0, f64::max);
+            let mean = total / times.len() as f64;
+            (format!("{method} {path}"), times.len(), mean, max, total)
+        })
+        .collect();
+    rows.sort_by(|a, b| b.4.total_cmp(&a.4).then_with(|| a.0.cmp(&b.0)));
+
+    let width = rows.iter().map(|r| r.0.len()).max().unwrap_or(0).max(5);
+    let mut summary = format!(
+        "   {:<width$}  {:>8}  {:>10}  {:>10}\n",
+        "Route", "Requests", "Mean (ms)", "Max (ms)"
+    );
+    for (route, count, mean, max, _) in &rows {
+        summary.push_str(&format!(
+            "   {route:<width$}  {count:>8}  {mean:>10.2}  {max:>10.2}\n"
+        ));
+    }
+    if skipped > 0 {
+        summary.push_str(&format!("   ({skipped} unreadable line(s) skipped)\n"));
+    }
+    summary
+}
+
+/// The directory whose `config.cln`/`frame.toml` apply to sources under
+/// `root`: the nearest ancestor holding either file, or `root` itself.
+fn project_config_dir(root: &Path) -> PathBuf {
+    root.ancestors()
+        .find(|dir| {
+            PROJECT_CONFIG_FILES
+                .iter()
+                .any(|name| dir.join(name).is_file())
+        })
+        .unwrap_or(root)
+        .to_path_buf()
+}
+
+/// Whether `event` should make `serve --watch` recompile: a create,
+/// modify or remove of any file under `root` that isn't the compiled
+/// output, another `.wasm` file, or inside a hidden directory such as
+/// `.git` or `.generated`.
+fn is_watched_change(event: &Event, root: &Path, wasm_path: &Path) -> bool {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return false;
+    }
+    event.paths.iter().any(|path| {
+        if path == wasm_path || path.extension().is_some_and(|ext| ext == "wasm") {
+            return false;
+        }
+        path.strip_prefix(root).is_ok_and(|relative| {
+            !relative
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+        })
+    })
+}
+
+/// How often `frame logs --follow` checks the log for new output.
+const LOG_FOLLOW_POLL: Duration = Duration::from_millis(250);
+
+/// Print the last `lines` lines of a detached server's log. With `follow`,
+/// keep printing output as it's appended until interrupted; a log that
+/// shrinks (a new `serve --detach` started) is read again from the top.
+pub fn show_server_logs(lines: usize, follow: bool) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    let config = Config::load()?;
+    let log_path = get_server_log_path(&config);
+
+    if !log_path.exists() {
+        println!("No Frame server log at {}", log_path.display());
+        println!("   Start a server in the background with: cleen frame serve --detach");
+        if !follow {
+            return Ok(());
+        }
+        while !log_path.exists() {
+            std::thread::sleep(LOG_FOLLOW_POLL);
+        }
+    }
+
+    let content = std::fs::read(&log_path)?;
+    let text = String::from_utf8_lossy(&content);
+    let all: Vec<&str> = text.lines().collect();
+    for line in &all[all.len().saturating_sub(lines)..] {
+        println!("{line}");
+    }
+    if !follow {
+        return Ok(());
+    }
+
+    let mut position = content.len() as u64;
+    let mut stdout = std::io::stdout();
+    loop {
+        std::thread::sleep(LOG_FOLLOW_POLL);
+        let Ok(mut file) = std::fs::File::open(&log_path) else {
+            continue;
+        };
+        let len = file.metadata()?.len();
+        if len < position {
+            position = 0;
+        }
+        if len == position {
+            continue;
+        }
+        file.seek(SeekFrom::Start(position))?;
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended)?;
+        position += appended.len() as u64;
+        stdout.write_all(&appended)?;
+        stdout.flush()?;
+    }
+}
+
 /// Stop a running Frame development server
 pub fn stop_server() -> Result<()> {
     let pid_file = get_pid_file_path();
 
     if !pid_file.exists() {
+        #[cfg(unix)]
+        if stop_orphaned_runtimes(None) {
+            return Ok(());
+        }
         println!("No Frame server is currently running");
         return Ok(());
     }
@@ -1032,17 +1651,40 @@ pub fn stop_server() -> Result<()> {
 
     println!("Stopping Frame server (PID: {pid})...");
 
+    // Remove the PID file first: `serve --watch` treats a runtime that
+    // exits without one as stopped rather than crashed.
+    let _ = std::fs::remove_file(&pid_file);
+
     #[cfg(unix)]
     {
-        let output = Command::new("kill")
-            .args(["-TERM", &pid.to_string()])
-            .output();
+        // Runtimes started by older versions share cleen's process group;
+        // only signal a group the runtime leads itself.
+        let leads_group = Command::new("ps")
+            .args(["-o", "pgid=", "-p", &pid.to_string()])
+            .output()
+            .ok()
+            .and_then(|o| {
+                String::from_utf8_lossy(&o.stdout)
+                    .trim()
+                    .parse::<i32>()
+                    .ok()
+            })
+            == Some(pid);
+
+        let stopped = if leads_group {
+            signal_process_group(pid as u32)
+        } else {
+            Command::new("kill")
+                .args(["-TERM", &pid.to_string()])
+                .output()
+                .map(|o| o.status.success())
+        };
 
-        match output {
-            Ok(o) if o.status.success() => {
+        match stopped {
+            Ok(true) => {
                 println!("✅ Server stopped successfully");
             }
-            Ok(_) => {
+            Ok(false) => {
                 // Process might already be dead
                 println!("⚠️  Process may have already stopped");
             }
@@ -1071,12 +1713,90 @@ pub fn stop_server() -> Result<()> {
         }
     }
 
-    // Clean up PID file
-    let _ = std::fs::remove_file(&pid_file);
+    #[cfg(unix)]
+    stop_orphaned_runtimes(Some(pid as u32));
 
     Ok(())
 }
 
+/// Send `SIGTERM` to the process group led by `pid`.
+#[cfg(unix)]
+fn signal_process_group(pid: u32) -> std::io::Result<bool> {
+    Command::new("kill")
+        .args(["-TERM", "--", &format!("-{pid}")])
+        .output()
+        .map(|o| o.status.success())
+}
+
+/// Look for `frame-runtime` processes left behind by a `serve` that
+/// crashed before cleaning up, and offer to terminate them.
+#[cfg(unix)]
+fn stop_orphaned_runtimes(exclude: Option<u32>) -> bool {
+    use std::io::IsTerminal;
+
+    let Ok(runtime) = Config::load().and_then(|config| find_frame_runtime(&config)) else {
+        return false;
+    };
+    let Ok(output) = Command::new("ps").args(["-eo", "pid=,args="]).output() else {
+        return false;
+    };
+    let orphans: Vec<u32> = orphaned_runtimes(
+        &String::from_utf8_lossy(&output.stdout),
+        &runtime,
+        &get_scratch_wasm_path(),
+    )
+    .into_iter()
+    .filter(|pid| Some(*pid) != exclude)
+    .collect();
+    if orphans.is_empty() {
+        return false;
+    }
+
+    let list = orphans
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    println!();
+    println!("⚠️  Found frame-runtime processes from an earlier serve: {list}");
+    if !std::io::stdout().is_terminal() {
+        println!("   Stop them with: kill {list}");
+        return true;
+    }
+    let confirmed = dialoguer::Confirm::new()
+        .with_prompt("Terminate them?")
+        .default(true)
+        .interact()
+        .unwrap_or_default();
+    if confirmed {
+        for pid in &orphans {
+            let _ = Command::new("kill")
+                .args(["-TERM", &pid.to_string()])
+                .output();
+        }
+        println!("✅ Stopped {} orphaned runtime(s)", orphans.len());
+    }
+    true
+}
+
+/// PIDs in `ps -eo pid=,args=` output whose command runs `runtime` on the
+/// scratch `wasm` file that `serve` compiles to.
+#[cfg(unix)]
+fn orphaned_runtimes(ps_output: &str, runtime: &Path, wasm: &Path) -> Vec<u32> {
+    let runtime = runtime.to_string_lossy();
+    let wasm = wasm.to_string_lossy();
+    ps_output
+        .lines()
+        .filter_map(|line| {
+            let (pid, args) = line.trim().split_once(char::is_whitespace)?;
+            let args = args.trim_start();
+            (args.starts_with(runtime.as_ref()) && args.contains(wasm.as_ref()))
+                .then(|| pid.parse().ok())
+                .flatten()
+        })
+        .collect()
+}
+
 /// Find the frame-runtime binary
 fn find_frame_runtime(config: &Config) -> Result<PathBuf> {
     // First, check if it's in the active Frame CLI version directory
@@ -1084,25 +1804,21 @@ fn find_frame_runtime(config: &Config) -> Result<PathBuf> {
         let version_dir = config.get_frame_versions_dir().join(frame_version);
 
         // Look for frame-runtime in the version directory
-        let runtime_name = if cfg!(windows) {
-            "frame-runtime.exe"
-        } else {
-            "frame-runtime"
-        };
+        let runtime_name = binaries::frame_runtime();
 
-        let runtime_path = version_dir.join(runtime_name);
+        let runtime_path = version_dir.join(&runtime_name);
         if runtime_path.exists() {
             return Ok(runtime_path);
         }
 
         // Also check in subdirectories
-        if let Ok(found) = find_binary_in_dir(&version_dir, runtime_name) {
+        if let Ok(found) = find_binary_in_dir(&version_dir, &runtime_name) {
             return Ok(found);
         }
     }
 
     // Check if frame-runtime is in PATH
-    if let Ok(path) = which::which("frame-runtime") {
+    if let Ok(path) = which::which(binaries::FRAME_RUNTIME) {
         return Ok(path);
     }
 
@@ -1128,29 +1844,69 @@ fn find_frame_runtime(config: &Config) -> Result<PathBuf> {
     })
 }
 
-/// Find a binary in a directory (recursive)
-fn find_binary_in_dir(dir: &Path, name: &str) -> Result<PathBuf> {
-    let direct_path = dir.join(name);
-    if direct_path.exists() {
-        return Ok(direct_path);
+// ---------------------------------------------------------------------------
+// Project compiler resolution
+// ---------------------------------------------------------------------------
+
+/// The compiler `serve` and `build` should use for `input`: the version
+/// requested with `--compiler`, else the version pinned in
+/// `.cleanlanguage/.cleanversion` above it, else the active version.
+/// Returns `None` when none of those is set.
+fn resolve_project_compiler(
+    config: &Config,
+    input: &Path,
+    requested: Option<&str>,
+) -> Result<Option<PathBuf>> {
+    if let Some(version) = requested {
+        crate::core::version::validate_version(version)?;
+        return installed_compiler(config, version)
+            .map(Some)
+            .ok_or_else(|| CleenError::VersionNotFound {
+                version: version.to_string(),
+            });
     }
 
-    for entry in std::fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    let mut start = std::env::current_dir()?.join(input);
+    if start.is_file() {
+        start.pop();
+    }
 
-        if path.is_dir() {
-            if let Ok(found) = find_binary_in_dir(&path, name) {
-                return Ok(found);
+    if let Some(spec) = config.find_version_file_in_tree(&start) {
+        let pinned = VersionManager::new(config.clone()).resolve_version_spec(&spec)?;
+        crate::core::version::validate_version(&pinned)?;
+        return installed_compiler(config, &pinned)
+            .map(Some)
+            .ok_or_else(|| CleenError::ProjectVersionNotInstalled {
+                version: normalize::to_clean_version(&pinned),
+            });
+    }
+
+    match &config.active_version {
+        Some(active) => installed_compiler(config, active).map(Some).ok_or_else(|| {
+            CleenError::VersionNotFound {
+                version: active.clone(),
             }
-        } else if path.file_name().and_then(|n| n.to_str()) == Some(name) {
-            return Ok(path);
-        }
+        }),
+        None => Ok(None),
     }
+}
 
-    Err(CleenError::BinaryNotFound {
-        name: name.to_string(),
-    })
+/// Version of an installed compiler binary, read from its version directory.
+fn compiler_version(binary: &Path) -> Option<String> {
+    let dir = binary.parent()?.file_name()?.to_str()?;
+    Some(normalize::to_clean_version(dir))
+}
+
+/// Compiler binary for an installed version, checking both the clean and
+/// the `v`-prefixed directory names.
+fn installed_compiler(config: &Config, version: &str) -> Option<PathBuf> {
+    [
+        normalize::to_clean_version(version),
+        normalize::to_github_version(version),
+    ]
+    .into_iter()
+    .map(|v| config.get_version_binary(&v))
+    .find(|binary| binary.exists())
 }
 
 // ---------------------------------------------------------------------------
@@ -1164,19 +1920,14 @@ fn find_frame_cli() -> Result<PathBuf> {
     // Check active frame version directory
     if let Some(ref frame_version) = config.frame_version {
         let version_dir = config.get_frame_versions_dir().join(frame_version);
-        let cli_name = if cfg!(windows) {
-            "frame-cli.exe"
-        } else {
-            "frame-cli"
-        };
-        let cli_path = version_dir.join(cli_name);
+        let cli_path = version_dir.join(binaries::frame_cli());
         if cli_path.exists() {
             return Ok(cli_path);
         }
     }
 
     // Check PATH
-    if let Ok(path) = which::which("frame-cli") {
+    if let Ok(path) = which::which(binaries::FRAME_CLI) {
         return Ok(path);
     }
 
@@ -1193,8 +1944,17 @@ fn find_frame_cli() -> Result<PathBuf> {
 }
 
 /// Create a new Frame project (delegates to frame-cli)
+///
+/// frame-cli writes the template files one by one. If it fails partway,
+/// the project directory is removed again, but only when it didn't exist
+/// before this call.
 pub fn create_project(name: &str, template: &str, port: u16) -> Result<()> {
+    if let Some(warning) = check_project_port(port)? {
+        println!("⚠️  {warning}");
+    }
     let frame_cli = find_frame_cli()?;
+    let project_dir = Path::new(name);
+    let created_here = !project_dir.exists();
 
     let status = Command::new(&frame_cli)
         .args([
@@ -1211,6 +1971,14 @@ pub fn create_project(name: &str, template: &str, port: u16) -> Result<()> {
         })?;
 
     if !status.success() {
+        if created_here && project_dir.is_dir() {
+            match std::fs::remove_dir_all(project_dir) {
+                Ok(()) => println!("🧹 Removed the partially created project {name}"),
+                Err(e) => {
+                    println!("⚠️  Could not remove the partially created project {name}: {e}")
+                }
+            }
+        }
         return Err(CleenError::CompilationFailed {
             message: "frame-cli new failed".to_string(),
         });
@@ -1219,26 +1987,400 @@ pub fn create_project(name: &str, template: &str, port: u16) -> Result<()> {
     Ok(())
 }
 
+/// Ports below this need root to bind on most systems.
+const FIRST_UNPRIVILEGED_PORT: u16 = 1024;
+
+/// Check the port a new project's development server will use. Port 0 is
+/// rejected; a privileged port is allowed with a warning, since the server
+/// won't be able to bind it without root.
+pub fn check_project_port(port: u16) -> Result<Option<String>> {
+    if port == 0 {
+        return Err(CleenError::InvalidPort { port });
+    }
+    Ok((port < FIRST_UNPRIVILEGED_PORT).then(|| {
+        format!(
+            "Port {port} is privileged; the server won't start without root. \
+             Consider a port from {FIRST_UNPRIVILEGED_PORT} up"
+        )
+    }))
+}
+
+/// `--port` parser for `frame new`: rejects what [`check_project_port`]
+/// rejects at argument-parsing time.
+pub fn parse_project_port(value: &str) -> std::result::Result<u16, String> {
+    let port: u16 = value
+        .parse()
+        .map_err(|_| format!("'{value}' is not a port number"))?;
+    check_project_port(port).map_err(|e| e.to_string())?;
+    Ok(port)
+}
+
+/// Optimization levels frame-cli accepts for `-O` / `--opt-level`.
+pub const OPT_LEVELS: [&str; 6] = ["0", "1", "2", "3", "s", "z"];
+
+/// `-O` / `--opt-level` parser for `frame build`: rejects anything outside
+/// [`OPT_LEVELS`] before a build starts.
+pub fn parse_opt_level(value: &str) -> std::result::Result<String, String> {
+    if OPT_LEVELS.contains(&value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "'{value}' is not an optimization level (expected one of {})",
+            OPT_LEVELS.join(", ")
+        ))
+    }
+}
+
 /// Build a Frame project (delegates to frame-cli)
-pub fn build_project(input: &str, output: &str, optimize: &str) -> Result<()> {
+///
+/// `frame-cli`'s stdout is passed through; its stderr is echoed line by line
+/// and warning lines are collected into the returned [`BuildReport`].
+pub fn build_project(
+    input: &str,
+    output: &str,
+    optimize: &str,
+    compiler: Option<&str>,
+) -> Result<BuildReport> {
     let frame_cli = find_frame_cli()?;
+    let config = Config::load()?;
+    let compiler = resolve_project_compiler(&config, Path::new(input), compiler)?;
+    let started = std::time::Instant::now();
+    // Compared against afterwards rather than a start time: file mtimes
+    // come from a coarser clock and can predate `SystemTime::now()`.
+    let existing = wasm_mtimes(Path::new(output));
+
+    let mut command = Command::new(&frame_cli);
+    command.args(["build", input, "--output", output, "--optimize", optimize]);
+    // frame-cli runs `cln` from PATH; put the project's compiler first so
+    // it doesn't fall back to whatever the shim resolves. This only works
+    // while the binary is named `cln`: with `CLEEN_CLN_BINARY` renaming it,
+    // frame-cli still finds the shim, since it has no way to be handed the
+    // compiler's path.
+    if let Some(version_dir) = compiler.as_deref().and_then(Path::parent) {
+        let inherited = std::env::var_os("PATH").unwrap_or_default();
+        let dirs =
+            std::iter::once(version_dir.to_path_buf()).chain(std::env::split_paths(&inherited));
+        if let Ok(path) = std::env::join_paths(dirs) {
+            command.env("PATH", path);
+        }
+    }
 
-    let status = Command::new(&frame_cli)
-        .args(["build", input, "--output", output, "--optimize", optimize])
-        .status()
+    let mut child = command
+        .stderr(std::process::Stdio::piped())
+        .spawn()
         .map_err(|e| CleenError::CompilationFailed {
             message: format!("Failed to run frame-cli: {e}"),
         })?;
 
+    let mut warnings = Vec::new();
+    let mut located = Vec::new();
+    if let Some(stderr) = child.stderr.take() {
+        use std::io::BufRead;
+        for line in std::io::BufReader::new(stderr)
+            .lines()
+            .map_while(|l| l.ok())
+        {
+            eprintln!("{line}");
+            if is_warning_line(&line) {
+                warnings.push(line.trim().to_string());
+            }
+            if compiler_output::CompilerMessage::parse(&line).is_some() {
+                located.push(line);
+            }
+        }
+    }
+
+    let status = child.wait().map_err(|e| CleenError::CompilationFailed {
+        message: format!("Failed to run frame-cli: {e}"),
+    })?;
+    timing::record("build", started.elapsed());
+
     if !status.success() {
+        // The raw stream has already scrolled past; repeat the located
+        // messages grouped by file so they're easy to act on.
+        if !located.is_empty() {
+            eprintln!();
+            eprint!("{}", compiler_output::render(&located.join("\n")));
+        }
         return Err(CleenError::CompilationFailed {
             message: "frame-cli build failed".to_string(),
         });
     }
 
+    let wasm_path = newest_changed_wasm(Path::new(output), &existing);
+    let size_bytes = wasm_path
+        .as_ref()
+        .and_then(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    Ok(BuildReport {
+        entry: PathBuf::from(input),
+        wasm_path,
+        size_bytes,
+        warnings,
+        compiler_version: compiler.as_deref().and_then(compiler_version),
+        duration: started.elapsed(),
+    })
+}
+
+/// The directory a build input belongs to: the input itself when it's a
+/// directory, otherwise the directory of the entry file.
+fn build_project_dir(input: &str) -> &Path {
+    let input = Path::new(input);
+    if input.is_dir() {
+        return input;
+    }
+    match input.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// Remove a previous build's `output` directory before `frame build
+/// --clean`, so nothing from removed routes or renamed components
+/// survives. Any directory under the project root (the nearest directory
+/// above the input with a `frame.toml` or `config.cln`) may be cleaned.
+/// `output` is relative to the working directory, so it may also lie
+/// beside the project; there it is only cleaned if it looks like build
+/// output (see [`looks_like_build_output`]). Neither the working
+/// directory, the project root nor the input's own directory is ever
+/// deleted.
+pub fn clean_output(input: &str, output: &str) -> Result<()> {
+    let output_path = Path::new(output);
+    if !output_path.exists() {
+        return Ok(());
+    }
+    let input_dir = build_project_dir(input).canonicalize()?;
+    let project = project_config_dir(&input_dir);
+    let cwd = std::env::current_dir()?.canonicalize()?;
+    // `symlink_metadata` so a link is judged by itself, not its target.
+    let is_dir = output_path.symlink_metadata()?.is_dir();
+    let resolved = output_path.canonicalize()?;
+    let inside = resolved.starts_with(&project)
+        || (resolved.starts_with(&cwd) && looks_like_build_output(&resolved));
+    let holds_protected = [&cwd, &project, &input_dir]
+        .iter()
+        .any(|dir| dir.starts_with(&resolved));
+    if !is_dir || !inside || holds_protected {
+        return Err(CleenError::UnsafeCleanPath {
+            path: output.to_string(),
+            project: project.display().to_string(),
+        });
+    }
+
+    println!("🧹 Removing {}", output_path.display());
+    std::fs::remove_dir_all(&resolved)?;
+    Ok(())
+}
+
+/// Whether every file under `dir` is a `.wasm` module or sits in a
+/// `.generated` directory, as in what `frame build` writes. An empty
+/// directory qualifies.
+fn looks_like_build_output(dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().all(|entry| {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(kind) if kind.is_dir() => {
+                entry.file_name() == ".generated" || looks_like_build_output(&path)
+            }
+            Ok(_) => path.extension().is_some_and(|ext| ext == "wasm"),
+            Err(_) => false,
+        }
+    })
+}
+
+/// Report a finished build. With `strict`, any warning fails it instead of
+/// printing the summary, so CI can insist on a warning-free project.
+pub fn finish_build(report: &BuildReport, strict: bool) -> Result<()> {
+    reject_warnings(report.warnings.len(), strict)?;
+    print_build_summary(report);
+    Ok(())
+}
+
+fn reject_warnings(count: usize, strict: bool) -> Result<()> {
+    if strict && count > 0 {
+        return Err(CleenError::CompilationFailed {
+            message: format!("{count} warning(s) treated as errors (--strict)"),
+        });
+    }
+    Ok(())
+}
+
+/// Build once per optimization level in `levels`, renaming each artifact to
+/// `<name>.opt<level>.wasm` so the next level doesn't overwrite it.
+pub fn build_project_levels(
+    input: &str,
+    output: &str,
+    levels: &[String],
+    compiler: Option<&str>,
+) -> Result<Vec<(String, BuildReport)>> {
+    let mut builds = Vec::with_capacity(levels.len());
+    for level in levels {
+        println!("📦 Building with -O{level}...");
+        let mut report = build_project(input, output, level, compiler)?;
+        if let Some(wasm) = report.wasm_path.take() {
+            let stem = wasm.file_stem().unwrap_or_default().to_string_lossy();
+            let renamed = wasm.with_file_name(format!("{stem}.opt{level}.wasm"));
+            std::fs::rename(&wasm, &renamed)?;
+            report.wasm_path = Some(renamed);
+        }
+        builds.push((level.clone(), report));
+    }
+    Ok(builds)
+}
+
+/// Report the builds from [`build_project_levels`] as a size table, each
+/// level compared against the first. `strict` applies as in [`finish_build`].
+pub fn finish_level_builds(builds: &[(String, BuildReport)], strict: bool) -> Result<()> {
+    reject_warnings(builds.iter().map(|(_, r)| r.warnings.len()).sum(), strict)?;
+
+    let baseline = builds
+        .first()
+        .filter(|(_, r)| r.wasm_path.is_some())
+        .map(|(_, r)| r.size_bytes);
+    println!();
+    println!("{:<7} {:>10}  {:>7}  File", "Level", "Size", "Change");
+    for (level, report) in builds {
+        let Some(path) = &report.wasm_path else {
+            println!(
+                "{:<7} {:>10}  {:>7}  (no .wasm found)",
+                format!("-O{level}"),
+                "-",
+                "-"
+            );
+            continue;
+        };
+        let change = match baseline {
+            Some(base) if base > 0 => {
+                let percent = (report.size_bytes as f64 - base as f64) * 100.0 / base as f64;
+                format!("{percent:+.0}%")
+            }
+            _ => "-".to_string(),
+        };
+        println!(
+            "{:<7} {:>10}  {:>7}  {}",
+            format!("-O{level}"),
+            format_size(report.size_bytes),
+            change,
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// After a build, point out an `output` directory that the project's git
+/// repository doesn't ignore, so generated files don't get committed by
+/// accident. With `update`, add it to the top-level `.gitignore` instead.
+/// Builds outside a git repository, or into a directory outside it, are
+/// left alone.
+pub fn check_output_ignored(input: &str, output: &str, update: bool) -> Result<()> {
+    let (Ok(project), Ok(output)) = (
+        build_project_dir(input).canonicalize(),
+        Path::new(output).canonicalize(),
+    ) else {
+        return Ok(());
+    };
+    let Some(root) = gitignore::repo_root(&project) else {
+        return Ok(());
+    };
+    let Ok(relative) = output.strip_prefix(&root) else {
+        return Ok(());
+    };
+    if relative.as_os_str().is_empty() {
+        return Ok(());
+    }
+
+    let gitignore_path = root.join(".gitignore");
+    let content = std::fs::read_to_string(&gitignore_path).unwrap_or_default();
+    if gitignore::is_ignored(&content, relative) {
+        return Ok(());
+    }
+
+    let entry = format!("/{}/", relative.to_string_lossy().replace('\\', "/"));
+    if update {
+        gitignore::append_entry(&gitignore_path, &entry)?;
+        println!("📝 Added {entry} to {}", gitignore_path.display());
+    } else {
+        println!(
+            "💡 {entry} isn't in .gitignore, so build output may get committed. \
+             Re-run with --update-gitignore to add it."
+        );
+    }
     Ok(())
 }
 
+/// Print the one-line summary both CLIs show after a successful build.
+pub fn print_build_summary(report: &BuildReport) {
+    match &report.wasm_path {
+        Some(path) => println!(
+            "✅ Built {} ({}) in {:.1}s",
+            path.display(),
+            format_size(report.size_bytes),
+            report.duration.as_secs_f64()
+        ),
+        None => println!("✅ Build finished in {:.1}s", report.duration.as_secs_f64()),
+    }
+    if let Some(version) = &report.compiler_version {
+        println!("   Compiler: cln {version}");
+    }
+    if !report.warnings.is_empty() {
+        println!("⚠️  {} warning(s)", report.warnings.len());
+    }
+}
+
+/// `warning: ...`, `Warning ...`, or `⚠️ ...` as printed by frame-cli and
+/// the compiler it drives.
+fn is_warning_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.to_ascii_lowercase().starts_with("warning") || trimmed.starts_with('⚠')
+}
+
+/// Modification time of every `.wasm` file under `dir`, searching
+/// subdirectories too.
+fn wasm_mtimes(dir: &Path) -> BTreeMap<PathBuf, std::time::SystemTime> {
+    let mut found = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if path.extension().and_then(|e| e.to_str()) != Some("wasm") {
+                continue;
+            }
+            if let Ok(modified) = meta.modified() {
+                found.insert(path, modified);
+            }
+        }
+    }
+    found
+}
+
+/// Most recently modified `.wasm` file under `dir` that is new or changed
+/// since `before` was taken with [`wasm_mtimes`].
+fn newest_changed_wasm(
+    dir: &Path,
+    before: &BTreeMap<PathBuf, std::time::SystemTime>,
+) -> Option<PathBuf> {
+    wasm_mtimes(dir)
+        .into_iter()
+        .filter(|(path, modified)| before.get(path) != Some(modified))
+        .max_by_key(|(_, modified)| *modified)
+        .map(|(path, _)| path)
+}
+
 /// Scan and discover project files (delegates to frame-cli)
 pub fn scan_project(project_dir: &str, format: &str, verbose: bool) -> Result<()> {
     let frame_cli = find_frame_cli()?;
@@ -1262,3 +2404,387 @@ pub fn scan_project(project_dir: &str, format: &str, verbose: bool) -> Result<()
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(cleen_dir: &Path, active_version: Option<&str>) -> Config {
+        Config {
+            active_version: active_version.map(str::to_string),
+            cleen_dir: cleen_dir.to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    fn install_fake_compiler(config: &Config, version: &str) -> PathBuf {
+        let binary = config.get_version_binary(version);
+        std::fs::create_dir_all(binary.parent().unwrap()).unwrap();
+        std::fs::write(&binary, b"").unwrap();
+        binary
+    }
+
+    fn pin_project(dir: &Path, version: &str) {
+        std::fs::create_dir_all(dir.join(".cleanlanguage")).unwrap();
+        std::fs::write(
+            dir.join(".cleanlanguage").join(".cleanversion"),
+            format!("{version}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn project_compiler_prefers_pin_over_active_version() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path(), Some("1.0.0"));
+        let global = install_fake_compiler(&config, "1.0.0");
+        let pinned = install_fake_compiler(&config, "v2.0.0");
+
+        let project = TempDir::new().unwrap();
+        pin_project(project.path(), "2.0.0");
+        let entry = project.path().join("app").join("api").join("main.cln");
+        std::fs::create_dir_all(entry.parent().unwrap()).unwrap();
+        std::fs::write(&entry, b"").unwrap();
+
+        assert_eq!(
+            resolve_project_compiler(&config, &entry, None).unwrap(),
+            Some(pinned.clone())
+        );
+        assert_eq!(
+            resolve_project_compiler(&config, project.path(), None).unwrap(),
+            Some(pinned)
+        );
+
+        let elsewhere = TempDir::new().unwrap();
+        assert_eq!(
+            resolve_project_compiler(&config, elsewhere.path(), None).unwrap(),
+            Some(global)
+        );
+        let unset = test_config(home.path(), None);
+        assert_eq!(
+            resolve_project_compiler(&unset, elsewhere.path(), None).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn project_pin_to_missing_version_errors_with_install_hint() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path(), Some("1.0.0"));
+        install_fake_compiler(&config, "1.0.0");
+
+        let project = TempDir::new().unwrap();
+        pin_project(project.path(), "v9.9.9");
+
+        let err = resolve_project_compiler(&config, project.path(), None).unwrap_err();
+        assert!(
+            matches!(&err, CleenError::ProjectVersionNotInstalled { version } if version == "9.9.9")
+        );
+        assert!(err.to_string().contains("cleen install 9.9.9"), "{err}");
+    }
+
+    #[test]
+    fn requested_compiler_overrides_project_pin() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path(), Some("1.0.0"));
+        install_fake_compiler(&config, "1.0.0");
+        install_fake_compiler(&config, "2.0.0");
+        let requested = install_fake_compiler(&config, "3.0.0");
+
+        let project = TempDir::new().unwrap();
+        pin_project(project.path(), "2.0.0");
+
+        assert_eq!(
+            resolve_project_compiler(&config, project.path(), Some("v3.0.0")).unwrap(),
+            Some(requested)
+        );
+        assert!(matches!(
+            resolve_project_compiler(&config, project.path(), Some("4.0.0")),
+            Err(CleenError::VersionNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn compiler_version_comes_from_the_version_directory() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path(), None);
+        let binary = install_fake_compiler(&config, "v2.1.0");
+        assert_eq!(compiler_version(&binary).as_deref(), Some("2.1.0"));
+    }
+
+    #[test]
+    fn find_frame_binary_prefers_shallowest_candidate() {
+        let tmp = TempDir::new().unwrap();
+        let name = binaries::frame();
+
+        // Deeper candidate sorts first by name; the shallower one must win.
+        let deep = tmp.path().join("a").join("tools").join(&name);
+        let shallow = tmp.path().join("z").join(&name);
+        for path in [&deep, &shallow] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"bin").unwrap();
+        }
+        // A directory with the binary's name is not a candidate.
+        std::fs::create_dir_all(tmp.path().join(&name).join("docs")).unwrap();
+
+        assert_eq!(
+            find_binary_in_dir(tmp.path(), &binaries::frame()).unwrap(),
+            shallow
+        );
+
+        // A `bin/` directory doesn't count against a candidate.
+        let in_bin = tmp.path().join("bin").join(&name);
+        std::fs::create_dir_all(in_bin.parent().unwrap()).unwrap();
+        std::fs::write(&in_bin, b"bin").unwrap();
+        assert_eq!(
+            find_binary_in_dir(tmp.path(), &binaries::frame()).unwrap(),
+            in_bin
+        );
+    }
+
+    #[test]
+    fn flatten_moves_nested_frame_and_runtime_to_version_root() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let tmp = TempDir::new().unwrap();
+        let archive_path = tmp.path().join("frame-linux-x86_64.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            std::fs::File::create(&archive_path).unwrap(),
+            Compression::default(),
+        ));
+        for (name, body) in [
+            (binaries::frame(), &b"frame"[..]),
+            (binaries::frame_runtime(), &b"runtime"[..]),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, format!("frame-1.0.0/bin/{name}"), body)
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let version_dir = tmp.path().join("1.0.0");
+        Downloader::new()
+            .extract_archive(&archive_path, &version_dir, &mut |_| {})
+            .unwrap();
+
+        let frame = flatten_frame_binaries(&version_dir).unwrap();
+        let runtime = version_dir.join(binaries::frame_runtime());
+        assert_eq!(frame, version_dir.join(binaries::frame()));
+        assert!(runtime.is_file());
+        assert!(crate::utils::fs::is_executable(&frame));
+        assert!(crate::utils::fs::is_executable(&runtime));
+        assert!(!version_dir
+            .join("frame-1.0.0")
+            .join("bin")
+            .join(binaries::frame())
+            .exists());
+        assert_eq!(
+            find_binary_in_dir(&version_dir, &binaries::frame_runtime()).unwrap(),
+            runtime
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn flatten_handles_binary_nested_in_a_directory_of_its_own_name() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let tmp = TempDir::new().unwrap();
+        let archive_path = tmp.path().join("frame-linux-x86_64.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            std::fs::File::create(&archive_path).unwrap(),
+            Compression::default(),
+        ));
+        for (name, body) in [
+            (binaries::frame(), &b"frame"[..]),
+            (binaries::frame_runtime(), &b"runtime"[..]),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, format!("frame/bin/{name}"), body)
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let version_dir = tmp.path().join("1.0.0");
+        Downloader::new()
+            .extract_archive(&archive_path, &version_dir, &mut |_| {})
+            .unwrap();
+
+        let frame = flatten_frame_binaries(&version_dir).unwrap();
+        assert_eq!(frame, version_dir.join(binaries::frame()));
+        assert_eq!(std::fs::read(&frame).unwrap(), b"frame");
+        assert!(crate::utils::fs::is_executable(&frame));
+        assert_eq!(
+            std::fs::read(version_dir.join(binaries::frame_runtime())).unwrap(),
+            b"runtime"
+        );
+
+        // A directory still holding other files is left alone.
+        let other = tmp.path().join("2.0.0");
+        std::fs::create_dir_all(other.join("frame").join("bin")).unwrap();
+        std::fs::write(other.join("frame").join("bin").join("frame"), b"frame").unwrap();
+        std::fs::write(other.join("frame").join("README"), b"docs").unwrap();
+        assert!(matches!(
+            flatten_frame_binaries(&other),
+            Err(CleenError::ValidationError { .. })
+        ));
+        assert!(other.join("frame").join("README").exists());
+    }
+
+    #[test]
+    fn find_frame_binary_reports_missing_binary() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("README.md"), b"no binary").unwrap();
+        assert!(matches!(
+            find_binary_in_dir(tmp.path(), &binaries::frame()),
+            Err(CleenError::BinaryNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn warning_lines_are_recognised() {
+        assert!(is_warning_line("warning: unused variable `x`"));
+        assert!(is_warning_line("  Warning: route /api shadows /api/"));
+        assert!(is_warning_line("⚠️  deprecated attribute"));
+        assert!(!is_warning_line("error: missing entry"));
+        assert!(!is_warning_line("Compiling app/main.cln"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn orphaned_runtimes_match_runtime_and_scratch_wasm() {
+        let runtime = Path::new("/home/me/.cleen/versions/frame/1.0.0/frame-runtime");
+        let wasm = Path::new("/tmp/cleen-serve-app.wasm");
+        let ps = "    1 /sbin/init\n\
+                  4242 /home/me/.cleen/versions/frame/1.0.0/frame-runtime /tmp/cleen-serve-app.wasm\n\
+                  4300 /home/me/.cleen/versions/frame/1.0.0/frame-runtime /home/me/app/out.wasm\n\
+                  4301 vim /tmp/cleen-serve-app.wasm\n\
+                 12345 /home/me/.cleen/versions/frame/1.0.0/frame-runtime /tmp/cleen-serve-app.wasm\n";
+
+        assert_eq!(orphaned_runtimes(ps, runtime, wasm), vec![4242, 12345]);
+        assert!(orphaned_runtimes("", runtime, wasm).is_empty());
+    }
+
+    #[test]
+    fn project_port_rejects_zero_and_warns_on_privileged() {
+        assert!(matches!(
+            check_project_port(0),
+            Err(CleenError::InvalidPort { port: 0 })
+        ));
+        let warning = check_project_port(80).unwrap().unwrap();
+        assert!(warning.contains("Port 80 is privileged"), "{warning}");
+        assert_eq!(check_project_port(3000).unwrap(), None);
+        assert_eq!(check_project_port(1024).unwrap(), None);
+
+        assert_eq!(parse_project_port("8080"), Ok(8080));
+        assert_eq!(parse_project_port("80"), Ok(80));
+        assert!(parse_project_port("0")
+            .unwrap_err()
+            .contains("Invalid port 0"));
+        assert!(parse_project_port("http").is_err());
+        assert!(parse_project_port("70000").is_err());
+    }
+
+    #[test]
+    fn opt_level_accepts_only_known_levels() {
+        for level in OPT_LEVELS {
+            assert_eq!(parse_opt_level(level), Ok(level.to_string()));
+        }
+        assert!(parse_opt_level("4")
+            .unwrap_err()
+            .contains("0, 1, 2, 3, s, z"));
+        assert!(parse_opt_level("O2").is_err());
+        assert!(parse_opt_level("").is_err());
+    }
+
+    #[test]
+    fn profile_summary_groups_requests_by_route() {
+        let content = "{\"method\":\"GET\",\"path\":\"/\",\"status\":200,\"duration_ms\":2.0}\n\
+                       {\"method\":\"POST\",\"path\":\"/api/users\",\"duration_ms\":9.5}\n\
+                       {\"method\":\"GET\",\"path\":\"/\",\"duration_ms\":4.0}\n\
+                       not json\n";
+
+        assert_eq!(
+            profile_summary(content),
+            "   Route            Requests   Mean (ms)    Max (ms)\n   \
+             POST /api/users         1        9.50        9.50\n   \
+             GET /                   2        3.00        4.00\n   \
+             (1 unreadable line(s) skipped)\n"
+        );
+    }
+
+    #[test]
+    fn changes_anywhere_in_the_project_trigger_a_rebuild() {
+        use notify::event::{AccessKind, CreateKind, ModifyKind};
+
+        let tmp = TempDir::new().unwrap();
+        let project = tmp.path();
+        let entry_dir = project.join("app").join("api");
+        std::fs::create_dir_all(&entry_dir).unwrap();
+        std::fs::write(project.join("frame.toml"), "").unwrap();
+        let root = project_config_dir(&entry_dir);
+        assert_eq!(root, project);
+
+        let wasm_path = entry_dir.join("app.wasm");
+        let event = |kind: EventKind, paths: &[PathBuf]| {
+            paths
+                .iter()
+                .fold(Event::new(kind), |event, path| event.add_path(path.clone()))
+        };
+        let classify = |paths: &[PathBuf]| {
+            let event = event(EventKind::Modify(ModifyKind::Any), paths);
+            is_watched_change(&event, &root, &wasm_path)
+        };
+
+        assert!(classify(&[entry_dir.join("main.cln")]));
+        assert!(classify(&[project
+            .join("app")
+            .join("ui")
+            .join("index.cln")]));
+        assert!(classify(&[project.join("config.cln")]));
+        assert!(classify(&[project.join("frame.toml")]));
+        assert!(classify(&[wasm_path.clone(), project.join("frame.toml")]));
+        assert!(!classify(std::slice::from_ref(&wasm_path)));
+        assert!(!classify(&[project.join("dist").join("app.wasm")]));
+        assert!(!classify(&[project.join(".generated").join("main.cln")]));
+        assert!(!classify(&[tmp.path().with_file_name("elsewhere.cln")]));
+
+        let config_cln = [project.join("config.cln")];
+        let access = event(EventKind::Access(AccessKind::Any), &config_cln);
+        assert!(!is_watched_change(&access, &root, &wasm_path));
+        let created = event(EventKind::Create(CreateKind::File), &config_cln);
+        assert!(is_watched_change(&created, &root, &wasm_path));
+    }
+
+    #[test]
+    fn newest_changed_wasm_finds_fresh_artifact_in_subdir() {
+        let tmp = TempDir::new().unwrap();
+        let before = wasm_mtimes(tmp.path());
+        std::fs::create_dir_all(tmp.path().join("wasm")).unwrap();
+        std::fs::write(tmp.path().join("index.html"), b"<html>").unwrap();
+        std::fs::write(tmp.path().join("wasm").join("app.wasm"), b"\0asm1234").unwrap();
+
+        let found = newest_changed_wasm(tmp.path(), &before).unwrap();
+        assert_eq!(found, tmp.path().join("wasm").join("app.wasm"));
+    }
+
+    #[test]
+    fn newest_changed_wasm_ignores_stale_artifacts() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("old.wasm"), b"\0asm").unwrap();
+        let before = wasm_mtimes(tmp.path());
+
+        assert_eq!(newest_changed_wasm(tmp.path(), &before), None);
+        let missing = tmp.path().join("missing");
+        assert_eq!(newest_changed_wasm(&missing, &wasm_mtimes(&missing)), None);
+    }
+}
@@ -1,6 +1,10 @@
+use crate::core::config::Config;
+use crate::error::CleenError;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::time::Duration;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Release {
@@ -8,12 +12,23 @@ pub struct Release {
     pub name: String,
     pub prerelease: bool,
     pub draft: bool,
+    /// Branch or commit SHA the release's tag was created from.
+    #[serde(default)]
+    pub target_commitish: String,
     pub assets: Vec<Asset>,
 }
 
+#[derive(Debug, Deserialize)]
+struct Commit {
+    sha: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Asset {
     pub name: String,
+    /// Absolute URL as returned by the API (on GitHub Enterprise it already
+    /// points at the enterprise host). Used as-is, never rebuilt from the
+    /// client's base URL.
     pub browser_download_url: String,
     pub size: u64,
 }
@@ -26,10 +41,65 @@ struct GithubError {
 pub struct GitHubClient {
     #[allow(dead_code)]
     github_token: Option<String>,
+    /// How many times an API request is retried after a 5xx response or a
+    /// connection error. Set to 0 to fail on the first attempt.
+    pub retries: u32,
+    /// Delay before the first retry; it doubles on each further attempt.
+    backoff: Duration,
+    /// API root without a trailing slash, e.g. `https://api.github.com`.
+    base_url: String,
 }
 
+/// The public GitHub API, used unless `github_api_base_url` is configured.
+pub const DEFAULT_API_BASE_URL: &str = "https://api.github.com";
+
 const USER_AGENT: &str = concat!("cleen/", env!("CARGO_PKG_VERSION"));
 
+const DEFAULT_RETRIES: u32 = 3;
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// One response from the GitHub API: status, headers and body.
+#[derive(Debug)]
+struct GithubResponse {
+    status: Option<i32>,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl GithubResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn is_server_error(&self) -> bool {
+        matches!(self.status, Some(500..=599))
+    }
+
+    /// The rate-limit error for a 403 caused by an exhausted quota, with the
+    /// reset time from `X-RateLimit-Reset` (epoch seconds).
+    fn rate_limit_error(&self) -> Option<CleenError> {
+        if self.status != Some(403) || self.header("x-ratelimit-remaining") != Some("0") {
+            return None;
+        }
+        let resets_at = self
+            .header("x-ratelimit-reset")
+            .and_then(|reset| reset.parse::<i64>().ok())
+            .and_then(|reset| DateTime::<Utc>::from_timestamp(reset, 0))
+            .map(|reset| {
+                let minutes = (reset - Utc::now()).num_minutes().max(0);
+                format!(
+                    "{} (in {minutes} minute(s))",
+                    reset.format("%Y-%m-%d %H:%M UTC")
+                )
+            })
+            .unwrap_or_else(|| "an unknown time".to_string());
+        Some(CleenError::GitHubRateLimited { resets_at })
+    }
+}
+
 /// Parse a GitHub API response.
 ///
 /// Two failure modes are treated as user-facing errors instead of raw serde
@@ -85,9 +155,13 @@ fn parse_github_response<T: serde::de::DeserializeOwned>(
     }
 }
 
-fn curl_with_status(url: &str) -> Result<(Option<i32>, String)> {
+/// Run one GET request with curl. The outer error means curl couldn't be
+/// run at all; the inner one is a connection-level failure worth retrying.
+fn curl_response(url: &str) -> Result<Result<GithubResponse>> {
     let output = Command::new("curl")
         .arg("-sS")
+        .arg("-D")
+        .arg("-")
         .arg("-w")
         .arg("\n%{http_code}")
         .arg("-H")
@@ -99,40 +173,124 @@ fn curl_with_status(url: &str) -> Result<(Option<i32>, String)> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow::anyhow!(
+        return Ok(Err(anyhow::anyhow!(
             "curl failed (exit {:?}): {}",
             output.status.code(),
             stderr.trim()
-        ));
+        )));
     }
 
-    let full = String::from_utf8(output.stdout)?;
-    let (body, status_code) = match full.rfind('\n') {
+    Ok(Ok(parse_curl_output(&String::from_utf8(output.stdout)?)))
+}
+
+/// Split curl's `-D - -w "\n%{http_code}"` output into headers, body and
+/// status. Only the last header block is kept, so a proxy's `CONNECT`
+/// response doesn't hide GitHub's own headers.
+fn parse_curl_output(full: &str) -> GithubResponse {
+    let (mut rest, status) = match full.rfind('\n') {
         Some(idx) => {
             let (b, s) = full.split_at(idx);
             let s = s.trim_start_matches('\n').trim();
-            (b.to_string(), s.parse::<i32>().ok())
+            (b, s.parse::<i32>().ok())
         }
         None => (full, None),
     };
-    Ok((status_code, body))
+
+    let mut headers = Vec::new();
+    while rest.starts_with("HTTP/") {
+        let (block, body) = match rest.find("\r\n\r\n") {
+            Some(idx) => (&rest[..idx], &rest[idx + 4..]),
+            None => (rest, ""),
+        };
+        headers = block
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+        rest = body;
+    }
+
+    GithubResponse {
+        status,
+        headers,
+        body: rest.to_string(),
+    }
 }
 
 impl GitHubClient {
     pub fn new(github_token: Option<String>) -> Self {
-        Self { github_token }
+        Self {
+            github_token,
+            retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+            base_url: DEFAULT_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// A client using the token and API base URL from `config`.
+    pub fn from_config(config: &Config) -> Self {
+        let mut client = Self::new(config.github_api_token.clone());
+        if let Some(base_url) = config
+            .github_api_base_url
+            .as_deref()
+            .map(|url| url.trim().trim_end_matches('/'))
+            .filter(|url| !url.is_empty())
+        {
+            client.base_url = base_url.to_string();
+        }
+        client
+    }
+
+    fn repo_url(&self, repo_owner: &str, repo_name: &str, path: &str) -> String {
+        format!("{}/repos/{repo_owner}/{repo_name}/{path}", self.base_url)
+    }
+
+    /// GET `url` and parse the response, retrying 5xx responses and
+    /// connection errors with exponential backoff.
+    fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        self.with_retries(|| curl_response(url))
+    }
+
+    fn with_retries<T: serde::de::DeserializeOwned>(
+        &self,
+        mut request: impl FnMut() -> Result<Result<GithubResponse>>,
+    ) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            let outcome = request()?;
+            let retry_reason = match &outcome {
+                Ok(response) if response.is_server_error() => {
+                    Some(format!("HTTP {}", response.status.unwrap_or_default()))
+                }
+                Ok(_) => None,
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(reason) = retry_reason.filter(|_| attempt < self.retries) {
+                let delay = self.backoff * 2u32.pow(attempt);
+                eprintln!(
+                    "⚠️  GitHub request failed ({reason}), retrying in {:.1}s...",
+                    delay.as_secs_f64()
+                );
+                std::thread::sleep(delay);
+                attempt += 1;
+                continue;
+            }
+
+            let response = outcome?;
+            if let Some(rate_limited) = response.rate_limit_error() {
+                return Err(rate_limited.into());
+            }
+            return parse_github_response::<T>(response.status, &response.body);
+        }
     }
 
     pub fn get_releases(&self, repo_owner: &str, repo_name: &str) -> Result<Vec<Release>> {
-        let url = format!("https://api.github.com/repos/{repo_owner}/{repo_name}/releases");
-        let (status, body) = curl_with_status(&url)?;
-        parse_github_response::<Vec<Release>>(status, &body)
+        self.get_json(&self.repo_url(repo_owner, repo_name, "releases"))
     }
 
     pub fn get_latest_release(&self, repo_owner: &str, repo_name: &str) -> Result<Release> {
-        let url = format!("https://api.github.com/repos/{repo_owner}/{repo_name}/releases/latest");
-        let (status, body) = curl_with_status(&url)?;
-        parse_github_response::<Release>(status, &body)
+        self.get_json(&self.repo_url(repo_owner, repo_name, "releases/latest"))
     }
 
     /// Fetch a single release by tag. Uses the /releases/tags/<tag> endpoint,
@@ -145,10 +303,19 @@ impl GitHubClient {
         repo_name: &str,
         tag: &str,
     ) -> Result<Release> {
-        let url =
-            format!("https://api.github.com/repos/{repo_owner}/{repo_name}/releases/tags/{tag}");
-        let (status, body) = curl_with_status(&url)?;
-        parse_github_response::<Release>(status, &body)
+        self.get_json(&self.repo_url(repo_owner, repo_name, &format!("releases/tags/{tag}")))
+    }
+
+    /// Resolve a branch, tag or abbreviated SHA to the full commit SHA.
+    pub fn get_commit_sha(
+        &self,
+        repo_owner: &str,
+        repo_name: &str,
+        reference: &str,
+    ) -> Result<String> {
+        let commit: Commit =
+            self.get_json(&self.repo_url(repo_owner, repo_name, &format!("commits/{reference}")))?;
+        Ok(commit.sha)
     }
 
     #[allow(dead_code)]
@@ -267,4 +434,118 @@ mod tests {
         assert!(msg.contains("HTTP 500"), "got: {msg}");
         assert!(msg.contains("internal server error"), "got: {msg}");
     }
+
+    fn response(status: i32, headers: &[(&str, &str)], body: &str) -> GithubResponse {
+        GithubResponse {
+            status: Some(status),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            body: body.to_string(),
+        }
+    }
+
+    fn client(retries: u32) -> GitHubClient {
+        GitHubClient {
+            retries,
+            backoff: Duration::ZERO,
+            ..GitHubClient::new(None)
+        }
+    }
+
+    #[test]
+    fn parse_curl_output_keeps_last_header_block() {
+        let output = "HTTP/1.1 200 Connection established\r\n\r\n\
+                      HTTP/2 403\r\nx-ratelimit-remaining: 0\r\nX-RateLimit-Reset: 1700000000\r\n\r\n\
+                      {\"message\": \"API rate limit exceeded\"}\n403";
+        let parsed = parse_curl_output(output);
+        assert_eq!(parsed.status, Some(403));
+        assert_eq!(parsed.header("X-RateLimit-Remaining"), Some("0"));
+        assert_eq!(parsed.header("x-ratelimit-reset"), Some("1700000000"));
+        assert_eq!(parsed.body, r#"{"message": "API rate limit exceeded"}"#);
+    }
+
+    #[test]
+    fn retries_server_errors_and_connection_failures() {
+        let mut responses = vec![
+            Ok(response(200, &[], VALID_RELEASE)),
+            Err(anyhow::anyhow!("curl failed (exit Some(7))")),
+            Ok(response(503, &[], "unavailable")),
+        ];
+        let release: Release = client(2)
+            .with_retries(|| Ok(responses.pop().unwrap()))
+            .unwrap();
+        assert_eq!(release.tag_name, "v2.12.127");
+        assert!(responses.is_empty());
+
+        let mut calls = 0;
+        let err = client(0)
+            .with_retries::<Release>(|| {
+                calls += 1;
+                Ok(Ok(response(502, &[], "bad gateway")))
+            })
+            .unwrap_err();
+        assert_eq!(calls, 1);
+        assert!(err.to_string().contains("HTTP 502"), "got: {err}");
+    }
+
+    #[test]
+    fn exhausted_rate_limit_reports_reset_without_retrying() {
+        let mut calls = 0;
+        let err = client(3)
+            .with_retries::<Release>(|| {
+                calls += 1;
+                Ok(Ok(response(
+                    403,
+                    &[
+                        ("X-RateLimit-Remaining", "0"),
+                        ("X-RateLimit-Reset", "1700000000"),
+                    ],
+                    RATE_LIMIT_BODY,
+                )))
+            })
+            .unwrap_err();
+        assert_eq!(calls, 1);
+        match err.downcast_ref::<CleenError>() {
+            Some(CleenError::GitHubRateLimited { resets_at }) => {
+                assert!(
+                    resets_at.starts_with("2023-11-14 22:13 UTC"),
+                    "got: {resets_at}"
+                );
+            }
+            other => panic!("expected GitHubRateLimited, got {other:?}"),
+        }
+
+        // A 403 with quota left is a plain API error.
+        let err = client(0)
+            .with_retries::<Release>(|| {
+                Ok(Ok(response(
+                    403,
+                    &[("X-RateLimit-Remaining", "12")],
+                    NOT_FOUND_BODY,
+                )))
+            })
+            .unwrap_err();
+        assert!(err.downcast_ref::<CleenError>().is_none());
+        assert!(err.to_string().contains("HTTP 403"), "got: {err}");
+    }
+
+    #[test]
+    fn base_url_comes_from_config() {
+        let default = GitHubClient::from_config(&Config::default());
+        assert_eq!(
+            default.repo_url("Ivan-Pasco", "clean-language-compiler", "releases"),
+            "https://api.github.com/repos/Ivan-Pasco/clean-language-compiler/releases"
+        );
+
+        let enterprise = GitHubClient::from_config(&Config {
+            github_api_base_url: Some("https://github.example.com/api/v3/".to_string()),
+            ..Config::default()
+        });
+        assert_eq!(
+            enterprise.repo_url("Ivan-Pasco", "cleen-framework", "releases/tags/v1.0.0"),
+            "https://github.example.com/api/v3/repos/Ivan-Pasco/cleen-framework/releases/tags/v1.0.0"
+        );
+    }
 }
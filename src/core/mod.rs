@@ -1,9 +1,12 @@
+pub mod binaries;
 pub mod compatibility;
 pub mod config;
 pub mod download;
 pub mod frame;
 pub mod github;
 pub mod heartbeat;
+pub mod report;
 pub mod server;
 pub mod shim;
+pub mod size_cache;
 pub mod version;
@@ -0,0 +1,47 @@
+//! Structured results of installs and builds, shared by the command
+//! layer and [`crate::api`].
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Outcome of a compiler install (see [`crate::api::install`]).
+#[derive(Debug, Clone)]
+pub struct InstallReport {
+    /// The resolved version, without a `v` prefix.
+    pub version: String,
+    /// Absolute path to the installed `cln` binary.
+    pub binary_path: PathBuf,
+    /// `true` when the version was already installed and nothing was done.
+    pub already_installed: bool,
+    /// `true` when the release archive came from
+    /// `~/.cleen/cache/downloads` instead of the network.
+    pub from_archive_cache: bool,
+    /// Release asset that was installed; `None` when the version was
+    /// already installed.
+    pub asset_name: Option<String>,
+    /// Bytes downloaded; `0` when the version was already installed or
+    /// its archive came from `~/.cleen/cache/downloads`.
+    pub bytes: u64,
+    /// Wall-clock time the install took.
+    pub duration: Duration,
+    /// `true` when an existing install was replaced (`force`).
+    pub reinstalled: bool,
+}
+
+/// Outcome of a Frame project build (see [`crate::core::frame::build_project`]).
+#[derive(Debug, Clone)]
+pub struct BuildReport {
+    /// The input passed to `frame-cli build` (file or project directory).
+    pub entry: PathBuf,
+    /// The newest `.wasm` the build wrote under the output directory, if
+    /// one could be found.
+    pub wasm_path: Option<PathBuf>,
+    /// Size of `wasm_path` in bytes; `0` when no artifact was found.
+    pub size_bytes: u64,
+    /// Lines `frame-cli` reported as warnings on stderr.
+    pub warnings: Vec<String>,
+    /// Compiler version put on `frame-cli`'s PATH, when one was resolved.
+    pub compiler_version: Option<String>,
+    /// Wall-clock time the build took.
+    pub duration: Duration,
+}
@@ -1,4 +1,8 @@
-use crate::core::{config::Config, download::Downloader, github::GitHubClient};
+use crate::core::{
+    config::Config,
+    download::{self, Downloader},
+    github::GitHubClient,
+};
 use crate::error::{CleenError, Result};
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -37,7 +41,7 @@ pub fn install_server(version: Option<&str>) -> Result<()> {
         v.to_string()
     } else {
         // Get latest version from GitHub
-        let github_client = GitHubClient::new(config.github_api_token.clone());
+        let github_client = GitHubClient::from_config(&config);
         println!("Fetching latest Clean Server version...");
 
         let releases = match github_client.get_releases(SERVER_REPO_OWNER, SERVER_REPO_NAME) {
@@ -81,7 +85,7 @@ pub fn install_server(version: Option<&str>) -> Result<()> {
     }
 
     // Fetch releases from GitHub
-    let github_client = GitHubClient::new(config.github_api_token.clone());
+    let github_client = GitHubClient::from_config(&config);
     println!("Fetching Clean Server releases...");
 
     let releases = match github_client.get_releases(SERVER_REPO_OWNER, SERVER_REPO_NAME) {
@@ -106,26 +110,40 @@ pub fn install_server(version: Option<&str>) -> Result<()> {
     let asset = release
         .assets
         .iter()
-        .find(|a| a.name.contains(&asset_name))
+        .find(|a| a.name.contains(&asset_name) && !download::is_checksum_asset(&a.name))
         .ok_or_else(|| CleenError::ServerAssetNotFound {
             version: server_version.clone(),
             platform: asset_name.clone(),
         })?;
 
+    crate::utils::fs::ensure_install_space(&version_dir, asset.size)?;
+
     println!("Downloading {asset_name}...");
 
-    // Create version directory
+    // Create version directory. The guard removes it again if the download
+    // or extraction fails so the next attempt starts clean.
+    let version_dir_guard = crate::utils::fs::PartialDirGuard::new(&version_dir);
     std::fs::create_dir_all(&version_dir)?;
 
     // Download the asset
     let downloader = Downloader::new();
     let download_path = version_dir.join(&asset.name);
-    downloader.download_file(&asset.browser_download_url, &download_path)?;
+    downloader.download_file(&asset.browser_download_url, &download_path, &mut |line| {
+        println!("{line}")
+    })?;
+    download::verify_checksum(
+        &downloader,
+        download::checksum_asset(&release.assets, &asset.name),
+        &download_path,
+        &mut |line| println!("{line}"),
+    )?;
 
     // Extract if it's a compressed file
-    if asset.name.ends_with(".tar.gz") || asset.name.ends_with(".zip") {
+    if download::is_archive(&asset.name) {
         println!("Extracting...");
-        extract_archive(&download_path, &version_dir)?;
+        downloader
+            .extract_archive(&download_path, &version_dir, &mut |line| println!("{line}"))
+            .map_err(|e| CleenError::extraction_failed(e, &download_path))?;
         std::fs::remove_file(&download_path)?;
     }
 
@@ -140,6 +158,7 @@ pub fn install_server(version: Option<&str>) -> Result<()> {
             std::fs::set_permissions(&binary_path, perms)?;
         }
     }
+    version_dir_guard.disarm();
 
     println!("✅ Clean Server {server_version} installed successfully!");
 
@@ -349,52 +368,6 @@ fn get_platform_asset_name() -> String {
     format!("{os}-{arch}")
 }
 
-/// Extract an archive (tar.gz or zip)
-fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<()> {
-    let archive_name = archive_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("");
-
-    if archive_name.ends_with(".tar.gz") {
-        // Use tar command
-        let status = Command::new("tar")
-            .args(["-xzf"])
-            .arg(archive_path)
-            .args(["-C"])
-            .arg(dest_dir)
-            .status()
-            .map_err(|e| CleenError::IoError {
-                message: format!("Failed to extract tar.gz: {e}"),
-            })?;
-
-        if !status.success() {
-            return Err(CleenError::IoError {
-                message: "tar extraction failed".to_string(),
-            });
-        }
-    } else if archive_name.ends_with(".zip") {
-        // Use unzip command
-        let status = Command::new("unzip")
-            .args(["-o"])
-            .arg(archive_path)
-            .args(["-d"])
-            .arg(dest_dir)
-            .status()
-            .map_err(|e| CleenError::IoError {
-                message: format!("Failed to extract zip: {e}"),
-            })?;
-
-        if !status.success() {
-            return Err(CleenError::IoError {
-                message: "unzip extraction failed".to_string(),
-            });
-        }
-    }
-
-    Ok(())
-}
-
 /// Compare two version strings (semver-like)
 fn version_compare(a: &str, b: &str) -> std::cmp::Ordering {
     let parse = |s: &str| -> Vec<u32> {
@@ -1,12 +1,40 @@
-use crate::core::{config::Config, version::normalize};
+use crate::core::{
+    binaries,
+    config::Config,
+    version::{self, normalize, VersionManager, VersionMetadata},
+};
 use crate::error::{CleenError, Result};
 use crate::utils::fs;
-use std::path::Path;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// First argument the Windows `cln.bat` shim passes to `cleen.exe` so it
+/// dispatches to the compiler instead of parsing its own CLI. On Unix the
+/// shim is a symlink named `cln`, which is detected from `argv[0]`.
+pub const SHIM_DISPATCH_ARG: &str = "__cln-shim";
+
+/// Skip rewriting `last_used` when it was already bumped this recently,
+/// so back-to-back `cln` invocations don't each pay for a file write.
+const LAST_USED_WRITE_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 pub struct ShimManager {
     config: Config,
 }
 
+/// What the `cln` shim in `~/.cleen/bin` currently points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShimTarget {
+    /// Routes through `cleen`, which picks the version on every run.
+    Trampoline,
+    /// Links straight to the binary of one installed version.
+    Version(String),
+    /// Points somewhere outside the versions directory.
+    Other(PathBuf),
+    /// No shim, or one that can't be read.
+    Missing,
+}
+
 impl ShimManager {
     pub fn new(config: Config) -> Self {
         Self { config }
@@ -28,11 +56,11 @@ impl ShimManager {
         let shim_name = shim_path
             .file_name()
             .and_then(|n| n.to_str())
-            .unwrap_or("cln");
+            .unwrap_or(binaries::CLN_SHIM);
         let lsp_name = lsp_shim_path
             .file_name()
             .and_then(|n| n.to_str())
-            .unwrap_or("clean-language-server");
+            .unwrap_or(binaries::LSP);
         let _ = fs::evict_locked_shims(&bin_dir, &[shim_name, lsp_name]);
 
         // Remove existing shim if it exists
@@ -47,6 +75,8 @@ impl ShimManager {
         // Also link clean-language-server if present in this version
         self.create_lsp_shim(&clean_version)?;
 
+        VersionMetadata::record_use(&self.config.get_version_dir(&clean_version));
+
         println!("✅ Activated Clean Language version {clean_version}");
 
         Ok(())
@@ -92,7 +122,12 @@ impl ShimManager {
         self.config.get_effective_version()
     }
 
-    /// Create a smart shim that checks for project-specific versions
+    /// Create a smart shim that checks for project-specific versions.
+    ///
+    /// The shim points at the `cleen` executable, which resolves the
+    /// effective version on every run (see [`dispatch_if_shim`]). If the
+    /// `cleen` executable can't be located, the shim falls back to pointing
+    /// straight at this version's binary.
     fn create_smart_shim(&self, shim_path: &Path, version: &str) -> Result<()> {
         // Find the actual binary path (checking both clean and v-prefixed versions)
         let binary_path = {
@@ -112,8 +147,31 @@ impl ShimManager {
             }
         };
 
-        // Create a wrapper script instead of a direct link to prevent exec issues
-        self.create_wrapper_script(&binary_path, shim_path)?;
+        match dispatcher_exe() {
+            Some(cleen) => self.create_dispatch_shim(&cleen, shim_path),
+            None => self.create_wrapper_script(&binary_path, shim_path),
+        }
+    }
+
+    #[cfg(unix)]
+    fn create_dispatch_shim(&self, cleen: &Path, shim_path: &Path) -> Result<()> {
+        // Same symlink approach as `create_wrapper_script`; `cleen` sees
+        // `cln` as its argv[0] and dispatches.
+        fs::atomic_replace_symlink(shim_path, cleen)?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn create_dispatch_shim(&self, cleen: &Path, shim_path: &Path) -> Result<()> {
+        let mut shim_path = shim_path.to_path_buf();
+        shim_path.set_extension("bat");
+
+        let script_content = format!(
+            "@echo off\n\"{}\" {SHIM_DISPATCH_ARG} %*\n",
+            cleen.display()
+        );
+
+        fs::atomic_write(&shim_path, script_content.as_bytes(), None)?;
         Ok(())
     }
 
@@ -189,8 +247,292 @@ impl ShimManager {
         Ok(())
     }
 
+    /// Whether the `cln` shim routes through `cleen` (and so honours
+    /// project pins) rather than linking straight to one version's binary,
+    /// as shims from older releases do.
+    pub fn is_trampoline(&self) -> bool {
+        self.shim_target() == ShimTarget::Trampoline
+    }
+
+    /// Read the `cln` shim back and map its target to a version, so callers
+    /// can spot a shim that no longer matches `config.active_version`.
+    pub fn shim_target(&self) -> ShimTarget {
+        let shim_path = self.config.get_shim_path();
+        let target = if cfg!(windows) {
+            let Ok(script) = std::fs::read_to_string(shim_path.with_extension("bat")) else {
+                return ShimTarget::Missing;
+            };
+            if script.contains(SHIM_DISPATCH_ARG) {
+                return ShimTarget::Trampoline;
+            }
+            // The version shim is `"<binary>" %*`.
+            match script.split('"').nth(1) {
+                Some(path) => PathBuf::from(path),
+                None => return ShimTarget::Missing,
+            }
+        } else {
+            match std::fs::read_link(&shim_path) {
+                Ok(target) => target,
+                Err(_) => return ShimTarget::Missing,
+            }
+        };
+
+        if target.file_stem().is_some_and(|stem| stem == "cleen") {
+            return ShimTarget::Trampoline;
+        }
+        match target
+            .strip_prefix(self.config.get_versions_dir())
+            .ok()
+            .and_then(|rest| rest.components().next())
+        {
+            Some(std::path::Component::Normal(version)) => {
+                ShimTarget::Version(normalize::to_clean_version(&version.to_string_lossy()))
+            }
+            _ => ShimTarget::Other(target),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn get_config(&self) -> &Config {
         &self.config
     }
 }
+
+/// The `cleen` executable the shim should dispatch through. When running
+/// from the standalone `frame` binary, look for `cleen` beside it.
+fn dispatcher_exe() -> Option<PathBuf> {
+    let current = std::env::current_exe().ok()?;
+    if current.file_stem().is_some_and(|stem| stem == "cleen") {
+        return Some(current);
+    }
+    let sibling = current.with_file_name(if cfg!(windows) { "cleen.exe" } else { "cleen" });
+    sibling.exists().then_some(sibling)
+}
+
+/// If this process was started through the `cln` shim, run the resolved
+/// compiler and return its exit code. Returns `None` for a normal `cleen`
+/// invocation so `main` can carry on parsing its own CLI.
+pub fn dispatch_if_shim() -> Option<i32> {
+    let mut args: Vec<OsString> = std::env::args_os().collect();
+    let invoked_as_cln = args
+        .first()
+        .and_then(|arg0| Path::new(arg0).file_stem())
+        .is_some_and(|stem| stem == binaries::CLN_SHIM);
+
+    let forwarded = if invoked_as_cln {
+        args.split_off(1)
+    } else if args.get(1).is_some_and(|arg| arg == SHIM_DISPATCH_ARG) {
+        args.split_off(2)
+    } else {
+        return None;
+    };
+
+    Some(run_shim(&forwarded).unwrap_or_else(|e| {
+        eprintln!("cln: {e}");
+        1
+    }))
+}
+
+fn run_shim(args: &[OsString]) -> Result<i32> {
+    let config = Config::load()?;
+    let cwd = std::env::current_dir().ok();
+    let binary_path = resolve_shim_target(&config, cwd.as_deref())?;
+
+    if let Some(version_dir) = binary_path.parent() {
+        record_shim_use(version_dir);
+    }
+
+    exec_compiler(&binary_path, args)
+}
+
+/// Resolve the compiler binary `cln` should run from `cwd`: the project's
+/// `.cleanlanguage/.cleanversion` pin when `shim_project_version` is on,
+/// otherwise the global active version. A pin naming a version that isn't
+/// installed is an error rather than a silent fallback, so a project never
+/// builds with a compiler it didn't ask for.
+pub fn resolve_shim_target(config: &Config, cwd: Option<&Path>) -> Result<PathBuf> {
+    let project_version = if config.shim_project_version {
+        cwd.and_then(|dir| config.find_version_file_in_tree(dir))
+    } else {
+        None
+    };
+    let version = match project_version {
+        Some(spec) => VersionManager::new(config.clone()).resolve_version_spec(&spec)?,
+        None => config
+            .active_version
+            .clone()
+            .ok_or(CleenError::NoActiveVersion)?,
+    };
+
+    // The pin comes from a file in the project; keep it inside ~/.cleen.
+    version::validate_version(&version)?;
+
+    let clean_version = normalize::to_clean_version(&version);
+    [
+        clean_version.clone(),
+        normalize::to_github_version(&clean_version),
+    ]
+    .iter()
+    .map(|v| config.get_version_binary(v))
+    .find(|path| path.exists())
+    .ok_or(CleenError::VersionNotFound {
+        version: clean_version,
+    })
+}
+
+/// Bump `last_used` for the version the shim is about to run. Best-effort
+/// and throttled: any failure is ignored, and a timestamp younger than
+/// [`LAST_USED_WRITE_INTERVAL`] is left alone.
+pub fn record_shim_use(version_dir: &Path) {
+    let recent = VersionMetadata::load(version_dir)
+        .last_used
+        .and_then(|t| (chrono::Utc::now() - t).to_std().ok())
+        .is_some_and(|age| age < LAST_USED_WRITE_INTERVAL);
+    if !recent {
+        VersionMetadata::record_use(version_dir);
+    }
+}
+
+#[cfg(unix)]
+fn exec_compiler(binary_path: &Path, args: &[OsString]) -> Result<i32> {
+    use std::os::unix::process::CommandExt;
+    // Only returns on failure; on success the compiler replaces this process.
+    let err = std::process::Command::new(binary_path).args(args).exec();
+    Err(err.into())
+}
+
+#[cfg(windows)]
+fn exec_compiler(binary_path: &Path, args: &[OsString]) -> Result<i32> {
+    let status = std::process::Command::new(binary_path)
+        .args(args)
+        .status()?;
+    Ok(status.code().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(cleen_dir: &Path) -> Config {
+        Config {
+            active_version: Some("1.0.0".to_string()),
+            cleen_dir: cleen_dir.to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    fn install_fake(config: &Config, version: &str) -> PathBuf {
+        let binary = config.get_version_binary(version);
+        std::fs::create_dir_all(binary.parent().unwrap()).unwrap();
+        std::fs::write(&binary, b"").unwrap();
+        binary
+    }
+
+    fn pin_project(dir: &Path, version: &str) {
+        std::fs::create_dir_all(dir.join(".cleanlanguage")).unwrap();
+        std::fs::write(
+            dir.join(".cleanlanguage").join(".cleanversion"),
+            format!("{version}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn resolve_prefers_project_pin_over_active_version() {
+        let home = TempDir::new().unwrap();
+        let mut config = test_config(home.path());
+        let global = install_fake(&config, "1.0.0");
+        let pinned = install_fake(&config, "2.0.0");
+
+        let project = TempDir::new().unwrap();
+        pin_project(project.path(), "2.0.0");
+        let nested = project.path().join("src").join("pages");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(resolve_shim_target(&config, Some(&nested)).unwrap(), pinned);
+
+        let elsewhere = TempDir::new().unwrap();
+        assert_eq!(
+            resolve_shim_target(&config, Some(elsewhere.path())).unwrap(),
+            global
+        );
+
+        config.shim_project_version = false;
+        assert_eq!(resolve_shim_target(&config, Some(&nested)).unwrap(), global);
+    }
+
+    #[test]
+    fn resolve_handles_v_prefix_and_missing_or_unsafe_pins() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path());
+        let legacy = install_fake(&config, "v3.0.0");
+
+        let project = TempDir::new().unwrap();
+        pin_project(project.path(), "3.0.0");
+        assert_eq!(
+            resolve_shim_target(&config, Some(project.path())).unwrap(),
+            legacy
+        );
+
+        pin_project(project.path(), "9.9.9");
+        assert!(matches!(
+            resolve_shim_target(&config, Some(project.path())),
+            Err(CleenError::VersionNotFound { .. })
+        ));
+
+        pin_project(project.path(), "../../etc");
+        assert!(matches!(
+            resolve_shim_target(&config, Some(project.path())),
+            Err(CleenError::InvalidVersion { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shim_target_maps_static_links_back_to_versions() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path());
+        let binary = install_fake(&config, "v1.0.0");
+        let manager = ShimManager::new(config.clone());
+        assert_eq!(manager.shim_target(), ShimTarget::Missing);
+
+        std::fs::create_dir_all(config.get_bin_dir()).unwrap();
+        let shim = config.get_shim_path();
+        fs::atomic_replace_symlink(&shim, &binary).unwrap();
+        assert_eq!(
+            manager.shim_target(),
+            ShimTarget::Version("1.0.0".to_string())
+        );
+
+        fs::atomic_replace_symlink(&shim, Path::new("/usr/local/bin/cln")).unwrap();
+        assert_eq!(
+            manager.shim_target(),
+            ShimTarget::Other(PathBuf::from("/usr/local/bin/cln"))
+        );
+
+        fs::atomic_replace_symlink(&shim, &home.path().join("bin").join("cleen")).unwrap();
+        assert!(manager.is_trampoline());
+    }
+
+    #[test]
+    fn record_shim_use_is_best_effort_and_throttled() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path());
+        let binary = install_fake(&config, "1.0.0");
+        let version_dir = binary.parent().unwrap();
+
+        record_shim_use(version_dir);
+        let first = VersionMetadata::load(version_dir).last_used;
+        assert!(first.is_some());
+
+        // A second run inside the interval leaves the timestamp alone.
+        record_shim_use(version_dir);
+        assert_eq!(VersionMetadata::load(version_dir).last_used, first);
+
+        // A missing directory is silently skipped, never created.
+        let missing = home.path().join("versions").join("gone");
+        record_shim_use(&missing);
+        assert!(!missing.exists());
+    }
+}
@@ -0,0 +1,164 @@
+//! On-disk cache of installed directory sizes.
+//!
+//! Version directories are immutable once installed, so walking them on
+//! every `cleen cleanup` is wasted work. Sizes are stored in
+//! `~/.cleen/cache/sizes.json`, keyed by the directory's path relative to
+//! `~/.cleen` and tagged with its mtime. Install, repair and reinstall all
+//! swap in a freshly created directory, which changes the mtime and
+//! forces a recompute.
+
+use crate::core::config::Config;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const SIZE_CACHE_FILE: &str = "sizes.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedSize {
+    /// Directory mtime in nanoseconds since the Unix epoch.
+    mtime: u128,
+    bytes: u64,
+}
+
+#[derive(Debug)]
+pub struct SizeCache {
+    root: PathBuf,
+    path: PathBuf,
+    entries: BTreeMap<String, CachedSize>,
+    dirty: bool,
+    recomputed: usize,
+}
+
+impl SizeCache {
+    /// Load the cache for `config.cleen_dir`. A missing or corrupt cache
+    /// file starts empty; it is only an optimisation.
+    pub fn load(config: &Config) -> Self {
+        let root = config.cleen_dir.clone();
+        let path = root.join("cache").join(SIZE_CACHE_FILE);
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            root,
+            path,
+            entries,
+            dirty: false,
+            recomputed: 0,
+        }
+    }
+
+    /// Size of `dir` in bytes, served from the cache when the directory's
+    /// mtime hasn't changed since it was last measured.
+    pub fn size_of(&mut self, dir: &Path) -> u64 {
+        let key = self.key_for(dir);
+        let Some(mtime) = dir_mtime(dir) else {
+            // Unreadable or gone: nothing worth caching.
+            self.entries.remove(&key);
+            return crate::utils::fs::dir_size(dir);
+        };
+
+        if let Some(cached) = self.entries.get(&key) {
+            if cached.mtime == mtime {
+                return cached.bytes;
+            }
+        }
+
+        let bytes = crate::utils::fs::dir_size(dir);
+        self.recomputed += 1;
+        self.entries.insert(key, CachedSize { mtime, bytes });
+        self.dirty = true;
+        bytes
+    }
+
+    /// Number of directories actually walked since the cache was loaded.
+    pub fn recomputed(&self) -> usize {
+        self.recomputed
+    }
+
+    /// Persist the cache if anything changed, dropping entries for
+    /// directories that no longer exist. Failures are ignored — the next
+    /// run simply recomputes.
+    pub fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let root = self.root.clone();
+        self.entries.retain(|key, _| root.join(key).exists());
+        if let Ok(content) = serde_json::to_string_pretty(&self.entries) {
+            if crate::utils::fs::atomic_write(&self.path, content.as_bytes(), None).is_ok() {
+                self.dirty = false;
+            }
+        }
+    }
+
+    fn key_for(&self, dir: &Path) -> String {
+        dir.strip_prefix(&self.root)
+            .unwrap_or(dir)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+fn dir_mtime(dir: &Path) -> Option<u128> {
+    let modified = std::fs::metadata(dir).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(cleen_dir: &Path) -> Config {
+        Config {
+            cleen_dir: cleen_dir.to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn unchanged_dir_is_served_from_cache_across_loads() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(tmp.path());
+        let version_dir = config.get_version_dir("1.2.3");
+        std::fs::create_dir_all(version_dir.join("bin")).unwrap();
+        std::fs::write(version_dir.join("bin").join("cln"), vec![0u8; 1024]).unwrap();
+
+        let mut cache = SizeCache::load(&config);
+        assert_eq!(cache.size_of(&version_dir), 1024);
+        assert_eq!(cache.size_of(&version_dir), 1024);
+        assert_eq!(cache.recomputed(), 1);
+        cache.save();
+        assert!(tmp.path().join("cache").join(SIZE_CACHE_FILE).exists());
+
+        let mut reloaded = SizeCache::load(&config);
+        assert_eq!(reloaded.size_of(&version_dir), 1024);
+        assert_eq!(reloaded.recomputed(), 0);
+    }
+
+    #[test]
+    fn mtime_change_invalidates_cached_size() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(tmp.path());
+        let version_dir = config.get_version_dir("1.2.3");
+        std::fs::create_dir_all(&version_dir).unwrap();
+        std::fs::write(version_dir.join("cln"), vec![0u8; 10]).unwrap();
+
+        let mut cache = SizeCache::load(&config);
+        assert_eq!(cache.size_of(&version_dir), 10);
+
+        // Stand-in for a reinstall swapping the directory: the contents
+        // change and the directory's mtime moves.
+        std::fs::write(version_dir.join("cln"), vec![0u8; 20]).unwrap();
+        let mut cached = cache.entries[&cache.key_for(&version_dir)];
+        cached.mtime -= 1;
+        cache.entries.insert(cache.key_for(&version_dir), cached);
+
+        assert_eq!(cache.size_of(&version_dir), 20);
+        assert_eq!(cache.recomputed(), 2);
+    }
+}
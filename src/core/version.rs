@@ -1,7 +1,10 @@
 use crate::core::config::Config;
 use crate::error::{CleenError, Result};
 use crate::utils::fs;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::fs::read_dir;
+use std::path::Path;
 
 /// Version normalization utilities
 pub mod normalize {
@@ -40,6 +43,84 @@ pub struct VersionInfo {
     pub is_active: bool,
     pub binary_path: std::path::PathBuf,
     pub is_valid: bool,
+    pub metadata: VersionMetadata,
+}
+
+/// File inside each version directory holding [`VersionMetadata`].
+pub const VERSION_METADATA_FILE: &str = ".cleen-meta.json";
+
+/// Install and usage timestamps for one installed compiler version.
+///
+/// `last_used` is bumped whenever the version is activated and when the
+/// `cln` shim runs it (see `core::shim::record_shim_use`). Shims created
+/// by older releases link straight to the binary, so
+/// [`VersionMetadata::last_used_at`] also consults the binary's access
+/// time as a best-effort signal for "ran recently".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl VersionMetadata {
+    /// Read the metadata for `version_dir`. Versions installed before this
+    /// file existed, or with an unreadable one, get empty metadata.
+    pub fn load(version_dir: &Path) -> Self {
+        std::fs::read_to_string(version_dir.join(VERSION_METADATA_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the metadata in place. Overwriting rather than rename-replacing
+    /// keeps the version directory's mtime stable, which the size cache
+    /// uses to detect a changed install.
+    pub fn save(&self, version_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(version_dir.join(VERSION_METADATA_FILE), content)?;
+        Ok(())
+    }
+
+    /// Stamp a fresh install. Best-effort: a failure only loses the date.
+    pub fn record_install(version_dir: &Path) {
+        let metadata = Self {
+            installed_at: Some(Utc::now()),
+            last_used: None,
+        };
+        let _ = metadata.save(version_dir);
+    }
+
+    /// Stamp an activation. Best-effort, like [`Self::record_install`].
+    pub fn record_use(version_dir: &Path) {
+        if !version_dir.is_dir() {
+            return;
+        }
+        let mut metadata = Self::load(version_dir);
+        metadata.last_used = Some(Utc::now());
+        let _ = metadata.save(version_dir);
+    }
+
+    /// Most recent known use: the recorded activation or the binary's
+    /// access time, whichever is later. The access time is ignored until
+    /// it moves past the install (validation runs the binary once) and on
+    /// filesystems that don't track it.
+    pub fn last_used_at(&self, binary_path: &Path) -> Option<DateTime<Utc>> {
+        let accessed = std::fs::metadata(binary_path)
+            .and_then(|m| m.accessed())
+            .ok()
+            .map(DateTime::<Utc>::from)
+            .filter(|accessed| match self.installed_at {
+                Some(installed) => *accessed > installed + chrono::Duration::minutes(1),
+                None => false,
+            });
+
+        match (self.last_used, accessed) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        }
+    }
 }
 
 pub struct VersionManager {
@@ -51,6 +132,9 @@ impl VersionManager {
         Self { config }
     }
 
+    /// Installed compiler versions, sorted. The `frame` directory (Frame
+    /// CLI versions) and hidden directories, such as the staging and
+    /// backup copies an interrupted reinstall leaves behind, are skipped.
     pub fn list_installed_versions(&self) -> Result<Vec<VersionInfo>> {
         let versions_dir = self.config.get_versions_dir();
 
@@ -65,7 +149,11 @@ impl VersionManager {
             let path = entry.path();
 
             if path.is_dir() {
-                if let Some(version_name) = path.file_name().and_then(|n| n.to_str()) {
+                if let Some(version_name) = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .filter(|name| is_version_dir_name(name))
+                {
                     let binary_path = self.config.get_version_binary(version_name);
                     let is_valid = binary_path.exists() && fs::is_executable(&binary_path);
                     let is_active = self
@@ -80,6 +168,7 @@ impl VersionManager {
                         is_active,
                         binary_path,
                         is_valid,
+                        metadata: VersionMetadata::load(&path),
                     });
                 }
             }
@@ -151,28 +240,269 @@ impl VersionManager {
     }
 
     pub fn validate_version(&self, version: &str) -> Result<()> {
-        if version.is_empty() {
-            return Err(CleenError::InvalidVersion {
-                version: version.to_string(),
-            });
+        validate_version(version)
+    }
+
+    /// Turn a `.cleanversion` pin into a concrete version. Exact versions
+    /// come back unchanged, installed or not; a range resolves to the
+    /// highest installed version satisfying it.
+    pub fn resolve_version_spec(&self, spec: &str) -> Result<String> {
+        let Some(range) = VersionRange::parse(spec)? else {
+            return Ok(spec.trim().to_string());
+        };
+        let installed = self.list_installed_versions()?;
+        match range.highest_match(&installed) {
+            Some(info) => Ok(normalize::to_clean_version(&info.version)),
+            None => {
+                let considered: Vec<&str> = installed
+                    .iter()
+                    .filter(|info| info.is_valid)
+                    .map(|info| info.version.as_str())
+                    .collect();
+                Err(CleenError::NoVersionMatchesRange {
+                    range: range.to_string(),
+                    considered: if considered.is_empty() {
+                        "none".to_string()
+                    } else {
+                        considered.join(", ")
+                    },
+                })
+            }
         }
+    }
 
-        // Basic validation - could be enhanced with semver parsing
-        if version.contains("..") || version.contains('/') || version.contains('\\') {
+    #[allow(dead_code)]
+    pub fn get_config(&self) -> &Config {
+        &self.config
+    }
+}
+
+/// Whether a directory under `versions/` holds a compiler version, rather
+/// than Frame CLI versions or a hidden leftover of an interrupted install.
+pub(crate) fn is_version_dir_name(name: &str) -> bool {
+    name != "frame" && !name.starts_with('.')
+}
+
+/// Reject version strings that are unsafe to join onto the versions
+/// directory. Commands call this before touching the filesystem so input
+/// like `../../etc` can never resolve outside `~/.cleen`.
+pub fn validate_version(version: &str) -> Result<()> {
+    let invalid = version.is_empty()
+        || version.starts_with('.')
+        || version.contains("..")
+        || version.contains(['/', '\\', ':'])
+        || version.chars().any(|c| c.is_control() || c.is_whitespace());
+
+    if invalid {
+        return Err(CleenError::InvalidVersion {
+            version: version.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Prefix of the directory a pre-release build installs under, e.g.
+/// `versions/nightly-1a2b3c4`, so it never collides with a tagged release.
+pub const NIGHTLY_PREFIX: &str = "nightly-";
+
+/// A pre-release compiler build requested by `cleen install nightly` or
+/// `cleen install sha:<hash>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NightlySpec {
+    /// The build published under the `nightly` tag.
+    Latest,
+    /// The pre-release built from this commit (lowercase hex, 7-40 chars).
+    Commit(String),
+}
+
+impl NightlySpec {
+    /// `Ok(None)` for anything that isn't a nightly request. A `sha:`
+    /// prefix without a hex hash is an `InvalidVersion`.
+    pub fn parse(spec: &str) -> Result<Option<Self>> {
+        if spec == "nightly" {
+            return Ok(Some(Self::Latest));
+        }
+        let Some(hash) = spec.strip_prefix("sha:") else {
+            return Ok(None);
+        };
+        if !(7..=40).contains(&hash.len()) || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
             return Err(CleenError::InvalidVersion {
-                version: version.to_string(),
+                version: spec.to_string(),
             });
         }
+        Ok(Some(Self::Commit(hash.to_ascii_lowercase())))
+    }
+}
 
-        Ok(())
+/// The local version name for a pre-release build of commit `sha`.
+pub fn nightly_version(sha: &str) -> String {
+    format!("{NIGHTLY_PREFIX}{}", &sha[..sha.len().min(7)])
+}
+
+/// Whether an installed version is a pre-release build rather than a
+/// tagged release.
+pub fn is_nightly(version: &str) -> bool {
+    version.starts_with(NIGHTLY_PREFIX)
+}
+
+/// A semver-style range pinned in `.cleanlanguage/.cleanversion`, such as
+/// `^0.14.0`, `~0.14.1`, `0.14.x` or `>=0.14.0 <0.15.0`. Every comparator
+/// must hold. Pre-release versions never satisfy a range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    spec: String,
+    comparators: Vec<(RangeOp, [u64; 3])>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeOp {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl VersionRange {
+    /// Parse `spec` as a range. Returns `Ok(None)` for a plain exact version
+    /// so callers can keep treating it as before, and an error for a range
+    /// that doesn't parse.
+    pub fn parse(spec: &str) -> Result<Option<Self>> {
+        let spec = spec.trim();
+        let tokens: Vec<&str> = spec.split([' ', ',']).filter(|t| !t.is_empty()).collect();
+        let is_range = tokens.len() > 1
+            || spec.starts_with(['^', '~', '>', '<', '='])
+            || spec.split('.').any(|part| matches!(part, "x" | "X" | "*"));
+        if !is_range {
+            return Ok(None);
+        }
+
+        let invalid = || CleenError::InvalidVersion {
+            version: spec.to_string(),
+        };
+        let mut comparators = Vec::new();
+        let mut pending_op: Option<&str> = None;
+        for token in tokens {
+            // Allow `>= 0.14.0` as well as `>=0.14.0`.
+            let token = match pending_op.take() {
+                Some(op) => format!("{op}{token}"),
+                None if matches!(token, ">=" | ">" | "<=" | "<" | "=") => {
+                    pending_op = Some(token);
+                    continue;
+                }
+                None => token.to_string(),
+            };
+            comparators.extend(parse_comparator(&token).ok_or_else(invalid)?);
+        }
+        if pending_op.is_some() || comparators.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Some(Self {
+            spec: spec.to_string(),
+            comparators,
+        }))
     }
 
-    #[allow(dead_code)]
-    pub fn get_config(&self) -> &Config {
-        &self.config
+    /// Whether an installed version name satisfies every comparator.
+    pub fn matches(&self, version: &str) -> bool {
+        let clean = normalize::to_clean_version(version);
+        if clean.contains('-') {
+            return false;
+        }
+        let Some((version, _)) = parse_partial(&clean) else {
+            return false;
+        };
+        self.comparators.iter().all(|(op, bound)| match op {
+            RangeOp::Eq => version == *bound,
+            RangeOp::Gt => version > *bound,
+            RangeOp::Gte => version >= *bound,
+            RangeOp::Lt => version < *bound,
+            RangeOp::Lte => version <= *bound,
+        })
+    }
+
+    /// Highest valid installed version in `installed` that satisfies the
+    /// range.
+    pub fn highest_match<'a>(&self, installed: &'a [VersionInfo]) -> Option<&'a VersionInfo> {
+        installed
+            .iter()
+            .filter(|info| info.is_valid && self.matches(&info.version))
+            .max_by(|a, b| version_compare(&a.version, &b.version))
     }
 }
 
+impl std::fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.spec)
+    }
+}
+
+/// Expand one range token into comparators.
+fn parse_comparator(token: &str) -> Option<Vec<(RangeOp, [u64; 3])>> {
+    let (prefix, rest) = ["^", "~", ">=", "<=", ">", "<", "="]
+        .iter()
+        .find_map(|prefix| token.strip_prefix(prefix).map(|rest| (*prefix, rest)))
+        .unwrap_or(("", token));
+
+    let (version, given) = parse_partial(&normalize::to_clean_version(rest))?;
+    let [major, minor, patch] = version;
+    let partial = given < 3;
+    // Exclusive upper bound once the first `parts` components are fixed:
+    // `0.14` (or `0.14.x`) covers every 0.14 patch release.
+    let next = |parts: usize| match parts {
+        1 => [major + 1, 0, 0],
+        2 => [major, minor + 1, 0],
+        _ => [major, minor, patch + 1],
+    };
+
+    Some(match prefix {
+        "^" => {
+            let upper = if major > 0 || given == 1 {
+                next(1)
+            } else if minor > 0 || given == 2 {
+                next(2)
+            } else {
+                next(3)
+            };
+            vec![(RangeOp::Gte, version), (RangeOp::Lt, upper)]
+        }
+        "~" => vec![(RangeOp::Gte, version), (RangeOp::Lt, next(given.min(2)))],
+        ">=" => vec![(RangeOp::Gte, version)],
+        "<" => vec![(RangeOp::Lt, version)],
+        ">" if partial => vec![(RangeOp::Gte, next(given))],
+        ">" => vec![(RangeOp::Gt, version)],
+        "<=" if partial => vec![(RangeOp::Lt, next(given))],
+        "<=" => vec![(RangeOp::Lte, version)],
+        _ if partial => vec![(RangeOp::Gte, version), (RangeOp::Lt, next(given))],
+        _ => vec![(RangeOp::Eq, version)],
+    })
+}
+
+/// Parse `1`, `1.2`, `1.2.3` or `1.2.x` into a full version plus the number
+/// of parts actually given. Only wildcards may follow a wildcard.
+fn parse_partial(version: &str) -> Option<([u64; 3], usize)> {
+    let mut parts = [0u64; 3];
+    let mut given = 0;
+    let mut wildcard = false;
+    for (index, part) in version.split('.').enumerate() {
+        if index >= 3 {
+            return None;
+        }
+        if matches!(part, "x" | "X" | "*") {
+            wildcard = true;
+            continue;
+        }
+        if wildcard {
+            return None;
+        }
+        parts[index] = part.parse().ok()?;
+        given += 1;
+    }
+    (given > 0).then_some((parts, given))
+}
+
 fn version_compare(a: &str, b: &str) -> std::cmp::Ordering {
     // Normalize versions before comparison to handle v prefixes consistently
     use std::cmp::Ordering;
@@ -195,3 +525,191 @@ fn version_compare(a: &str, b: &str) -> std::cmp::Ordering {
 
     a_parts.len().cmp(&b_parts.len())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_metadata_round_trips_and_records_use() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            VersionMetadata::load(tmp.path()),
+            VersionMetadata::default()
+        );
+
+        VersionMetadata::record_install(tmp.path());
+        let installed = VersionMetadata::load(tmp.path());
+        assert!(installed.installed_at.is_some());
+        assert!(installed.last_used.is_none());
+
+        VersionMetadata::record_use(tmp.path());
+        let used = VersionMetadata::load(tmp.path());
+        assert_eq!(used.installed_at, installed.installed_at);
+        assert!(used.last_used >= used.installed_at);
+        assert_eq!(
+            used.last_used_at(&tmp.path().join("missing-binary")),
+            used.last_used
+        );
+    }
+
+    #[test]
+    fn version_metadata_ignores_corrupt_file_and_missing_dir() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(VERSION_METADATA_FILE), "not json").unwrap();
+        assert_eq!(
+            VersionMetadata::load(tmp.path()),
+            VersionMetadata::default()
+        );
+
+        let missing = tmp.path().join("gone");
+        VersionMetadata::record_use(&missing);
+        assert!(!missing.exists());
+    }
+
+    #[test]
+    fn validate_version_accepts_release_shapes() {
+        for v in [
+            "0.30.109",
+            "v0.30.109",
+            "latest",
+            "1.0.0-beta.1",
+            "1.0.0+build.5",
+        ] {
+            assert!(validate_version(v).is_ok(), "{v} should be accepted");
+        }
+    }
+
+    #[test]
+    fn validate_version_rejects_path_traversal() {
+        for v in [
+            "",
+            ".",
+            "..",
+            "../../etc",
+            "..\\..\\Windows",
+            "1.0/../../x",
+            "/etc/passwd",
+            "C:\\Windows",
+            ".hidden",
+            "1.0 .0",
+            "1.0\0",
+        ] {
+            assert!(
+                matches!(validate_version(v), Err(CleenError::InvalidVersion { .. })),
+                "{v:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn nightly_spec_parses_tag_and_commit() {
+        assert_eq!(
+            NightlySpec::parse("nightly").unwrap(),
+            Some(NightlySpec::Latest)
+        );
+        assert_eq!(
+            NightlySpec::parse("sha:1A2B3C4D5E").unwrap(),
+            Some(NightlySpec::Commit("1a2b3c4d5e".to_string()))
+        );
+        assert_eq!(NightlySpec::parse("0.30.0").unwrap(), None);
+        assert_eq!(NightlySpec::parse("latest").unwrap(), None);
+        for bad in ["sha:", "sha:abc", "sha:../../etc", "sha:xyz1234"] {
+            assert!(
+                matches!(
+                    NightlySpec::parse(bad),
+                    Err(CleenError::InvalidVersion { .. })
+                ),
+                "{bad} should be rejected"
+            );
+        }
+
+        let name = nightly_version("1a2b3c4d5e6f");
+        assert_eq!(name, "nightly-1a2b3c4");
+        assert!(is_nightly(&name));
+        assert!(validate_version(&name).is_ok());
+        assert!(!is_nightly("0.30.0"));
+    }
+
+    #[test]
+    fn version_range_parses_common_shapes() {
+        let matching = |spec: &str, version: &str| {
+            VersionRange::parse(spec).unwrap().unwrap().matches(version)
+        };
+
+        assert!(matching("^0.14.0", "0.14.9"));
+        assert!(!matching("^0.14.0", "0.15.0"));
+        assert!(matching("^1.2", "1.9.0"));
+        assert!(!matching("^1.2", "2.0.0"));
+        assert!(matching("~0.14.1", "v0.14.3"));
+        assert!(!matching("~0.14.1", "0.14.0"));
+        assert!(matching(">=0.14.0 <0.15.0", "0.14.2"));
+        assert!(matching(">= 0.14.0, < 0.15.0", "0.14.2"));
+        assert!(!matching(">=0.14.0 <0.15.0", "0.15.0"));
+        assert!(matching("0.14.x", "0.14.7"));
+        assert!(!matching("0.14.x", "0.13.7"));
+        assert!(!matching("^0.14.0", "0.14.5-beta.1"));
+
+        assert_eq!(VersionRange::parse("0.14.2").unwrap(), None);
+        assert_eq!(VersionRange::parse("v0.14.2").unwrap(), None);
+        assert_eq!(VersionRange::parse("latest").unwrap(), None);
+        for spec in ["^", ">=", "^0.x.y", ">=0.14.0 <", "~1.2.3.4"] {
+            assert!(
+                matches!(
+                    VersionRange::parse(spec),
+                    Err(CleenError::InvalidVersion { .. })
+                ),
+                "{spec:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn list_installed_versions_skips_frame_and_hidden_dirs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            cleen_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let versions_dir = config.get_versions_dir();
+        for dir in ["0.30.0", "frame", ".0.30.0.staging-42", ".0.29.0.old-42"] {
+            std::fs::create_dir_all(versions_dir.join(dir)).unwrap();
+        }
+
+        let listed: Vec<String> = VersionManager::new(config)
+            .list_installed_versions()
+            .unwrap()
+            .into_iter()
+            .map(|v| v.version)
+            .collect();
+        assert_eq!(listed, ["0.30.0"]);
+    }
+
+    #[test]
+    fn resolve_version_spec_picks_highest_installed_match() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            cleen_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        for version in ["0.13.9", "0.14.1", "v0.14.10", "0.15.0"] {
+            let binary = config.get_version_binary(version);
+            std::fs::create_dir_all(binary.parent().unwrap()).unwrap();
+            std::fs::write(&binary, b"#!/bin/sh\n").unwrap();
+            fs::make_executable(&binary).unwrap();
+        }
+        let manager = VersionManager::new(config);
+
+        assert_eq!(manager.resolve_version_spec("^0.14.0").unwrap(), "0.14.10");
+        assert_eq!(manager.resolve_version_spec("~0.13").unwrap(), "0.13.9");
+        assert_eq!(manager.resolve_version_spec("0.12.0").unwrap(), "0.12.0");
+
+        match manager.resolve_version_spec(">=0.16.0 <0.17.0") {
+            Err(CleenError::NoVersionMatchesRange { range, considered }) => {
+                assert_eq!(range, ">=0.16.0 <0.17.0");
+                assert_eq!(considered, "0.13.9, 0.14.1, v0.14.10, 0.15.0");
+            }
+            other => panic!("expected NoVersionMatchesRange, got {other:?}"),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, CleenError>;
@@ -17,6 +17,12 @@ pub enum CleenError {
     #[error("Version '{version}' is already installed")]
     VersionAlreadyInstalled { version: String },
 
+    #[error("This project pins Clean Language {version}, which is not installed. Run 'cleen install {version}'")]
+    ProjectVersionNotInstalled { version: String },
+
+    #[error("No installed version matches '{range}' (considered: {considered})")]
+    NoVersionMatchesRange { range: String, considered: String },
+
     #[error("No version is currently active")]
     #[allow(dead_code)]
     NoActiveVersion,
@@ -28,15 +34,42 @@ pub enum CleenError {
     ConfigError { message: String },
 
     #[error("GitHub API error: {message}")]
-    #[allow(dead_code)]
     GitHubError { message: String },
 
+    #[error("GitHub API rate limit exceeded. It resets at {resets_at}")]
+    GitHubRateLimited { resets_at: String },
+
     #[error("Download failed: {url}")]
     DownloadError { url: String },
 
     #[error("Extraction failed: {path}")]
     ExtractionError { path: PathBuf },
 
+    #[error("Checksum mismatch: expected SHA-256 {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+
+    #[error("Unsafe archive entry '{entry}': it would be written outside the install directory")]
+    UnsafeArchive { entry: String },
+
+    #[error(
+        "Archive of {} expands past {}, refusing a possible decompression bomb",
+        crate::utils::fs::format_size(*archive_size),
+        crate::utils::fs::format_size(*limit)
+    )]
+    ArchiveTooLarge { archive_size: u64, limit: u64 },
+
+    #[error(
+        "Insufficient disk space at {}: {} required, {} available",
+        path.display(),
+        crate::utils::fs::format_size(*required),
+        crate::utils::fs::format_size(*available)
+    )]
+    InsufficientDiskSpace {
+        path: PathBuf,
+        required: u64,
+        available: u64,
+    },
+
     #[error("Home directory not found")]
     HomeDirectoryNotFound,
 
@@ -59,9 +92,21 @@ pub enum CleenError {
     #[error("Update error: {message}")]
     UpdateError { message: String },
 
+    #[error("Repair failed for: {versions}")]
+    RepairFailed { versions: String },
+
+    #[error("Nightly build '{version}' has no release to repair from. Reinstall it with 'cleen install sha:{commit} --force'")]
+    NightlyRepairUnsupported { version: String, commit: String },
+
+    #[error("Install failed for: {versions}")]
+    InstallFailed { versions: String },
+
     #[error("Frame CLI version '{frame_version}' not found")]
     FrameVersionNotFound { frame_version: String },
 
+    #[error("No Frame CLI version is active. Run 'cleen frame use <version>'")]
+    NoActiveFrameVersion,
+
     #[error("Frame CLI version '{frame_version}' is already installed")]
     #[allow(dead_code)]
     FrameVersionAlreadyInstalled { frame_version: String },
@@ -107,16 +152,31 @@ pub enum CleenError {
     #[error("Invalid template: '{template}'. Valid templates: api, web, minimal")]
     InvalidTemplate { template: String },
 
+    #[error("Invalid port {port}: the development server needs a port from 1 to 65535")]
+    InvalidPort { port: u16 },
+
+    #[error("Refusing to clean '{path}': it is neither a directory inside the project at '{project}' nor a previous build's output")]
+    UnsafeCleanPath { path: String, project: String },
+
     // Plugin errors
     #[error("Plugin '{name}' not found")]
     PluginNotFound { name: String },
 
+    #[error("Invalid plugin specifier '{specifier}': {reason}")]
+    InvalidPluginSpecifier { specifier: String, reason: String },
+
+    #[error("Invalid plugin name: '{name}'. Plugin names can only contain alphanumeric characters, dots, hyphens, and underscores")]
+    InvalidPluginName { name: String },
+
     #[error("Plugin '{name}' version '{version}' not found")]
     PluginVersionNotFound { name: String, version: String },
 
     #[error("Plugin '{name}' is already installed")]
     PluginAlreadyInstalled { name: String },
 
+    #[error("Failed to install plugin(s): {names}")]
+    PluginInstallFailed { names: String },
+
     #[error("Plugin manifest not found: {path}")]
     PluginManifestNotFound { path: PathBuf },
 
@@ -134,6 +194,13 @@ pub enum CleenError {
         current: String,
     },
 
+    #[error("Plugin '{name}' supports compiler <= {maximum}, but current is {current}")]
+    PluginCompilerTooNew {
+        name: String,
+        maximum: String,
+        current: String,
+    },
+
     #[error("Plugin registry error: {message}")]
     PluginRegistryError { message: String },
 
@@ -167,6 +234,20 @@ impl From<anyhow::Error> for CleenError {
 }
 
 impl CleenError {
+    /// Map a failed `Downloader::extract_archive` call. Unsafe entries and
+    /// oversized archives keep their own variants so a hostile archive is
+    /// not reported as a corrupt one.
+    pub fn extraction_failed(error: anyhow::Error, archive: &Path) -> Self {
+        match error.downcast::<CleenError>() {
+            Ok(err @ (CleenError::UnsafeArchive { .. } | CleenError::ArchiveTooLarge { .. })) => {
+                err
+            }
+            _ => CleenError::ExtractionError {
+                path: archive.to_path_buf(),
+            },
+        }
+    }
+
     #[allow(dead_code)]
     pub fn config_error<S: Into<String>>(message: S) -> Self {
         CleenError::ConfigError {
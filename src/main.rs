@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 // Use the library modules
 use cleen::{commands, core};
@@ -9,6 +10,10 @@ use cleen::{commands, core};
 #[clap(about = "Clean Language version manager")]
 #[clap(version = env!("CARGO_PKG_VERSION"))]
 pub struct Cli {
+    /// Print how long each phase (fetch, download, extraction, build...) took
+    #[clap(long, global = true)]
+    timings: bool,
+
     #[clap(subcommand)]
     command: Commands,
 }
@@ -17,22 +22,47 @@ pub struct Cli {
 enum Commands {
     /// Install a specific version of Clean Language
     Install {
-        /// Version to install (e.g., 1.2.3, latest)
-        version: String,
+        /// Version(s) to install (e.g., 1.2.3, latest, nightly, sha:<commit>)
+        #[clap(required = true)]
+        versions: Vec<String>,
+        /// How many versions to download at once when installing several
+        #[clap(short, long, default_value = "2")]
+        jobs: usize,
         /// Also install Frame CLI
         #[clap(long)]
         with_frame: bool,
         /// Skip Frame CLI prompt
         #[clap(long)]
         no_frame: bool,
+        /// Reinstall even if the version is already installed
+        #[clap(long)]
+        force: bool,
+        /// Keep the downloaded archive in ~/.cleen/cache/downloads for offline reinstalls
+        #[clap(long)]
+        keep_archive: bool,
+        /// Show which asset would be downloaded, and its size, without installing
+        #[clap(long)]
+        dry_run: bool,
     },
     /// Install the version specified in .cleanlanguage/.cleanversion file
     Sync,
+    /// Print the path of the binary 'cln' would run in this directory
+    Which {
+        /// Print the active Frame CLI binary instead
+        #[clap(long)]
+        frame: bool,
+    },
     /// List installed versions
     List {
         /// List Frame CLI versions
         #[clap(long)]
         frame: bool,
+        /// Show when each version was installed and last used
+        #[clap(long, short)]
+        verbose: bool,
+        /// Print a JSON array instead of the human-readable list
+        #[clap(long, conflicts_with = "verbose")]
+        json: bool,
     },
     /// List available versions from GitHub
     Available,
@@ -52,13 +82,29 @@ enum Commands {
     /// Uninstall a specific version
     Uninstall {
         /// Version to uninstall
-        version: String,
+        #[clap(required_unless_present = "all", conflicts_with = "all")]
+        version: Option<String>,
         /// Uninstall Frame CLI version instead
-        #[clap(long)]
+        #[clap(long, conflicts_with = "all")]
         frame: bool,
-        /// Force uninstall even if Frame depends on it
+        /// Force uninstall even if Frame depends on it (with --all, also remove the active version)
         #[clap(long)]
         force: bool,
+        /// Uninstall every installed compiler version
+        #[clap(long)]
+        all: bool,
+        /// Skip the confirmation prompt for --all
+        #[clap(long, short, requires = "all")]
+        yes: bool,
+    },
+    /// Re-download and reinstall a broken version in place
+    Repair {
+        /// Version to repair
+        #[clap(required_unless_present = "all", conflicts_with = "all")]
+        version: Option<String>,
+        /// Repair every version reported as invalid by `cleen list`
+        #[clap(long)]
+        all: bool,
     },
     /// Initialize shell configuration
     Init,
@@ -67,6 +113,9 @@ enum Commands {
         /// Check Frame CLI installation
         #[clap(long)]
         frame: bool,
+        /// Repair missing directories, shims and PATH configuration
+        #[clap(long)]
+        fix: bool,
     },
     /// Check for Clean Language compiler updates
     Update,
@@ -128,10 +177,11 @@ enum FrameCommands {
         #[clap(short, long, default_value = "api")]
         template: String,
         /// Port for development server (default: 3000)
-        #[clap(short, long, default_value = "3000")]
+        #[clap(short, long, default_value = "3000", value_parser = core::frame::parse_project_port)]
         port: u16,
     },
     /// Scan and discover project files (dry-run for build)
+    #[clap(visible_alias = "info")]
     Scan {
         /// Project directory to scan (default: current directory)
         #[clap(default_value = ".")]
@@ -139,6 +189,9 @@ enum FrameCommands {
         /// Output format: text or json
         #[clap(short, long, default_value = "text")]
         format: String,
+        /// Shorthand for --format json
+        #[clap(long, conflicts_with = "format")]
+        json: bool,
         /// Show verbose output including file paths
         #[clap(short, long)]
         verbose: bool,
@@ -152,8 +205,23 @@ enum FrameCommands {
         #[clap(short, long, default_value = "dist")]
         output: String,
         /// Optimization level: 0, 1, 2, 3, s, z (default: 2)
-        #[clap(short = 'O', long, default_value = "2")]
+        #[clap(short = 'O', long, default_value = "2", value_parser = core::frame::parse_opt_level)]
         optimize: String,
+        /// Build once per level (repeatable), keeping each as <name>.opt<level>.wasm
+        #[clap(long = "opt-level", conflicts_with = "optimize", value_parser = core::frame::parse_opt_level)]
+        opt_levels: Vec<String>,
+        /// Installed compiler version to use instead of the project's pin
+        #[clap(long)]
+        compiler: Option<String>,
+        /// Fail the build if frame-cli reports any warnings
+        #[clap(long)]
+        strict: bool,
+        /// Add the output directory to .gitignore if it isn't ignored yet
+        #[clap(long)]
+        update_gitignore: bool,
+        /// Delete the output directory before building
+        #[clap(long)]
+        clean: bool,
     },
     /// Start a development server for a Frame application
     Serve {
@@ -169,9 +237,34 @@ enum FrameCommands {
         /// Enable debug output
         #[clap(short, long)]
         debug: bool,
+        /// Installed compiler version to use instead of the project's pin
+        #[clap(long)]
+        compiler: Option<String>,
+        /// Keep the compiled WASM at this path (default: a temp file)
+        #[clap(long)]
+        out: Option<PathBuf>,
+        /// Recompile and restart the server when source files change
+        #[clap(long)]
+        watch: bool,
+        /// Run the server in the background, logging to ~/.cleen/logs/frame-server.log
+        #[clap(long, conflicts_with = "watch")]
+        detach: bool,
+        /// Ask the runtime to record request timings (FRAME_PROFILE=1) and
+        /// summarize them when the server stops
+        #[clap(long, conflicts_with = "detach")]
+        profile: bool,
     },
     /// Stop a running Frame development server
     Stop,
+    /// Show the log of a server started with `serve --detach`
+    Logs {
+        /// Number of lines to show from the end of the log
+        #[clap(short = 'n', long, default_value = "50")]
+        lines: usize,
+        /// Keep printing new output as the server writes it
+        #[clap(short, long)]
+        follow: bool,
+    },
     /// Install Frame runtime
     Install {
         /// Version to install (optional, auto-detects compatible version)
@@ -193,16 +286,22 @@ enum FrameCommands {
 
 #[derive(Subcommand)]
 enum PluginCommands {
-    /// Install a plugin from the registry
+    /// Install one or more plugins from the registry
     Install {
-        /// Plugin name or name@version (e.g., frame.web or frame.web@1.0.0)
-        plugin: String,
-        /// Install from a local directory instead of registry
+        /// Plugin names or name@version (e.g., frame.web frame.ui@1.0.0)
+        #[clap(required = true)]
+        plugins: Vec<String>,
+        /// Install from local directories instead of registry
         #[clap(long)]
         local: bool,
     },
     /// List installed plugins
     List,
+    /// Search the plugin registry by name or description
+    Search {
+        /// Text to look for in plugin names and descriptions
+        query: String,
+    },
     /// Create a new plugin project
     Create {
         /// Name of the plugin to create
@@ -223,6 +322,17 @@ enum PluginCommands {
         name: String,
         /// Version to use
         version: String,
+        /// Refuse to activate a version the active compiler can't run
+        #[clap(long)]
+        strict: bool,
+        /// Record the version for this project in .cleanlanguage/plugins.toml
+        /// (advisory: builds use the global version until the compiler
+        /// reads plugins.toml)
+        #[clap(long, conflicts_with = "global")]
+        local: bool,
+        /// Set the version for every project without a local pin (default)
+        #[clap(long)]
+        global: bool,
     },
 }
 
@@ -261,25 +371,58 @@ enum ServerCommands {
 }
 
 fn main() -> Result<()> {
+    // `cln` is a shim pointing back at this binary; hand off to the
+    // resolved compiler before clap sees the compiler's arguments.
+    if let Some(code) = core::shim::dispatch_if_shim() {
+        std::process::exit(code);
+    }
+
     let cli = Cli::parse();
 
     // Best-effort weekly heartbeat so the errors dashboard can advance bugs
     // from fix_released → fix_installed for long-running projects that
     // haven't run `cleen install` recently. Silent on failure; gated by
-    // CLEEN_HEARTBEAT env var. See core::heartbeat.
-    core::heartbeat::maybe_send_weekly();
+    // CLEEN_HEARTBEAT env var. See core::heartbeat. Skipped for output
+    // meant for scripts, which must stay pipeable.
+    if !matches!(
+        cli.command,
+        Commands::List { json: true, .. } | Commands::Which { .. }
+    ) {
+        core::heartbeat::maybe_send_weekly();
+    }
+
+    if cli.timings {
+        cleen::utils::timing::enable();
+    }
 
     let result = match cli.command {
         Commands::Install {
-            version,
+            versions,
+            jobs,
             with_frame,
             no_frame,
-        } => commands::install::install_version(&version, with_frame, no_frame)
-            .map_err(|e| anyhow::anyhow!(e)),
+            force,
+            keep_archive,
+            dry_run,
+        } => commands::install::install_versions(
+            &versions,
+            jobs,
+            with_frame,
+            no_frame,
+            force,
+            keep_archive,
+            dry_run,
+        )
+        .map_err(|e| anyhow::anyhow!(e)),
         Commands::Sync => commands::sync::sync_project_version().map_err(|e| anyhow::anyhow!(e)),
-        Commands::List { frame } => {
-            commands::list::list_versions(frame).map_err(|e| anyhow::anyhow!(e))
+        Commands::Which { frame } => {
+            commands::which::which_binary(frame).map_err(|e| anyhow::anyhow!(e))
         }
+        Commands::List {
+            frame,
+            verbose,
+            json,
+        } => commands::list::list_versions(frame, verbose, json).map_err(|e| anyhow::anyhow!(e)),
         Commands::Available => commands::available::list_available_versions(),
         Commands::Use { version, frame } => {
             commands::use_version::use_version(&version, frame).map_err(|e| anyhow::anyhow!(e))
@@ -291,11 +434,23 @@ fn main() -> Result<()> {
             version,
             frame,
             force,
-        } => commands::uninstall::uninstall_version(&version, frame, force)
-            .map_err(|e| anyhow::anyhow!(e)),
+            all,
+            yes,
+        } => match version {
+            Some(version) if !all => commands::uninstall::uninstall_version(&version, frame, force)
+                .map_err(|e| anyhow::anyhow!(e)),
+            _ => commands::uninstall::uninstall_all_versions(force, yes)
+                .map_err(|e| anyhow::anyhow!(e)),
+        },
+        Commands::Repair { version, all } => match version {
+            Some(version) if !all => {
+                commands::repair::repair_version(&version).map_err(|e| anyhow::anyhow!(e))
+            }
+            _ => commands::repair::repair_all().map_err(|e| anyhow::anyhow!(e)),
+        },
         Commands::Init => commands::init::init_shell().map_err(|e| anyhow::anyhow!(e)),
-        Commands::Doctor { frame } => {
-            commands::doctor::check_environment(frame).map_err(|e| anyhow::anyhow!(e))
+        Commands::Doctor { frame, fix } => {
+            commands::doctor::check_environment(frame, fix).map_err(|e| anyhow::anyhow!(e))
         }
         Commands::Update => commands::update::check_for_updates().map_err(|e| anyhow::anyhow!(e)),
         Commands::SelfUpdate => {
@@ -336,23 +491,72 @@ fn main() -> Result<()> {
             FrameCommands::Scan {
                 project,
                 format,
+                json,
                 verbose,
-            } => core::frame::scan_project(&project, &format, verbose)
+            } => core::frame::scan_project(&project, if json { "json" } else { &format }, verbose)
                 .map_err(|e| anyhow::anyhow!(e)),
             FrameCommands::Build {
                 input,
                 output,
                 optimize,
-            } => core::frame::build_project(&input, &output, &optimize)
-                .map_err(|e| anyhow::anyhow!(e)),
+                opt_levels,
+                compiler,
+                strict,
+                update_gitignore,
+                clean,
+            } => {
+                let cleaned = if clean {
+                    core::frame::clean_output(&input, &output)
+                } else {
+                    Ok(())
+                };
+                cleaned
+                    .and_then(|()| {
+                        let compiler = compiler.as_deref();
+                        if opt_levels.is_empty() {
+                            core::frame::build_project(&input, &output, &optimize, compiler)
+                                .and_then(|report| core::frame::finish_build(&report, strict))
+                        } else {
+                            core::frame::build_project_levels(
+                                &input,
+                                &output,
+                                &opt_levels,
+                                compiler,
+                            )
+                            .and_then(|builds| core::frame::finish_level_builds(&builds, strict))
+                        }
+                    })
+                    .and_then(|()| {
+                        core::frame::check_output_ignored(&input, &output, update_gitignore)
+                    })
+                    .map_err(|e| anyhow::anyhow!(e))
+            }
             FrameCommands::Serve {
                 input,
                 port,
                 host,
                 debug,
-            } => core::frame::serve_application(&input, port, &host, debug)
-                .map_err(|e| anyhow::anyhow!(e)),
+                compiler,
+                out,
+                watch,
+                detach,
+                profile,
+            } => core::frame::serve_application(
+                &input,
+                port,
+                &host,
+                debug,
+                compiler.as_deref(),
+                out.as_deref(),
+                watch,
+                detach,
+                profile,
+            )
+            .map_err(|e| anyhow::anyhow!(e)),
             FrameCommands::Stop => core::frame::stop_server().map_err(|e| anyhow::anyhow!(e)),
+            FrameCommands::Logs { lines, follow } => {
+                core::frame::show_server_logs(lines, follow).map_err(|e| anyhow::anyhow!(e))
+            }
             FrameCommands::Install { version } => {
                 core::frame::install_frame(version.as_deref(), false)
                     .map_err(|e| anyhow::anyhow!(e))
@@ -388,17 +592,15 @@ fn main() -> Result<()> {
             }
         },
         Commands::Plugin { command } => match command {
-            PluginCommands::Install { plugin, local } => {
-                if local {
-                    let path = std::path::Path::new(&plugin);
-                    commands::plugin::install_local_plugin(path).map_err(|e| anyhow::anyhow!(e))
-                } else {
-                    commands::plugin::install_plugin(&plugin).map_err(|e| anyhow::anyhow!(e))
-                }
+            PluginCommands::Install { plugins, local } => {
+                commands::plugin::install_plugins(&plugins, local).map_err(|e| anyhow::anyhow!(e))
             }
             PluginCommands::List => {
                 commands::plugin::list_plugins().map_err(|e| anyhow::anyhow!(e))
             }
+            PluginCommands::Search { query } => {
+                commands::plugin::search_plugins(&query).map_err(|e| anyhow::anyhow!(e))
+            }
             PluginCommands::Create { name } => {
                 commands::plugin::create_plugin(&name).map_err(|e| anyhow::anyhow!(e))
             }
@@ -411,10 +613,14 @@ fn main() -> Result<()> {
             PluginCommands::Remove { name } => {
                 commands::plugin::remove_plugin_command(&name).map_err(|e| anyhow::anyhow!(e))
             }
-            PluginCommands::Use { name, version } => {
-                commands::plugin::use_plugin_version(&name, &version)
-                    .map_err(|e| anyhow::anyhow!(e))
-            }
+            PluginCommands::Use {
+                name,
+                version,
+                strict,
+                local,
+                global: _,
+            } => commands::plugin::use_plugin_version(&name, &version, strict, local)
+                .map_err(|e| anyhow::anyhow!(e)),
         },
         Commands::Test {
             file,
@@ -443,6 +649,10 @@ fn main() -> Result<()> {
         },
     };
 
+    if let Some(timings) = cleen::utils::timing::take() {
+        eprint!("{}", timings.report());
+    }
+
     if let Err(e) = result {
         eprintln!("Error: {e}");
         std::process::exit(1);
@@ -138,17 +138,9 @@ impl PluginManifest {
             });
         }
 
-        // Validate name format (alphanumeric, dots, hyphens)
-        if !self
-            .plugin
-            .name
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
-        {
-            return Err(CleenError::PluginManifestError {
-                message: "Plugin name can only contain alphanumeric characters, dots, hyphens, and underscores".to_string(),
-            });
-        }
+        // The name becomes a directory under ~/.cleen/plugins on install
+        super::validate_plugin_name(&self.plugin.name)?;
+        crate::core::version::validate_version(&self.plugin.version)?;
 
         Ok(())
     }
@@ -7,6 +7,7 @@ use crate::error::{CleenError, Result};
 use crate::utils::fs as fs_utils;
 use manifest::PluginManifest;
 use std::fs;
+use std::path::Path;
 
 /// Represents an installed plugin with its metadata
 #[derive(Debug, Clone)]
@@ -101,6 +102,23 @@ pub fn get_plugin_versions(config: &Config, name: &str) -> Result<Vec<String>> {
     Ok(versions)
 }
 
+/// Resolve the plugin version in effect for `cwd`. A project pin from
+/// `.cleanlanguage/plugins.toml` wins over the global `.active-version`
+/// marker; a project pin naming a version that isn't installed is skipped,
+/// like a ghost marker in [`read_active_version`](crate::core::config::read_active_version).
+///
+/// Only `cleen plugin list` uses this so far. The compiler, heartbeat and
+/// Frame re-pinning still read `.active-version`, so a project pin is
+/// advisory until the compiler reads `plugins.toml`.
+pub fn resolve_plugin_version(config: &Config, name: &str, cwd: Option<&Path>) -> Option<String> {
+    if let Some(version) = cwd.and_then(|dir| config.find_project_plugin_version(dir, name)) {
+        if config.get_plugin_wasm_path(name, &version).exists() {
+            return Some(version);
+        }
+    }
+    crate::core::config::read_active_version(config, name)
+}
+
 /// Check if a plugin version is installed
 pub fn is_plugin_installed(config: &Config, name: &str, version: &str) -> bool {
     let manifest_path = config.get_plugin_manifest_path(name, version);
@@ -279,21 +297,90 @@ fn clean_plugin_root_files(config: &Config, name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Parse a plugin specifier (e.g., "frame.web" or "frame.web@1.0.0")
-pub fn parse_plugin_specifier(specifier: &str) -> (String, Option<String>) {
-    if let Some(at_pos) = specifier.rfind('@') {
-        let name = specifier[..at_pos].to_string();
-        let version = specifier[at_pos + 1..].to_string();
-        (name, Some(version))
+/// Parse a plugin specifier (e.g., "frame.web" or "frame.web@1.0.0").
+///
+/// Both halves end up as directory names under `~/.cleen/plugins`, so the
+/// name and version are validated here rather than at each call site.
+/// Surrounding whitespace is ignored; everything after the first `@` is
+/// the version, so `name@1.0@2.0` and `name@` are rejected with a clear
+/// reason instead of producing a nonsense or empty version.
+pub fn parse_plugin_specifier(specifier: &str) -> Result<(String, Option<String>)> {
+    let trimmed = specifier.trim();
+    let invalid = |reason: &str| CleenError::InvalidPluginSpecifier {
+        specifier: specifier.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let (name, version) = match trimmed.split_once('@') {
+        Some((name, version)) => (name.trim(), Some(version.trim())),
+        None => (trimmed, None),
+    };
+
+    if name.is_empty() {
+        if trimmed.contains('/') {
+            return Err(invalid(
+                "scoped names like @scope/pkg aren't supported; use a dotted name such as scope.pkg",
+            ));
+        }
+        return Err(invalid("missing plugin name before '@'"));
+    }
+    validate_plugin_name(name)?;
+
+    let version = match version {
+        None => None,
+        Some("") => return Err(invalid("missing version after '@'")),
+        Some(v) if v.contains('@') => {
+            return Err(invalid("expected a single '@' between name and version"))
+        }
+        Some(v) if !looks_like_version(v) => {
+            return Err(invalid("version must look like 1.2.3, v1.2.3, or latest"))
+        }
+        Some(v) => {
+            crate::core::version::validate_version(v)?;
+            Some(v.to_string())
+        }
+    };
+
+    Ok((name.to_string(), version))
+}
+
+/// A version is `latest` or starts with a digit, optionally after `v`.
+fn looks_like_version(version: &str) -> bool {
+    version == "latest"
+        || version
+            .strip_prefix('v')
+            .unwrap_or(version)
+            .starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Reject plugin names that are unsafe to use as a path component.
+///
+/// Names are restricted to alphanumerics, dots, hyphens, and underscores.
+/// Dots are allowed for namespacing (`frame.web`), so `..` and a leading
+/// dot are rejected explicitly.
+pub fn validate_plugin_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains("..")
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_');
+
+    if valid {
+        Ok(())
     } else {
-        (specifier.to_string(), None)
+        Err(CleenError::InvalidPluginName {
+            name: name.to_string(),
+        })
     }
 }
 
 /// Check if the current compiler version is compatible with a plugin.
 ///
 /// Returns `Err(PluginIncompatible)` when the plugin's manifest declares a
-/// `min_compiler_version` that the active compiler does not satisfy. Called
+/// `min_compiler_version` that the active compiler does not satisfy, and
+/// `Err(PluginCompilerTooNew)` when the active compiler is newer than its
+/// `max_compiler_version`. Called
 /// during frame meta-bundle install to surface registry-drift conditions
 /// before the user discovers them at `cln build` time.
 pub fn check_plugin_compatibility(config: &Config, manifest: &PluginManifest) -> Result<()> {
@@ -313,6 +400,16 @@ pub fn check_plugin_compatibility(config: &Config, manifest: &PluginManifest) ->
         }
     }
 
+    if let Some(max_version) = &manifest.compatibility.max_compiler_version {
+        if !version_at_most(&current_version, max_version) {
+            return Err(CleenError::PluginCompilerTooNew {
+                name: manifest.plugin.name.clone(),
+                maximum: max_version.clone(),
+                current: current_version,
+            });
+        }
+    }
+
     Ok(())
 }
 
@@ -341,23 +438,161 @@ fn version_satisfies(current: &str, required: &str) -> bool {
     true // Equal versions
 }
 
+/// Simple version comparison (current <= maximum)
+fn version_at_most(current: &str, maximum: &str) -> bool {
+    version_satisfies(maximum, current)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(cleen_dir: &Path) -> Config {
+        Config {
+            cleen_dir: cleen_dir.to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    fn install_fake_plugin(config: &Config, name: &str, version: &str) {
+        let wasm = config.get_plugin_wasm_path(name, version);
+        fs::create_dir_all(wasm.parent().unwrap()).unwrap();
+        fs::write(wasm, b"\0asm").unwrap();
+    }
+
+    #[test]
+    fn project_plugin_pin_wins_over_global_in_nested_dirs() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path());
+        install_fake_plugin(&config, "frame.web", "1.0.0");
+        install_fake_plugin(&config, "frame.web", "2.0.0");
+        install_fake_plugin(&config, "frame.data", "1.0.0");
+        activate_plugin_version_root(&config, "frame.web", "1.0.0").unwrap();
+
+        let project = TempDir::new().unwrap();
+        config
+            .set_project_plugin_version(project.path(), "frame.web", "2.0.0")
+            .unwrap();
+        let nested = project.path().join("app").join("pages");
+        fs::create_dir_all(&nested).unwrap();
+
+        let resolve = |dir: &Path, name: &str| resolve_plugin_version(&config, name, Some(dir));
+        assert_eq!(resolve(&nested, "frame.web").as_deref(), Some("2.0.0"));
+        assert_eq!(
+            resolve(project.path(), "frame.web").as_deref(),
+            Some("2.0.0")
+        );
+
+        // Outside the project, and for plugins the project doesn't pin,
+        // the global marker still applies.
+        let elsewhere = TempDir::new().unwrap();
+        assert_eq!(
+            resolve(elsewhere.path(), "frame.web").as_deref(),
+            Some("1.0.0")
+        );
+        assert_eq!(resolve(&nested, "frame.data"), None);
+
+        // A nested project pin shadows the outer one for that subtree only.
+        let inner = project.path().join("app");
+        config
+            .set_project_plugin_version(&inner, "frame.web", "1.0.0")
+            .unwrap();
+        assert_eq!(resolve(&nested, "frame.web").as_deref(), Some("1.0.0"));
+        assert_eq!(
+            resolve(project.path(), "frame.web").as_deref(),
+            Some("2.0.0")
+        );
+    }
+
+    #[test]
+    fn project_plugin_pin_to_missing_version_falls_back_to_global() {
+        let home = TempDir::new().unwrap();
+        let config = test_config(home.path());
+        install_fake_plugin(&config, "frame.web", "1.0.0");
+        activate_plugin_version_root(&config, "frame.web", "1.0.0").unwrap();
+
+        let project = TempDir::new().unwrap();
+        let file = config
+            .set_project_plugin_version(project.path(), "frame.web", "9.9.9")
+            .unwrap();
+        config
+            .set_project_plugin_version(project.path(), "frame.data", "1.0.0")
+            .unwrap();
+        let content = fs::read_to_string(file).unwrap();
+        assert!(content.contains("\"frame.web\" = \"9.9.9\""), "{content}");
+        assert!(content.contains("\"frame.data\" = \"1.0.0\""), "{content}");
+
+        assert_eq!(
+            resolve_plugin_version(&config, "frame.web", Some(project.path())).as_deref(),
+            Some("1.0.0")
+        );
+    }
 
     #[test]
     fn test_parse_plugin_specifier() {
-        let (name, version) = parse_plugin_specifier("frame.web");
+        let (name, version) = parse_plugin_specifier("frame.web").unwrap();
         assert_eq!(name, "frame.web");
         assert_eq!(version, None);
 
-        let (name, version) = parse_plugin_specifier("frame.web@1.0.0");
+        let (name, version) = parse_plugin_specifier("frame.web@1.0.0").unwrap();
         assert_eq!(name, "frame.web");
         assert_eq!(version, Some("1.0.0".to_string()));
 
-        let (name, version) = parse_plugin_specifier("my-plugin@2.1.0-beta");
+        let (name, version) = parse_plugin_specifier("my-plugin@2.1.0-beta").unwrap();
         assert_eq!(name, "my-plugin");
         assert_eq!(version, Some("2.1.0-beta".to_string()));
+
+        let (name, version) = parse_plugin_specifier("  frame.web @ v1.0.0 ").unwrap();
+        assert_eq!(name, "frame.web");
+        assert_eq!(version, Some("v1.0.0".to_string()));
+
+        let (_, version) = parse_plugin_specifier("frame.web@latest").unwrap();
+        assert_eq!(version, Some("latest".to_string()));
+
+        for spec in [
+            "frame.web@",
+            "frame.web@  ",
+            "frame.web@1.0@2.0",
+            "frame.web@@1.0",
+        ] {
+            assert!(
+                matches!(
+                    parse_plugin_specifier(spec),
+                    Err(CleenError::InvalidPluginSpecifier { .. })
+                ),
+                "{spec:?} should be rejected as a malformed specifier"
+            );
+        }
+        assert!(parse_plugin_specifier("frame.web@beta").is_err());
+
+        match parse_plugin_specifier("@scope/pkg@1.0.0") {
+            Err(CleenError::InvalidPluginSpecifier { reason, .. }) => {
+                assert!(reason.contains("scoped"), "{reason}")
+            }
+            other => panic!("expected InvalidPluginSpecifier, got {other:?}"),
+        }
+        assert!(parse_plugin_specifier("   ").is_err());
+    }
+
+    #[test]
+    fn test_parse_plugin_specifier_rejects_traversal() {
+        for spec in [
+            "../../evil",
+            "..",
+            ".hidden",
+            "frame/web",
+            "frame\\web",
+            "",
+            "@1.0.0",
+            "frame.web@../../evil",
+            "frame.web@1.0/../x",
+        ] {
+            assert!(
+                parse_plugin_specifier(spec).is_err(),
+                "{spec:?} should be rejected"
+            );
+        }
     }
 
     #[test]
@@ -369,4 +604,51 @@ mod tests {
         assert!(version_satisfies("v1.0.0", "1.0.0"));
         assert!(version_satisfies("1.0.0", "v1.0.0"));
     }
+
+    #[test]
+    fn test_version_at_most() {
+        assert!(version_at_most("0.14.0", "0.14.0"));
+        assert!(version_at_most("0.13.9", "0.14.0"));
+        assert!(version_at_most("v0.14", "0.14.0"));
+        assert!(!version_at_most("0.14.1", "0.14.0"));
+        assert!(!version_at_most("0.16.0", "0.14"));
+        assert!(!version_at_most("1.0.0", "v0.14.0"));
+    }
+
+    #[test]
+    fn compatibility_enforces_max_compiler_version() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(tmp.path());
+        let manifest = PluginManifest::parse(
+            "[plugin]\nname = \"frame.web\"\nversion = \"1.0.0\"\n\n\
+             [compatibility]\nmin_compiler_version = \"0.10.0\"\n\
+             max_compiler_version = \"0.14.0\"\n",
+        )
+        .unwrap();
+
+        for ok in ["0.10.0", "0.14.0"] {
+            config.active_version = Some(ok.to_string());
+            assert!(
+                check_plugin_compatibility(&config, &manifest).is_ok(),
+                "{ok}"
+            );
+        }
+
+        config.active_version = Some("0.16.0".to_string());
+        match check_plugin_compatibility(&config, &manifest) {
+            Err(CleenError::PluginCompilerTooNew {
+                maximum, current, ..
+            }) => {
+                assert_eq!(maximum, "0.14.0");
+                assert_eq!(current, "0.16.0");
+            }
+            other => panic!("expected PluginCompilerTooNew, got {other:?}"),
+        }
+
+        config.active_version = Some("0.9.0".to_string());
+        assert!(matches!(
+            check_plugin_compatibility(&config, &manifest),
+            Err(CleenError::PluginIncompatible { .. })
+        ));
+    }
 }
@@ -3,20 +3,25 @@ use crate::error::{CleenError, Result};
 use crate::plugin::activate_plugin_version_root;
 use crate::plugin::manifest::PluginManifest;
 use crate::utils::fs as fs_utils;
+use serde::Deserialize;
 use std::fs;
 use std::path::Path;
+use std::process::Command;
 
 /// Plugin registry base URL (placeholder for future implementation)
 const REGISTRY_URL: &str = "https://plugins.cleanlang.org";
 
-/// Plugin information from the registry
-#[derive(Debug, Clone)]
+/// Plugin information from the registry. `index.json` at the registry
+/// root is an array of these records.
+#[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub struct PluginInfo {
     pub name: String,
     pub version: String,
+    #[serde(default)]
     pub description: Option<String>,
     pub download_url: String,
+    #[serde(default)]
     pub checksum: Option<String>,
 }
 
@@ -51,25 +56,47 @@ impl RegistryClient {
         })
     }
 
-    /// List all available plugins from the registry
-    #[allow(dead_code)]
+    /// List all available plugins from the registry's `index.json`
     pub fn list_available(&self) -> Result<Vec<PluginInfo>> {
-        // Placeholder for future implementation
-        Err(CleenError::PluginRegistryError {
-            message: format!("Plugin registry not yet available at {}", self.base_url),
+        let url = format!("{}/index.json", self.base_url.trim_end_matches('/'));
+        let output = Command::new("curl")
+            .args(["-fsSL", "-H"])
+            .arg(concat!("User-Agent: cleen/", env!("CARGO_PKG_VERSION")))
+            .arg(&url)
+            .output();
+
+        // Any transport failure means there's no registry to talk to yet.
+        let body = match output {
+            Ok(output) if output.status.success() => output.stdout,
+            _ => {
+                return Err(CleenError::PluginRegistryError {
+                    message: format!("Plugin registry not yet available at {}", self.base_url),
+                })
+            }
+        };
+
+        serde_json::from_slice(&body).map_err(|e| CleenError::PluginRegistryError {
+            message: format!("Invalid plugin index at {url}: {e}"),
         })
     }
 
-    /// Search for plugins by name or description
-    #[allow(dead_code)]
+    /// Search for plugins whose name or description contains `query`,
+    /// ignoring case. Results are sorted by name.
     pub fn search(&self, query: &str) -> Result<Vec<PluginInfo>> {
-        // Placeholder for future implementation
-        Err(CleenError::PluginRegistryError {
-            message: format!(
-                "Plugin registry search not yet available. Query: '{}'",
-                query
-            ),
-        })
+        let query = query.to_lowercase();
+        let mut matches: Vec<PluginInfo> = self
+            .list_available()?
+            .into_iter()
+            .filter(|info| {
+                info.name.to_lowercase().contains(&query)
+                    || info
+                        .description
+                        .as_deref()
+                        .is_some_and(|d| d.to_lowercase().contains(&query))
+            })
+            .collect();
+        matches.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(matches)
     }
 
     /// Publish a plugin to the registry
@@ -213,4 +240,53 @@ mod tests {
         let result = client.get_plugin_info("test-plugin", None);
         assert!(result.is_err());
     }
+
+    fn file_registry(dir: &Path) -> RegistryClient {
+        RegistryClient {
+            base_url: format!("file://{}", dir.display()),
+        }
+    }
+
+    #[test]
+    fn search_matches_name_or_description_from_index() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("index.json"),
+            r#"[
+                {"name": "frame.web", "version": "1.2.0", "description": "HTTP routing",
+                 "download_url": "https://example.com/frame.web-1.2.0.tar.gz"},
+                {"name": "frame.ui", "version": "0.4.0", "description": "Web components",
+                 "download_url": "https://example.com/frame.ui-0.4.0.tar.gz"},
+                {"name": "frame.data", "version": "2.0.0",
+                 "download_url": "https://example.com/frame.data-2.0.0.tar.gz"}
+            ]"#,
+        )
+        .unwrap();
+        let client = file_registry(tmp.path());
+
+        let names = |query| -> Vec<String> {
+            client
+                .search(query)
+                .unwrap()
+                .into_iter()
+                .map(|info| info.name)
+                .collect()
+        };
+        assert_eq!(names("WEB"), vec!["frame.ui", "frame.web"]);
+        assert_eq!(names("data"), vec!["frame.data"]);
+        assert!(names("graphql").is_empty());
+    }
+
+    #[test]
+    fn search_reports_unavailable_registry() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let client = file_registry(&tmp.path().join("missing"));
+
+        match client.search("web") {
+            Err(CleenError::PluginRegistryError { message }) => {
+                assert!(message.contains("not yet available"), "{message}")
+            }
+            other => panic!("expected PluginRegistryError, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,187 @@
+//! Parse `file:line[:col]: message` lines from compiler output so `frame
+//! build`/`serve` can group failures by file and show the offending source
+//! line instead of a raw stderr dump.
+
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// One located message from the compiler's output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilerMessage {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl CompilerMessage {
+    /// Parse `path:line:col: message` or `path:line: message`, optionally
+    /// prefixed with `--> `. Returns `None` for lines without a location.
+    pub fn parse(line: &str) -> Option<Self> {
+        let trimmed = line.trim();
+        let trimmed = trimmed.strip_prefix("--> ").unwrap_or(trimmed);
+
+        // Try each colon as the end of the path, so drive letters and
+        // colons inside the message don't confuse the split.
+        for (index, _) in trimmed.match_indices(':') {
+            let file = &trimmed[..index];
+            if file.is_empty() || file.contains(": ") {
+                continue;
+            }
+            let Some((line, rest)) = leading_number(&trimmed[index + 1..]) else {
+                continue;
+            };
+            let (column, rest) = match leading_number(rest) {
+                Some((column, rest)) => (Some(column), rest),
+                None => (None, rest),
+            };
+            return Some(Self {
+                file: PathBuf::from(file),
+                line,
+                column,
+                message: rest.trim().to_string(),
+            });
+        }
+        None
+    }
+}
+
+/// Split `123:rest` into `(123, "rest")`.
+fn leading_number(text: &str) -> Option<(usize, &str)> {
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    let rest = text[digits..].strip_prefix(':')?;
+    Some((text[..digits].parse().ok()?, rest))
+}
+
+/// Render compiler output with located messages grouped by file, each
+/// followed by the source line it points at. Output without any located
+/// messages is returned unchanged.
+pub fn render(output: &str) -> String {
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    render_with(output, color)
+}
+
+fn render_with(output: &str, color: bool) -> String {
+    let mut groups: Vec<(PathBuf, Vec<CompilerMessage>)> = Vec::new();
+    let mut other = Vec::new();
+    for line in output.lines() {
+        match CompilerMessage::parse(line) {
+            Some(message) => match groups.iter_mut().find(|(file, _)| *file == message.file) {
+                Some((_, messages)) => messages.push(message),
+                None => groups.push((message.file.clone(), vec![message])),
+            },
+            None if !line.trim().is_empty() => other.push(line),
+            None => {}
+        }
+    }
+    if groups.is_empty() {
+        return output.to_string();
+    }
+
+    let paint = |text: &str, code: &str| {
+        if color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut rendered = String::new();
+    for (file, messages) in &groups {
+        rendered.push_str(&format!("❌ {}\n", paint(&file.display().to_string(), "1")));
+        for message in messages {
+            let location = match message.column {
+                Some(column) => format!("{}:{column}", message.line),
+                None => message.line.to_string(),
+            };
+            rendered.push_str(&format!(
+                "   {}  {}\n",
+                paint(&location, "1;31"),
+                message.message
+            ));
+            if let Some(source) = source_line(file, message.line) {
+                let gutter = message.line.to_string();
+                rendered.push_str(&format!("      {gutter} | {source}\n"));
+                if let Some(column) = message.column.filter(|c| *c > 0) {
+                    let pad = " ".repeat(gutter.len());
+                    let caret = " ".repeat(column - 1);
+                    rendered.push_str(&format!("      {pad} | {caret}{}\n", paint("^", "31")));
+                }
+            }
+        }
+    }
+    for line in other {
+        rendered.push_str(line);
+        rendered.push('\n');
+    }
+    rendered
+}
+
+fn source_line(file: &Path, line: usize) -> Option<String> {
+    let content = std::fs::read_to_string(file).ok()?;
+    content
+        .lines()
+        .nth(line.checked_sub(1)?)
+        .map(|l| l.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_located_messages() {
+        assert_eq!(
+            CompilerMessage::parse("app/api/main.cln:12:5: error: expected ':' here"),
+            Some(CompilerMessage {
+                file: PathBuf::from("app/api/main.cln"),
+                line: 12,
+                column: Some(5),
+                message: "error: expected ':' here".to_string(),
+            })
+        );
+        assert_eq!(
+            CompilerMessage::parse("  --> lib/util.cln:3: unknown function"),
+            Some(CompilerMessage {
+                file: PathBuf::from("lib/util.cln"),
+                line: 3,
+                column: None,
+                message: "unknown function".to_string(),
+            })
+        );
+        let windows = CompilerMessage::parse(r"C:\proj\main.cln:7:1: bad token").unwrap();
+        assert_eq!(windows.file, PathBuf::from(r"C:\proj\main.cln"));
+        assert_eq!((windows.line, windows.column), (7, Some(1)));
+
+        assert_eq!(CompilerMessage::parse("error: compilation failed"), None);
+        assert_eq!(
+            CompilerMessage::parse("note: see main.cln for details"),
+            None
+        );
+    }
+
+    #[test]
+    fn render_groups_by_file_with_source_excerpt() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let main = tmp.path().join("main.cln");
+        std::fs::write(&main, "start()\n\tprint(x\n").unwrap();
+        let main = main.display();
+
+        let output = format!(
+            "{main}:2:8: expected ')'\nother.cln:1: unused import\n{main}:1:1: warning: empty\nerror: 2 errors\n"
+        );
+        let rendered = render_with(&output, false);
+
+        assert_eq!(
+            rendered,
+            format!(
+                "❌ {main}\n   2:8  expected ')'\n      2 | \tprint(x\n        |        ^\n   \
+                 1:1  warning: empty\n      1 | start()\n        | ^\n\
+                 ❌ other.cln\n   1  unused import\n\
+                 error: 2 errors\n"
+            )
+        );
+
+        assert_eq!(render_with("plain failure\n", false), "plain failure\n");
+    }
+}
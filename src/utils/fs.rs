@@ -1,5 +1,5 @@
 use crate::error::{CleenError, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn ensure_dir_exists(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -687,11 +687,97 @@ fn is_graveyard_name(name: &str) -> bool {
     name.contains(".locked-")
 }
 
+/// Upper bound on size-walker threads. Directory walks are I/O bound, so
+/// past a handful of threads the disk is the bottleneck, not the CPU.
+const MAX_SIZE_WORKERS: usize = 8;
+
+/// Sum the byte size of every file under `path`, spreading the walk
+/// across a small bounded pool of threads. Same error semantics as
+/// [`dir_size_bytes`]: unreadable entries count as `0`.
+///
+/// The tree is expanded breadth-first until there are enough
+/// subdirectories to keep every worker busy, then each worker pulls
+/// subtrees from a shared queue. Falls back to the single-threaded walk
+/// when only one core is available, when the tree is too shallow to
+/// split, or when no worker thread can be spawned — the calling thread
+/// drains the queue too, so nothing is lost if spawning fails.
+pub fn dir_size(path: &Path) -> u64 {
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(MAX_SIZE_WORKERS);
+    dir_size_with_workers(path, workers)
+}
+
+fn dir_size_with_workers(path: &Path, workers: usize) -> u64 {
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    if workers <= 1 {
+        return dir_size_bytes(path);
+    }
+
+    // Expand a few levels so a version dir holding a single `bin/` still
+    // splits into enough independent subtrees.
+    let mut total = 0u64;
+    let mut frontier = vec![path.to_path_buf()];
+    for _ in 0..3 {
+        if frontier.len() >= workers {
+            break;
+        }
+        let mut next = Vec::new();
+        for dir in &frontier {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                if meta.is_file() {
+                    total += meta.len();
+                } else if meta.is_dir() {
+                    next.push(entry.path());
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    if frontier.len() < 2 {
+        return total + frontier.iter().map(|d| dir_size_bytes(d)).sum::<u64>();
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let subtree_total = AtomicU64::new(0);
+    let drain = || loop {
+        let i = next_index.fetch_add(1, Ordering::Relaxed);
+        let Some(dir) = frontier.get(i) else {
+            break;
+        };
+        subtree_total.fetch_add(dir_size_bytes(dir), Ordering::Relaxed);
+    };
+
+    std::thread::scope(|scope| {
+        for _ in 1..workers.min(frontier.len()) {
+            if std::thread::Builder::new()
+                .name("cleen-dir-size".into())
+                .spawn_scoped(scope, drain)
+                .is_err()
+            {
+                break;
+            }
+        }
+        drain();
+    });
+
+    total + subtree_total.into_inner()
+}
+
 /// Sum the byte size of every file under `path`, following the tree
 /// recursively. Returns `0` on permission errors and broken symlinks
 /// rather than aborting — this is used for cleanup accounting, not for
 /// data correctness.
-fn dir_size_bytes(path: &Path) -> u64 {
+pub fn dir_size_bytes(path: &Path) -> u64 {
     let mut total = 0u64;
     let entries = match std::fs::read_dir(path) {
         Ok(e) => e,
@@ -710,6 +796,23 @@ fn dir_size_bytes(path: &Path) -> u64 {
     total
 }
 
+/// Format bytes as human-readable size
+pub fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
 /// Count eviction graveyards directly under `parent` without removing
 /// them. Used by `cleen doctor` to surface accumulation early.
 pub fn count_graveyards(parent: &Path) -> usize {
@@ -770,6 +873,126 @@ pub fn prune_graveyards(parent: &Path) -> (usize, u64) {
     (removed, freed)
 }
 
+/// Removes a half-populated install directory when dropped, unless
+/// [`PartialDirGuard::disarm`] is called first.
+///
+/// Installers create the version directory before downloading and
+/// extracting into it. Any `?` between that point and a usable binary
+/// would otherwise leave the directory behind, and the next attempt
+/// would then refuse with `VersionAlreadyInstalled`.
+pub struct PartialDirGuard {
+    path: PathBuf,
+    armed: bool,
+}
+
+impl PartialDirGuard {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            armed: true,
+        }
+    }
+
+    /// Keep the directory: the install reached a consistent state.
+    pub fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for PartialDirGuard {
+    fn drop(&mut self) {
+        if self.armed && self.path.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&self.path) {
+                eprintln!(
+                    "  warning: could not remove partial install {}: {e}",
+                    self.path.display()
+                );
+            }
+        }
+    }
+}
+
+/// Bytes of free space an install of a `asset_size`-byte archive needs:
+/// the archive itself plus the same again for extraction headroom.
+pub fn required_install_space(asset_size: u64) -> u64 {
+    asset_size.saturating_mul(2)
+}
+
+/// Query the free space (in bytes) on the filesystem holding `path`.
+///
+/// `path` does not need to exist yet — the nearest existing ancestor is
+/// queried instead, which is what a not-yet-created version directory
+/// needs. Returns `None` when the platform query fails; callers treat
+/// that as "unknown" and proceed rather than blocking the install.
+pub fn available_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+
+    #[cfg(unix)]
+    {
+        // POSIX `df -Pk` prints a header plus one line per filesystem with
+        // the available 1K-blocks in the fourth column.
+        let output = std::process::Command::new("df")
+            .arg("-Pk")
+            .arg(existing)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let line = stdout.lines().nth(1)?;
+        let kib: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+        Some(kib.saturating_mul(1024))
+    }
+
+    #[cfg(windows)]
+    {
+        let script = format!(
+            "(Get-Item -LiteralPath '{}').PSDrive.Free",
+            existing.display().to_string().replace('\'', "''")
+        );
+        let output = std::process::Command::new("powershell")
+            .args(["-NoProfile", "-Command", &script])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = existing;
+        None
+    }
+}
+
+/// Compare a required byte count against the space reported for `path`.
+/// An unknown `available` value passes so a failing `df` never blocks an
+/// install on its own.
+pub fn check_disk_space(path: &Path, required: u64, available: Option<u64>) -> Result<()> {
+    match available {
+        Some(available) if available < required => Err(CleenError::InsufficientDiskSpace {
+            path: path.to_path_buf(),
+            required,
+            available,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Fail early with [`CleenError::InsufficientDiskSpace`] when the
+/// filesystem holding `target` cannot fit a `asset_size`-byte archive
+/// plus its extracted contents.
+pub fn ensure_install_space(target: &Path, asset_size: u64) -> Result<()> {
+    check_disk_space(
+        target,
+        required_install_space(asset_size),
+        available_space(target),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1024,4 +1247,103 @@ mod tests {
 
         fs::remove_dir_all(&tmp).unwrap();
     }
+
+    #[test]
+    fn required_install_space_doubles_asset_size() {
+        assert_eq!(required_install_space(50 * 1024 * 1024), 100 * 1024 * 1024);
+        assert_eq!(required_install_space(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn check_disk_space_rejects_when_available_is_short() {
+        let target = Path::new("/opt/cleen/versions/0.30.0");
+        let required = required_install_space(40 * 1024 * 1024);
+
+        match check_disk_space(target, required, Some(10 * 1024 * 1024)) {
+            Err(CleenError::InsufficientDiskSpace {
+                path,
+                required: r,
+                available,
+            }) => {
+                assert_eq!(path, target);
+                assert_eq!(r, required);
+                assert_eq!(available, 10 * 1024 * 1024);
+            }
+            other => panic!("expected InsufficientDiskSpace, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn check_disk_space_passes_with_enough_or_unknown_space() {
+        let target = Path::new("/opt/cleen/versions/0.30.0");
+        assert!(check_disk_space(target, 100, Some(100)).is_ok());
+        assert!(check_disk_space(target, 100, Some(u64::MAX)).is_ok());
+        // A failed platform query must not block the install.
+        assert!(check_disk_space(target, u64::MAX, None).is_ok());
+    }
+
+    #[test]
+    fn available_space_resolves_missing_path_to_existing_ancestor() {
+        let missing = std::env::temp_dir()
+            .join(format!("cleen-fs-space-{}", std::process::id()))
+            .join("not/yet/created");
+        assert!(!missing.exists());
+        if cfg!(unix) {
+            assert!(available_space(&missing).is_some());
+        }
+    }
+
+    #[test]
+    fn partial_dir_guard_removes_dir_unless_disarmed() {
+        let tmp = std::env::temp_dir().join(format!("cleen-fs-guard-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&tmp);
+
+        let dropped = tmp.join("dropped");
+        fs::create_dir_all(dropped.join("bin")).unwrap();
+        drop(PartialDirGuard::new(&dropped));
+        assert!(!dropped.exists());
+
+        let kept = tmp.join("kept");
+        fs::create_dir_all(&kept).unwrap();
+        PartialDirGuard::new(&kept).disarm();
+        assert!(kept.exists());
+
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+
+    #[test]
+    fn dir_size_matches_sequential_walk_on_deep_tree() {
+        let tmp = std::env::temp_dir().join(format!("cleen-fs-size-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&tmp);
+
+        // 6 top-level dirs, each 4 levels deep with a file at every level,
+        // plus a single-child chain so the breadth-first split has to dig.
+        let mut expected = 0u64;
+        for branch in 0..6 {
+            let mut dir = tmp.join(format!("branch-{branch}"));
+            for depth in 0..4 {
+                fs::create_dir_all(&dir).unwrap();
+                let len = (branch * 100 + depth * 10 + 1) as usize;
+                fs::write(dir.join("data.bin"), vec![0u8; len]).unwrap();
+                expected += len as u64;
+                dir = dir.join(format!("level-{depth}"));
+            }
+        }
+        let chain = tmp.join("only").join("child").join("leaf");
+        fs::create_dir_all(&chain).unwrap();
+        fs::write(chain.join("tail.bin"), vec![0u8; 777]).unwrap();
+        fs::write(tmp.join("root.bin"), vec![0u8; 33]).unwrap();
+        expected += 777 + 33;
+
+        let sequential = dir_size_bytes(&tmp);
+        let parallel = dir_size_with_workers(&tmp, 4);
+
+        assert_eq!(sequential, expected);
+        assert_eq!(parallel, expected);
+        assert_eq!(dir_size_with_workers(&tmp, 1), expected);
+        assert_eq!(dir_size(&tmp), expected);
+        assert_eq!(dir_size(&tmp.join("missing")), 0);
+
+        fs::remove_dir_all(&tmp).unwrap();
+    }
 }
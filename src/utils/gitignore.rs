@@ -0,0 +1,142 @@
+//! Just enough `.gitignore` handling for `frame build` to tell whether its
+//! output directory would be committed. Only the repository's top-level
+//! `.gitignore` is read; `*` and `?` wildcards, anchoring, trailing slashes
+//! and `!` negations are understood, `**` is not.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+/// Nearest directory at or above `start` that contains a `.git` entry.
+pub fn repo_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Whether the directory at `relative` (from the repository root) is
+/// ignored by `content`, either directly or through an ignored parent.
+pub fn is_ignored(content: &str, relative: &Path) -> bool {
+    let components: Vec<String> = relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+
+    // Git never looks inside an ignored directory, so the first ignored
+    // prefix settles it. Within one prefix the last matching rule wins.
+    (1..=components.len()).any(|len| {
+        let prefix = &components[..len];
+        let mut ignored = false;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            if rule_matches(pattern.trim_end_matches('/'), prefix) {
+                ignored = !negated;
+            }
+        }
+        ignored
+    })
+}
+
+/// Append `entry` to the `.gitignore` at `path`, creating the file if
+/// needed and keeping the existing last line intact.
+pub fn append_entry(path: &Path, entry: &str) -> std::io::Result<()> {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        writeln!(file)?;
+    }
+    writeln!(file, "{entry}")
+}
+
+/// A pattern with an inner `/` is anchored to the repository root and has
+/// to match the whole prefix; otherwise it matches the prefix's last name.
+fn rule_matches(pattern: &str, prefix: &[String]) -> bool {
+    if pattern.is_empty() {
+        return false;
+    }
+    if pattern.contains('/') {
+        let parts: Vec<&str> = pattern.trim_start_matches('/').split('/').collect();
+        parts.len() == prefix.len()
+            && parts
+                .iter()
+                .zip(prefix)
+                .all(|(part, name)| glob_match(part, name))
+    } else {
+        prefix.last().is_some_and(|name| glob_match(pattern, name))
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_common_gitignore_shapes() {
+        let dist = Path::new("dist");
+        let nested = Path::new("web/build/.generated");
+
+        assert!(is_ignored("dist/\n", dist));
+        assert!(is_ignored("/dist\n", dist));
+        assert!(is_ignored("# output\nd?st\n", dist));
+        assert!(is_ignored("build\n", nested));
+        assert!(is_ignored("web/*\n", nested));
+        assert!(is_ignored(".generated/\n", nested));
+
+        assert!(!is_ignored("", dist));
+        assert!(!is_ignored("target/\n", dist));
+        assert!(!is_ignored("/build\n", nested));
+        assert!(!is_ignored("dist/\n!dist/\n", dist));
+    }
+
+    #[test]
+    fn append_entry_keeps_last_line() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(".gitignore");
+        fs::write(&path, "target").unwrap();
+
+        append_entry(&path, "/dist/").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "target\n/dist/\n");
+    }
+}
@@ -1,2 +1,5 @@
+pub mod compiler_output;
 pub mod fs;
+pub mod gitignore;
 pub mod shell;
+pub mod timing;
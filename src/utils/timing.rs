@@ -0,0 +1,132 @@
+//! Opt-in phase timings for `--timings`.
+//!
+//! Long-running paths wrap each phase in [`phase`]. Nothing is recorded
+//! until [`enable`] is called, so the wrappers cost one mutex check when
+//! timings are off. The CLI enables recording before dispatch and prints
+//! [`Timings::report`] to stderr at the end, so `--json` output stays parseable.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static RECORDER: Mutex<Option<Timings>> = Mutex::new(None);
+
+/// Ordered phase durations. A phase that runs more than once accumulates.
+#[derive(Debug, Default, Clone)]
+pub struct Timings {
+    entries: Vec<(String, Duration)>,
+}
+
+impl Timings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `elapsed` to `name`, keeping first-seen order.
+    pub fn record(&mut self, name: &str, elapsed: Duration) {
+        match self.entries.iter_mut().find(|(n, _)| n == name) {
+            Some((_, total)) => *total += elapsed,
+            None => self.entries.push((name.to_string(), elapsed)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Duration> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, d)| *d)
+    }
+
+    pub fn entries(&self) -> &[(String, Duration)] {
+        &self.entries
+    }
+
+    pub fn total(&self) -> Duration {
+        self.entries.iter().map(|(_, d)| *d).sum()
+    }
+
+    /// Human-readable breakdown, one phase per line.
+    pub fn report(&self) -> String {
+        let width = self.entries.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
+        let mut out = String::from("⏱️  Timings:\n");
+        for (name, elapsed) in &self.entries {
+            out.push_str(&format!(
+                "   {name:<width$}  {:>8.3}s\n",
+                elapsed.as_secs_f64()
+            ));
+        }
+        out.push_str(&format!(
+            "   {:<width$}  {:>8.3}s\n",
+            "total",
+            self.total().as_secs_f64()
+        ));
+        out
+    }
+}
+
+/// Start recording phases for the rest of the process.
+pub fn enable() {
+    if let Ok(mut recorder) = RECORDER.lock() {
+        recorder.get_or_insert_with(Timings::new);
+    }
+}
+
+/// Run `f`, recording its duration under `name` when timings are enabled.
+pub fn phase<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    record(name, started.elapsed());
+    result
+}
+
+/// Record an already-measured phase, for work that doesn't fit a closure.
+pub fn record(name: &str, elapsed: Duration) {
+    if let Ok(mut recorder) = RECORDER.lock() {
+        if let Some(timings) = recorder.as_mut() {
+            timings.record(name, elapsed);
+        }
+    }
+}
+
+/// Stop recording and return what was collected, if timings were enabled.
+pub fn take() -> Option<Timings> {
+    RECORDER
+        .lock()
+        .ok()
+        .and_then(|mut recorder| recorder.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_accumulates_repeated_phases_in_order() {
+        let mut timings = Timings::new();
+        timings.record("download", Duration::from_millis(200));
+        timings.record("extraction", Duration::from_millis(50));
+        timings.record("download", Duration::from_millis(100));
+
+        let names: Vec<&str> = timings.entries().iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["download", "extraction"]);
+        assert_eq!(timings.get("download"), Some(Duration::from_millis(300)));
+        assert_eq!(timings.total(), Duration::from_millis(350));
+        assert!(timings.report().contains("extraction"));
+    }
+
+    #[test]
+    fn phase_records_only_phases_that_ran() {
+        enable();
+        let value = phase("github fetch", || 42);
+        phase("download", || ());
+        let timings = take().expect("timings were enabled");
+
+        assert_eq!(value, 42);
+        assert!(timings.get("github fetch").is_some());
+        assert!(timings.get("download").is_some());
+        assert!(timings.get("validation").is_none());
+
+        // Disabled again after take(): nothing is recorded.
+        phase("validation", || ());
+        assert!(take().is_none());
+    }
+}
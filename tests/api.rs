@@ -0,0 +1,82 @@
+//! Tests for the embeddable API in `cleen::api`. Everything here runs
+//! against a `Config` rooted in a temp directory and never reaches the
+//! network: the install cases exercise the paths that resolve from disk.
+
+use cleen::api::{self, InstallOptions};
+use cleen::core::config::Config;
+use cleen::error::CleenError;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn test_config(cleen_dir: &Path) -> Config {
+    Config {
+        cleen_dir: cleen_dir.to_path_buf(),
+        ..Config::default()
+    }
+}
+
+fn fake_install(config: &Config, version: &str) {
+    let binary = config.get_version_binary(version);
+    fs::create_dir_all(binary.parent().unwrap()).unwrap();
+    fs::write(&binary, b"#!/bin/sh\n").unwrap();
+}
+
+#[test]
+fn install_reports_existing_version_without_downloading() {
+    let tmp = TempDir::new().unwrap();
+    let config = test_config(tmp.path());
+    fake_install(&config, "0.30.0");
+
+    let report = api::install_with_config(
+        &config,
+        &InstallOptions {
+            version: "v0.30.0".to_string(),
+            ..InstallOptions::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(report.version, "0.30.0");
+    assert_eq!(report.binary_path, config.get_version_binary("0.30.0"));
+    assert!(report.already_installed);
+    assert!(!report.reinstalled);
+    assert_eq!(report.asset_name, None);
+    assert_eq!(report.bytes, 0);
+    assert!(report.duration < std::time::Duration::from_secs(5));
+}
+
+#[test]
+fn install_rejects_invalid_version_before_any_work() {
+    let tmp = TempDir::new().unwrap();
+    let config = test_config(tmp.path());
+
+    let err = api::install_with_config(
+        &config,
+        &InstallOptions {
+            version: "../../etc".to_string(),
+            ..InstallOptions::default()
+        },
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, CleenError::InvalidVersion { .. }));
+    assert!(!config.get_versions_dir().exists());
+}
+
+#[test]
+fn installed_versions_lists_compiler_versions_only() {
+    let tmp = TempDir::new().unwrap();
+    let config = test_config(tmp.path());
+    fake_install(&config, "0.30.0");
+    fake_install(&config, "0.29.1");
+    fs::create_dir_all(config.get_frame_versions_dir().join("1.0.0")).unwrap();
+
+    let versions: Vec<String> = api::installed_versions_with_config(&config)
+        .unwrap()
+        .into_iter()
+        .map(|v| v.version)
+        .collect();
+
+    assert_eq!(versions, vec!["0.29.1".to_string(), "0.30.0".to_string()]);
+}
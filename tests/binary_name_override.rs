@@ -0,0 +1,48 @@
+//! `CLEEN_CLN_BINARY` renames the compiler binary everywhere cleen looks
+//! for it. This lives in its own test binary because it sets a process-wide
+//! environment variable that would race with other tests.
+
+use cleen::core::binaries::{self, CLN_BINARY_ENV};
+use cleen::core::config::Config;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn test_config(cleen_dir: &Path) -> Config {
+    Config {
+        cleen_dir: cleen_dir.to_path_buf(),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn override_changes_resolved_compiler_binary() {
+    let tmp = TempDir::new().unwrap();
+    let config = test_config(tmp.path());
+
+    std::env::remove_var(CLN_BINARY_ENV);
+    assert_eq!(
+        config.get_version_binary("1.0.0"),
+        config
+            .get_version_dir("1.0.0")
+            .join(binaries::executable("cln"))
+    );
+
+    std::env::set_var(CLN_BINARY_ENV, "clean");
+    let renamed = binaries::executable("clean");
+    assert_eq!(binaries::compiler(), renamed);
+    assert_eq!(
+        config.get_version_binary("1.0.0"),
+        config.get_version_dir("1.0.0").join(&renamed)
+    );
+    // The user-facing shim keeps its name.
+    assert_eq!(
+        config.get_shim_path(),
+        config.get_bin_dir().join(binaries::executable("cln"))
+    );
+
+    // Unsafe overrides fall back to the default name.
+    std::env::set_var(CLN_BINARY_ENV, "../evil");
+    assert_eq!(binaries::compiler(), binaries::executable("cln"));
+
+    std::env::remove_var(CLN_BINARY_ENV);
+}
@@ -0,0 +1,68 @@
+//! `cleen doctor` flags `cln` and `frame` shims that are dangling symlinks
+//! instead of reporting them as present. Symlinks are created with the Unix
+//! API, so these tests only run on Unix.
+#![cfg(unix)]
+
+use cleen::core::config::Config;
+use std::fs;
+use std::os::unix::fs::{symlink, PermissionsExt};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn doctor(home: &Path) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .arg("doctor")
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .env("PATH", "/usr/bin:/bin")
+        .output()
+        .expect("failed to run cleen doctor");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn doctor_reports_dangling_cln_and_frame_shims() {
+    let home = TempDir::new().unwrap();
+    let config = Config {
+        active_version: Some("0.2.0".to_string()),
+        frame_version: Some("1.0.0".to_string()),
+        cleen_dir: home.path().join(".cleen"),
+        ..Config::default()
+    };
+    fs::create_dir_all(config.get_bin_dir()).unwrap();
+    fs::write(
+        config.cleen_dir.join("config.json"),
+        serde_json::to_string(&config).unwrap(),
+    )
+    .unwrap();
+
+    // `cln` points at a version directory that was deleted by hand.
+    symlink(config.get_version_binary("0.1.0"), config.get_shim_path()).unwrap();
+
+    // `frame` points at a binary that exists but isn't executable.
+    let frame_binary = config.get_frame_version_binary("1.0.0");
+    fs::create_dir_all(frame_binary.parent().unwrap()).unwrap();
+    fs::write(&frame_binary, "#!/bin/sh\n").unwrap();
+    fs::set_permissions(&frame_binary, fs::Permissions::from_mode(0o644)).unwrap();
+    symlink(&frame_binary, config.get_frame_shim_path()).unwrap();
+
+    let stdout = doctor(home.path());
+
+    assert!(
+        stdout.contains("Shim is a broken symlink: its target")
+            && stdout.contains("does not exist"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("Run 'cleen use 0.2.0' to re-create it"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("✅ Shim exists"), "{stdout}");
+    assert!(
+        stdout.contains("Frame shim is a broken symlink") && stdout.contains("is not executable"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Run: cleen frame use 1.0.0"), "{stdout}");
+}
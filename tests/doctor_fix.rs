@@ -0,0 +1,65 @@
+//! `cleen doctor --fix` repairs what needs no decision (directories, the
+//! shim, PATH configuration) and reports only what is left afterwards. The
+//! shim is a Unix symlink, so these tests only run on Unix.
+#![cfg(unix)]
+
+use cleen::core::config::Config;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn doctor(home: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .arg("doctor")
+        .args(args)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("SHELL", "/bin/zsh")
+        .env("CLEEN_HEARTBEAT", "off")
+        .env("PATH", "/usr/bin:/bin")
+        .output()
+        .expect("failed to run cleen doctor");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn fix_creates_shim_and_path_config() {
+    let home = TempDir::new().unwrap();
+    let config = Config {
+        active_version: Some("0.2.0".to_string()),
+        cleen_dir: home.path().join(".cleen"),
+        ..Config::default()
+    };
+    let binary = config.get_version_binary("0.2.0");
+    fs::create_dir_all(binary.parent().unwrap()).unwrap();
+    fs::write(&binary, "#!/bin/sh\necho 0.2.0\n").unwrap();
+    fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::write(
+        config.cleen_dir.join("config.json"),
+        serde_json::to_string(&config).unwrap(),
+    )
+    .unwrap();
+
+    // Without --fix nothing changes.
+    let stdout = doctor(home.path(), &[]);
+    assert!(stdout.contains("❌ Shim missing"), "{stdout}");
+    assert!(stdout.contains("cleen doctor --fix"), "{stdout}");
+    assert!(!config.get_shim_path().exists());
+    assert!(!home.path().join(".zshrc").exists());
+
+    let stdout = doctor(home.path(), &["--fix"]);
+    assert!(stdout.contains("🔧 Created the shim for 0.2.0"), "{stdout}");
+    assert!(config.get_shim_path().exists());
+
+    let zshrc = fs::read_to_string(home.path().join(".zshrc")).unwrap();
+    assert!(
+        zshrc.contains(&*config.get_bin_dir().to_string_lossy()),
+        "{zshrc}"
+    );
+
+    // The follow-up run no longer sees the repaired shim.
+    let stdout = doctor(home.path(), &[]);
+    assert!(!stdout.contains("❌ Shim missing"), "{stdout}");
+}
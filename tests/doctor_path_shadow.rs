@@ -0,0 +1,64 @@
+//! `cleen doctor` resolves `cln` through PATH and reports any other `cln`
+//! that would run instead of cleen's shim. The stand-in binaries are shell
+//! scripts, so these tests only run on Unix.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+fn write_script(path: &Path, body: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+fn doctor(home: &Path, path_dirs: &[PathBuf]) -> String {
+    let mut dirs = path_dirs.to_vec();
+    dirs.extend([PathBuf::from("/usr/bin"), PathBuf::from("/bin")]);
+    let output = Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .arg("doctor")
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .env("PATH", std::env::join_paths(dirs).unwrap())
+        .output()
+        .expect("failed to run cleen doctor");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn doctor_reports_cln_shadowing_the_shim() {
+    let home = TempDir::new().unwrap();
+    let bin_dir = home.path().join(".cleen").join("bin");
+    write_script(&bin_dir.join("cln"), "echo shim");
+    let decoy_dir = home.path().join("opt").join("homebrew").join("bin");
+    write_script(&decoy_dir.join("cln"), "echo decoy");
+
+    let stdout = doctor(home.path(), &[decoy_dir.clone(), bin_dir.clone()]);
+
+    assert!(stdout.contains("shadows cleen's shim"), "{stdout}");
+    assert!(
+        stdout.contains(&format!("{:?}", decoy_dir.join("cln"))),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn doctor_accepts_shim_first_on_path() {
+    let home = TempDir::new().unwrap();
+    let bin_dir = home.path().join(".cleen").join("bin");
+    write_script(&bin_dir.join("cln"), "echo shim");
+    let decoy_dir = home.path().join("opt").join("homebrew").join("bin");
+    write_script(&decoy_dir.join("cln"), "echo decoy");
+
+    let stdout = doctor(home.path(), &[bin_dir, decoy_dir]);
+
+    assert!(
+        stdout.contains("'cln' resolves to cleen's shim"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("shadows"), "{stdout}");
+}
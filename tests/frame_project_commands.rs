@@ -0,0 +1,709 @@
+//! `cleen frame build`/`serve` against stand-in binaries: `frame-cli`,
+//! `frame-runtime` and the compilers are shell scripts, so these tests only
+//! run on Unix.
+#![cfg(unix)]
+
+use cleen::core::config::Config;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+fn write_script(path: &Path, body: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// A home with compilers 1.0.0 and 2.0.0 (1.0.0 active), a `frame-cli`
+/// whose build reports which `cln` it found on PATH, and a `frame-runtime`
+/// that exits straight away. The compilers write a stub to their `-o` path.
+fn setup(home: &Path) -> Config {
+    let config = Config {
+        active_version: Some("1.0.0".to_string()),
+        frame_version: Some("1.0.0".to_string()),
+        cleen_dir: home.join(".cleen"),
+        ..Config::default()
+    };
+    fs::create_dir_all(&config.cleen_dir).unwrap();
+    fs::write(
+        config.cleen_dir.join("config.json"),
+        serde_json::to_string(&config).unwrap(),
+    )
+    .unwrap();
+
+    for version in ["1.0.0", "2.0.0"] {
+        write_script(
+            &config.get_version_binary(version),
+            &format!(
+                "while [ $# -gt 0 ]; do [ \"$1\" = -o ] && echo {version} > \"$2\"; shift; done\n\
+                 echo {version}"
+            ),
+        );
+    }
+    let frame_dir = config.get_frame_versions_dir().join("1.0.0");
+    write_script(&frame_dir.join("frame-cli"), "echo \"compiler $(cln)\"");
+    write_script(&frame_dir.join("frame-runtime"), "exit 0");
+    config
+}
+
+fn frame_build(home: &Path, project: &Path, extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(["frame", "build"])
+        .arg(project)
+        .args(extra)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen frame build")
+}
+
+#[test]
+fn compiler_flag_selects_the_invoked_compiler() {
+    let home = TempDir::new().unwrap();
+    setup(home.path());
+    let project = TempDir::new().unwrap();
+
+    let output = frame_build(home.path(), project.path(), &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("compiler 1.0.0"), "{stdout}");
+
+    let output = frame_build(home.path(), project.path(), &["--compiler", "2.0.0"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("compiler 2.0.0"), "{stdout}");
+    assert!(stdout.contains("Compiler: cln 2.0.0"), "{stdout}");
+}
+
+#[test]
+fn strict_build_fails_on_warnings() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-cli"),
+        "echo 'warning: component Card is never used' >&2",
+    );
+    let project = TempDir::new().unwrap();
+
+    let output = frame_build(home.path(), project.path(), &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("1 warning(s)"), "{stdout}");
+
+    let output = frame_build(home.path(), project.path(), &["--strict"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{stderr}");
+    assert!(stderr.contains("treated as errors"), "{stderr}");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Build finished"));
+}
+
+#[test]
+fn build_hints_at_output_missing_from_gitignore() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    // `frame-cli build <input> --output <dir> ...`
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-cli"),
+        "mkdir -p \"$4\"",
+    );
+    let project = TempDir::new().unwrap();
+    fs::create_dir(project.path().join(".git")).unwrap();
+    fs::write(project.path().join(".gitignore"), "target/\n").unwrap();
+    let out = project.path().join("dist");
+    let out = out.to_str().unwrap();
+
+    let output = frame_build(home.path(), project.path(), &["--output", out]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("/dist/ isn't in .gitignore"), "{stdout}");
+    assert_eq!(
+        fs::read_to_string(project.path().join(".gitignore")).unwrap(),
+        "target/\n"
+    );
+
+    let output = frame_build(
+        home.path(),
+        project.path(),
+        &["--output", out, "--update-gitignore"],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(project.path().join(".gitignore")).unwrap(),
+        "target/\n/dist/\n"
+    );
+
+    let output = frame_build(home.path(), project.path(), &["--output", out]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains(".gitignore"), "{stdout}");
+}
+
+#[test]
+fn clean_build_removes_stale_output_first() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    // `frame-cli build <input> --output <dir> ...`
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-cli"),
+        "mkdir -p \"$4/.generated\" && touch \"$4/.generated/main.cln\"",
+    );
+    let project = TempDir::new().unwrap();
+    let generated = project.path().join("dist").join(".generated");
+    fs::create_dir_all(&generated).unwrap();
+    fs::write(generated.join("old_route.cln"), "stale").unwrap();
+    let out = project.path().join("dist");
+    let out = out.to_str().unwrap();
+
+    let output = frame_build(home.path(), project.path(), &["--output", out]);
+    assert!(output.status.success());
+    assert!(generated.join("old_route.cln").exists());
+
+    let output = frame_build(home.path(), project.path(), &["--output", out, "--clean"]);
+    assert!(output.status.success());
+    assert!(!generated.join("old_route.cln").exists());
+    assert!(generated.join("main.cln").exists());
+
+    // Never outside the project, and never the project itself.
+    let elsewhere = TempDir::new().unwrap();
+    fs::write(elsewhere.path().join("keep.txt"), "keep").unwrap();
+    for target in [elsewhere.path(), project.path()] {
+        let output = frame_build(
+            home.path(),
+            project.path(),
+            &["--output", target.to_str().unwrap(), "--clean"],
+        );
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success());
+        assert!(stderr.contains("Refusing to clean"), "{stderr}");
+    }
+    assert!(elsewhere.path().join("keep.txt").exists());
+    assert!(generated.join("main.cln").exists());
+}
+
+#[test]
+fn opt_levels_keep_one_artifact_per_level_with_size_table() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    // `frame-cli build <input> --output <dir> --optimize <level>`: -O0
+    // writes a bigger module than any other level.
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-cli"),
+        "mkdir -p \"$4\"\n\
+         if [ \"$6\" = 0 ]; then n=4096; else n=1024; fi\n\
+         head -c $n /dev/zero > \"$4/app.wasm\"",
+    );
+    let project = TempDir::new().unwrap();
+    let dist = project.path().join("dist");
+
+    let output = frame_build(
+        home.path(),
+        project.path(),
+        &[
+            "--output",
+            dist.to_str().unwrap(),
+            "--opt-level",
+            "0",
+            "--opt-level",
+            "2",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+
+    assert_eq!(
+        fs::metadata(dist.join("app.opt0.wasm")).unwrap().len(),
+        4096
+    );
+    assert_eq!(
+        fs::metadata(dist.join("app.opt2.wasm")).unwrap().len(),
+        1024
+    );
+    assert!(!dist.join("app.wasm").exists());
+    let row = |level: &str| {
+        stdout
+            .lines()
+            .find(|l| l.starts_with(level))
+            .unwrap_or_else(|| panic!("no {level} row in:\n{stdout}"))
+            .to_string()
+    };
+    assert!(row("-O0").contains("app.opt0.wasm"), "{stdout}");
+    assert!(row("-O2").contains("-75%"), "{stdout}");
+}
+
+#[test]
+fn compiler_flag_rejects_a_version_that_is_not_installed() {
+    let home = TempDir::new().unwrap();
+    setup(home.path());
+    let project = TempDir::new().unwrap();
+
+    let output = frame_build(home.path(), project.path(), &["--compiler", "9.9.9"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("9.9.9"), "{stderr}");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("compiler"));
+}
+
+#[test]
+fn serve_keeps_compiled_wasm_at_out_path() {
+    let home = TempDir::new().unwrap();
+    setup(home.path());
+    let project = TempDir::new().unwrap();
+    let entry = project.path().join("main.cln");
+    fs::write(&entry, "start()\n").unwrap();
+    let out = project.path().join("build").join("debug").join("app.wasm");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(["frame", "serve"])
+        .arg(&entry)
+        .args(["--compiler", "2.0.0", "--out"])
+        .arg(&out)
+        .env("HOME", home.path())
+        .env("USERPROFILE", home.path())
+        .env("TMPDIR", home.path())
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen frame serve");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert_eq!(fs::read_to_string(&out).unwrap().trim(), "2.0.0");
+    assert!(!home.path().join("cleen-serve-app.wasm").exists());
+}
+
+#[test]
+fn serve_profile_sets_env_and_summarizes_timings() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    let seen = home.path().join("profile-env.txt");
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-runtime"),
+        &format!(
+            "echo \"$FRAME_PROFILE\" > {}\n\
+             [ -n \"$FRAME_PROFILE_OUTPUT\" ] && \
+             echo '{{\"method\":\"GET\",\"path\":\"/hello\",\"duration_ms\":1.5}}' >> \"$FRAME_PROFILE_OUTPUT\"\n\
+             exit 0",
+            seen.display()
+        ),
+    );
+    let project = TempDir::new().unwrap();
+    let entry = project.path().join("main.cln");
+    fs::write(&entry, "start()\n").unwrap();
+
+    let serve = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_cleen"))
+            .args(["frame", "serve"])
+            .arg(&entry)
+            .args(extra)
+            .env("HOME", home.path())
+            .env("USERPROFILE", home.path())
+            .env("TMPDIR", home.path())
+            .env("CLEEN_HEARTBEAT", "off")
+            .env_remove("FRAME_PROFILE")
+            .output()
+            .expect("failed to run cleen frame serve")
+    };
+
+    let output = serve(&["--profile"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert_eq!(fs::read_to_string(&seen).unwrap().trim(), "1");
+    assert!(stdout.contains("Request timings"), "{stdout}");
+    assert!(stdout.contains("GET /hello"), "{stdout}");
+
+    let output = serve(&[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert_eq!(fs::read_to_string(&seen).unwrap().trim(), "");
+    assert!(!stdout.contains("Request timings"), "{stdout}");
+}
+
+#[test]
+fn failed_frame_new_removes_only_the_directory_it_created() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    // Scaffold one file, then fail partway as a full disk would.
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-cli"),
+        "mkdir -p \"$2/app/api\" && echo 'start()' > \"$2/app/api/main.cln\"\n\
+         echo 'write failed: No space left on device' >&2\n\
+         exit 1",
+    );
+    let workspace = TempDir::new().unwrap();
+    let frame_new = |name: &str| {
+        Command::new(env!("CARGO_BIN_EXE_cleen"))
+            .args(["frame", "new", name])
+            .current_dir(workspace.path())
+            .env("HOME", home.path())
+            .env("USERPROFILE", home.path())
+            .env("CLEEN_HEARTBEAT", "off")
+            .output()
+            .expect("failed to run cleen frame new")
+    };
+
+    let output = frame_new("myapp");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("Removed the partially created project"),
+        "{stdout}"
+    );
+    assert!(!workspace.path().join("myapp").exists());
+
+    // A directory that was already there is left alone.
+    let existing = workspace.path().join("existing");
+    fs::create_dir(&existing).unwrap();
+    fs::write(existing.join("notes.txt"), "keep me").unwrap();
+    let output = frame_new("existing");
+    assert!(!output.status.success());
+    assert_eq!(
+        fs::read_to_string(existing.join("notes.txt")).unwrap(),
+        "keep me"
+    );
+    assert!(existing.join("app/api/main.cln").exists());
+}
+
+#[test]
+fn clean_build_accepts_output_relative_to_the_working_directory() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-cli"),
+        "mkdir -p \"$4\" && touch \"$4/app.wasm\"",
+    );
+    let workspace = TempDir::new().unwrap();
+    let build = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_cleen"))
+            .args(["frame", "build"])
+            .args(args)
+            .current_dir(workspace.path())
+            .env("HOME", home.path())
+            .env("USERPROFILE", home.path())
+            .env("CLEEN_HEARTBEAT", "off")
+            .output()
+            .expect("failed to run cleen frame build")
+    };
+    let stale = workspace.path().join("dist").join("stale.wasm");
+
+    // A nested entry file, with the default `dist` next to the project root.
+    fs::create_dir_all(workspace.path().join("app/server")).unwrap();
+    fs::write(workspace.path().join("app/server/main.cln"), "start()\n").unwrap();
+    fs::create_dir_all(stale.parent().unwrap()).unwrap();
+    fs::write(&stale, "old").unwrap();
+    let output = build(&["app/server/main.cln", "--clean"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!stale.exists());
+    assert!(workspace.path().join("dist/app.wasm").exists());
+
+    // A project directory as input, still building into `./dist`.
+    let myapp = workspace.path().join("myapp");
+    fs::create_dir_all(&myapp).unwrap();
+    fs::write(myapp.join("frame.toml"), "").unwrap();
+    fs::write(&stale, "old").unwrap();
+    let output = build(&["myapp", "--clean"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!stale.exists());
+
+    // The project itself, or a directory holding it, is never cleaned.
+    for target in ["myapp", "."] {
+        let output = build(&["myapp", "--output", target, "--clean"]);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success());
+        assert!(stderr.contains("Refusing to clean"), "{stderr}");
+    }
+    assert!(myapp.join("frame.toml").exists());
+
+    // Nor is a directory beside the project that isn't build output, as
+    // in `cd ~ && cleen frame build myapp --output Documents --clean`.
+    let documents = workspace.path().join("Documents");
+    fs::create_dir_all(&documents).unwrap();
+    fs::write(documents.join("notes.txt"), "keep me").unwrap();
+    let output = build(&["myapp", "--output", "Documents", "--clean"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("Refusing to clean"), "{stderr}");
+    assert!(documents.join("notes.txt").exists());
+
+    // Inside the project, any directory may be cleaned.
+    let notes = myapp.join("out").join("notes.txt");
+    fs::create_dir_all(notes.parent().unwrap()).unwrap();
+    fs::write(&notes, "stale").unwrap();
+    let output = build(&["myapp", "--output", "myapp/out", "--clean"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!notes.exists());
+}
+
+fn wait_for(what: &str, mut done: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(15);
+    while !done() {
+        assert!(Instant::now() < deadline, "timed out waiting for {what}");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn serve_watch_recompiles_on_change_and_stops_with_frame_stop() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    let log = home.path().join("compiles.log");
+    write_script(
+        &config.get_version_binary("3.0.0"),
+        &format!("echo compiled >> {}", log.display()),
+    );
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-runtime"),
+        "exec sleep 30",
+    );
+    // The entry sits below the project root, which holds `frame.toml`.
+    let project = TempDir::new().unwrap();
+    fs::write(project.path().join("frame.toml"), "").unwrap();
+    let entry = project.path().join("app").join("api").join("main.cln");
+    fs::create_dir_all(entry.parent().unwrap()).unwrap();
+    fs::write(&entry, "start()\n").unwrap();
+    let page = project.path().join("app").join("ui").join("index.cln");
+    fs::create_dir_all(page.parent().unwrap()).unwrap();
+    let pid_file = home.path().join("cleen-frame-server.pid");
+
+    let cleen = |args: &[&str]| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_cleen"));
+        command
+            .args(["frame"])
+            .args(args)
+            .env("HOME", home.path())
+            .env("USERPROFILE", home.path())
+            .env("TMPDIR", home.path())
+            .env("CLEEN_HEARTBEAT", "off")
+            .stdout(Stdio::null());
+        command
+    };
+    let compiles = || fs::read_to_string(&log).map_or(0, |l| l.lines().count());
+    let pid = || fs::read_to_string(&pid_file).ok();
+
+    let mut serve = cleen(&["serve", "--watch", "--compiler", "3.0.0"])
+        .arg(&entry)
+        .spawn()
+        .unwrap();
+
+    wait_for("first compile", || compiles() == 1 && pid().is_some());
+    let first_pid = pid();
+
+    fs::write(&entry, "start()\n\tprint(\"changed\")\n").unwrap();
+    wait_for("rebuild", || {
+        compiles() == 2 && pid().is_some() && pid() != first_pid
+    });
+
+    // A file outside the entry's directory but inside the project counts.
+    fs::write(&page, "page()\n").unwrap();
+    wait_for("rebuild after a sibling change", || {
+        compiles() == 3 && pid().is_some()
+    });
+
+    assert!(cleen(&["stop"]).status().unwrap().success());
+    wait_for("serve to exit", || serve.try_wait().unwrap().is_some());
+    assert!(serve.wait().unwrap().success());
+    assert!(!pid_file.exists());
+    assert_eq!(compiles(), 3);
+}
+
+#[test]
+fn serve_watch_restart_stops_what_the_runtime_spawned() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    let helpers = home.path().join("helpers.log");
+    write_script(&config.get_version_binary("3.0.0"), "exit 0");
+    // The runtime starts a helper of its own and records its PID.
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-runtime"),
+        &format!(
+            "sleep 30 &
+echo $! >> {}
+wait",
+            helpers.display()
+        ),
+    );
+    let project = TempDir::new().unwrap();
+    let entry = project.path().join("main.cln");
+    fs::write(&entry, "start()\n").unwrap();
+
+    let cleen = |args: &[&str]| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_cleen"));
+        command
+            .args(["frame"])
+            .args(args)
+            .env("HOME", home.path())
+            .env("USERPROFILE", home.path())
+            .env("TMPDIR", home.path())
+            .env("CLEEN_HEARTBEAT", "off")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        command
+    };
+    let helper_pids = || -> Vec<String> {
+        fs::read_to_string(&helpers)
+            .map(|l| l.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+    };
+    let alive = |pid: &str| {
+        Command::new("kill")
+            .args(["-0", pid])
+            .stderr(Stdio::null())
+            .status()
+            .unwrap()
+            .success()
+    };
+
+    let mut serve = cleen(&["serve", "--watch", "--compiler", "3.0.0"])
+        .arg(&entry)
+        .spawn()
+        .unwrap();
+    wait_for("first runtime", || helper_pids().len() == 1);
+
+    fs::write(&entry, "start()\n\tprint(\"changed\")\n").unwrap();
+    wait_for("restarted runtime", || helper_pids().len() == 2);
+    let first = helper_pids()[0].clone();
+    wait_for("the first runtime's helper to exit", || !alive(&first));
+
+    assert!(cleen(&["stop"]).status().unwrap().success());
+    wait_for("serve to exit", || serve.try_wait().unwrap().is_some());
+    let second = helper_pids()[1].clone();
+    wait_for("the second runtime's helper to exit", || !alive(&second));
+}
+
+#[test]
+fn serve_passes_a_hangup_on_to_the_runtime() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-runtime"),
+        "exec sleep 30",
+    );
+    let project = TempDir::new().unwrap();
+    let entry = project.path().join("main.cln");
+    fs::write(&entry, "start()\n").unwrap();
+    let pid_file = home.path().join("cleen-frame-server.pid");
+
+    let mut serve = Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(["frame", "serve"])
+        .arg(&entry)
+        .env("HOME", home.path())
+        .env("USERPROFILE", home.path())
+        .env("TMPDIR", home.path())
+        .env("CLEEN_HEARTBEAT", "off")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    wait_for("the runtime to start", || pid_file.exists());
+    let runtime = fs::read_to_string(&pid_file).unwrap();
+
+    // What the terminal sends when its window closes.
+    let hangup = Command::new("kill")
+        .args(["-HUP", &serve.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(hangup.success());
+    wait_for("serve to exit", || serve.try_wait().unwrap().is_some());
+    let alive = || {
+        Command::new("kill")
+            .args(["-0", runtime.trim()])
+            .stderr(Stdio::null())
+            .status()
+            .unwrap()
+            .success()
+    };
+    wait_for("the runtime to exit", || !alive());
+}
+
+#[test]
+fn serve_detach_logs_to_file_shown_by_frame_logs() {
+    let home = TempDir::new().unwrap();
+    let config = setup(home.path());
+    write_script(
+        &config
+            .get_frame_versions_dir()
+            .join("1.0.0")
+            .join("frame-runtime"),
+        "echo \"listening on $FRAME_PORT\"\necho 'request failed' >&2",
+    );
+    let project = TempDir::new().unwrap();
+    let entry = project.path().join("main.cln");
+    fs::write(&entry, "start()\n").unwrap();
+    let log = config.get_logs_dir().join("frame-server.log");
+
+    let cleen = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_cleen"))
+            .args(["frame"])
+            .args(args)
+            .env("HOME", home.path())
+            .env("USERPROFILE", home.path())
+            .env("TMPDIR", home.path())
+            .env("CLEEN_HEARTBEAT", "off")
+            .output()
+            .expect("failed to run cleen frame")
+    };
+
+    let output = cleen(&["logs"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("No Frame server log"), "{stdout}");
+
+    let output = cleen(&["serve", entry.to_str().unwrap(), "-p", "4321", "--detach"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("running in the background"), "{stdout}");
+    assert!(home.path().join("cleen-frame-server.pid").exists());
+
+    wait_for("runtime output", || {
+        fs::read_to_string(&log).is_ok_and(|l| l.contains("request failed"))
+    });
+    let output = cleen(&["logs"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "listening on 4321\nrequest failed\n"
+    );
+    let output = cleen(&["logs", "-n", "1"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "request failed\n");
+}
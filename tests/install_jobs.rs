@@ -0,0 +1,67 @@
+//! `cleen install` with several versions at once. The archives are kept in
+//! `~/.cleen/cache/downloads`, so nothing here reaches the network; the
+//! stand-in compilers are shell scripts, so this only runs on Unix.
+#![cfg(unix)]
+
+use flate2::{write::GzEncoder, Compression};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Keep a release archive for `version` holding a `cln` script.
+fn keep_archive(home: &Path, version: &str) {
+    let dir = home.join(".cleen/cache/downloads").join(version);
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = std::fs::File::create(dir.join("cln-linux-x86_64.tar.gz")).unwrap();
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let body = format!("#!/bin/sh\necho cln {version}\n");
+    let mut header = tar::Header::new_gnu();
+    header.set_size(body.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "cln", body.as_bytes())
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+}
+
+#[test]
+fn parallel_installs_prefix_every_progress_line_with_the_version() {
+    let home = TempDir::new().unwrap();
+    keep_archive(home.path(), "1.0.0");
+    keep_archive(home.path(), "2.0.0");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(["install", "1.0.0", "2.0.0", "--jobs", "2"])
+        .env("HOME", home.path())
+        .env("USERPROFILE", home.path())
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen install");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Everything between the header and the summary comes from a worker,
+    // including the extractor's own status lines.
+    let progress: Vec<&str> = stdout
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .collect();
+    assert!(
+        progress.iter().any(|l| l.starts_with("[2.0.0] Extracting")),
+        "{stdout}"
+    );
+    for line in progress {
+        assert!(
+            line.starts_with("[1.0.0] ") || line.starts_with("[2.0.0] "),
+            "unprefixed progress line {line:?} in:\n{stdout}"
+        );
+    }
+    assert!(home.path().join(".cleen/versions/1.0.0/cln").exists());
+    assert!(home.path().join(".cleen/versions/2.0.0/cln").exists());
+}
@@ -0,0 +1,107 @@
+//! `cleen list --json` must print nothing but a JSON array so scripts can
+//! pipe it straight into `jq`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn cleen(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(args)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen")
+}
+
+fn write_config(cleen_dir: &Path, config: serde_json::Value) {
+    let mut config = config;
+    config["cleen_dir"] = serde_json::json!(cleen_dir);
+    config["auto_cleanup"] = serde_json::json!(false);
+    config["check_updates"] = serde_json::json!(false);
+    std::fs::write(cleen_dir.join("config.json"), config.to_string()).unwrap();
+}
+
+fn list_json(home: &Path, args: &[&str]) -> serde_json::Value {
+    let output = cleen(home, args);
+    assert!(output.status.success(), "{args:?} failed: {output:?}");
+    serde_json::from_slice(&output.stdout).expect("stdout should be only JSON")
+}
+
+#[test]
+fn list_json_reports_compiler_versions() {
+    let home = TempDir::new().unwrap();
+    let cleen_dir = home.path().join(".cleen");
+    let versions = cleen_dir.join("versions");
+    std::fs::create_dir_all(versions.join("0.29.1")).unwrap();
+    let binary = versions.join("0.30.0").join("cln");
+    std::fs::create_dir_all(binary.parent().unwrap()).unwrap();
+    std::fs::write(&binary, "#!/bin/sh\n").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    write_config(
+        &cleen_dir,
+        serde_json::json!({ "active_version": "0.30.0" }),
+    );
+
+    let json = list_json(home.path(), &["list", "--json"]);
+
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {
+                "version": "0.29.1",
+                "active": false,
+                "valid": false,
+                "binary_path": versions.join("0.29.1").join("cln"),
+            },
+            {
+                "version": "0.30.0",
+                "active": true,
+                "valid": true,
+                "binary_path": binary,
+            },
+        ])
+    );
+}
+
+#[test]
+fn list_frame_json_marks_active_frame_version() {
+    let home = TempDir::new().unwrap();
+    let cleen_dir = home.path().join(".cleen");
+    let frame_dir = cleen_dir.join("versions").join("frame");
+    std::fs::create_dir_all(frame_dir.join("1.0.0")).unwrap();
+    std::fs::create_dir_all(frame_dir.join("1.1.0")).unwrap();
+    write_config(&cleen_dir, serde_json::json!({ "frame_version": "1.1.0" }));
+
+    let json = list_json(home.path(), &["list", "--frame", "--json"]);
+
+    assert_eq!(
+        json,
+        serde_json::json!([
+            { "version": "1.0.0", "active": false },
+            { "version": "1.1.0", "active": true },
+        ])
+    );
+
+    let empty = TempDir::new().unwrap();
+    assert_eq!(
+        list_json(empty.path(), &["list", "--json"]),
+        serde_json::json!([])
+    );
+}
+
+#[test]
+fn list_json_stays_parseable_with_timings() {
+    let home = TempDir::new().unwrap();
+    let versions = home.path().join(".cleen").join("versions");
+    std::fs::create_dir_all(versions.join("0.30.0")).unwrap();
+
+    let json = list_json(home.path(), &["list", "--json", "--timings"]);
+
+    assert_eq!(json[0]["version"], "0.30.0");
+}
@@ -0,0 +1,138 @@
+//! End-to-end tests for `cleen plugin` subcommands, run against the built
+//! binary with a throwaway `HOME`.
+//!
+//! Plugin names and versions are joined onto `~/.cleen/plugins`, so every
+//! subcommand that takes one must reject traversal attempts before
+//! touching the filesystem.
+
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Run the `cleen` binary against a throwaway `HOME`.
+fn cleen(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(args)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen")
+}
+
+fn assert_rejected(home: &Path, args: &[&str], expected: &str) {
+    let output = cleen(home, args);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{args:?} should fail");
+    assert!(
+        stderr.contains(expected),
+        "{args:?} should report {expected:?}, got: {stderr}"
+    );
+}
+
+#[test]
+fn plugin_install_rejects_traversal() {
+    let home = TempDir::new().unwrap();
+    assert_rejected(
+        home.path(),
+        &["plugin", "install", "../../evil"],
+        "Invalid plugin name",
+    );
+    assert_rejected(
+        home.path(),
+        &["plugin", "install", "frame/web@1.0.0"],
+        "Invalid plugin name",
+    );
+    assert_rejected(
+        home.path(),
+        &["plugin", "install", "frame.web@../../evil"],
+        "Invalid plugin specifier",
+    );
+}
+
+#[test]
+fn plugin_use_rejects_traversal() {
+    let home = TempDir::new().unwrap();
+    assert_rejected(
+        home.path(),
+        &["plugin", "use", "../../evil", "1.0.0"],
+        "Invalid plugin name",
+    );
+    assert_rejected(
+        home.path(),
+        &["plugin", "use", "frame.web", "../.."],
+        "Invalid version format",
+    );
+}
+
+#[test]
+fn plugin_remove_rejects_traversal_and_leaves_siblings() {
+    let home = TempDir::new().unwrap();
+    let versions = home.path().join(".cleen").join("versions");
+    std::fs::create_dir_all(&versions).unwrap();
+
+    assert_rejected(
+        home.path(),
+        &["plugin", "remove", "../versions"],
+        "Invalid plugin name",
+    );
+    assert_rejected(
+        home.path(),
+        &["plugin", "remove", ".."],
+        "Invalid plugin name",
+    );
+    assert!(versions.exists());
+}
+
+/// Lay out a buildable-looking local plugin directory.
+fn local_plugin(root: &Path, name: &str, version: &str) -> std::path::PathBuf {
+    let dir = root.join(format!("src-{name}"));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("plugin.toml"),
+        format!("[plugin]\nname = \"{name}\"\nversion = \"{version}\"\n"),
+    )
+    .unwrap();
+    std::fs::write(dir.join("plugin.wasm"), b"\0asm").unwrap();
+    dir
+}
+
+#[test]
+fn plugin_install_multiple_continues_past_failures() {
+    let home = TempDir::new().unwrap();
+    let sources = TempDir::new().unwrap();
+    let web = local_plugin(sources.path(), "frame.web", "1.0.0");
+    let ui = local_plugin(sources.path(), "frame.ui", "2.0.0");
+    let missing = sources.path().join("does-not-exist");
+
+    let output = cleen(
+        home.path(),
+        &[
+            "plugin",
+            "install",
+            "--local",
+            web.to_str().unwrap(),
+            missing.to_str().unwrap(),
+            ui.to_str().unwrap(),
+        ],
+    );
+
+    assert!(
+        !output.status.success(),
+        "one failure must fail the command"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Plugin install summary:"), "{stdout}");
+
+    let plugins = home.path().join(".cleen").join("plugins");
+    assert!(plugins
+        .join("frame.web")
+        .join("1.0.0")
+        .join("plugin.wasm")
+        .exists());
+    assert!(plugins
+        .join("frame.ui")
+        .join("2.0.0")
+        .join("plugin.wasm")
+        .exists());
+}
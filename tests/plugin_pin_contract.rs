@@ -32,6 +32,7 @@ fn test_config(cleen_dir: &Path) -> Config {
         auto_offer_frame: false,
         last_update_check: None,
         last_self_update_check: None,
+        ..Config::default()
     }
 }
 
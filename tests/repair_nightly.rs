@@ -0,0 +1,38 @@
+//! `cleen repair` on nightly builds, which are stored by commit and have no
+//! release tag to re-download from.
+
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn cleen(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(args)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen")
+}
+
+#[test]
+fn repair_points_nightly_builds_at_a_reinstall() {
+    let home = TempDir::new().unwrap();
+    // A broken nightly: the directory is there, the binary isn't.
+    let nightly = home.path().join(".cleen/versions/nightly-abc1234");
+    std::fs::create_dir_all(&nightly).unwrap();
+
+    let output = cleen(home.path(), &["repair", "nightly-abc1234"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("cleen install sha:abc1234 --force"),
+        "got: {stderr}"
+    );
+
+    let output = cleen(home.path(), &["repair", "--all"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Skipping nightly-abc1234"), "{stdout}");
+    assert!(nightly.exists());
+}
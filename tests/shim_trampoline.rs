@@ -0,0 +1,126 @@
+//! End-to-end checks for the `cln` trampoline: `cleen use` points the shim
+//! back at `cleen`, which picks the compiler per working directory. The
+//! "compilers" here are shell scripts that echo their own version, so the
+//! tests only run on Unix.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn cleen(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(args)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen")
+}
+
+fn cln(home: &Path, cwd: &Path, args: &[&str]) -> Output {
+    Command::new(home.join(".cleen").join("bin").join("cln"))
+        .args(args)
+        .current_dir(cwd)
+        .env("HOME", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cln shim")
+}
+
+/// Install a stand-in compiler that prints `<version> <args...>`.
+fn fake_compiler(home: &Path, version: &str) -> PathBuf {
+    let dir = home.join(".cleen").join("versions").join(version);
+    fs::create_dir_all(&dir).unwrap();
+    let binary = dir.join("cln");
+    fs::write(&binary, format!("#!/bin/sh\necho {version} \"$@\"\n")).unwrap();
+    fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+    binary
+}
+
+fn pin(project: &Path, version: &str) {
+    fs::create_dir_all(project.join(".cleanlanguage")).unwrap();
+    fs::write(
+        project.join(".cleanlanguage").join(".cleanversion"),
+        format!("{version}\n"),
+    )
+    .unwrap();
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "shim failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn trampoline_resolves_version_per_directory() {
+    let home = TempDir::new().unwrap();
+    fake_compiler(home.path(), "1.0.0");
+    fake_compiler(home.path(), "2.0.0");
+    assert!(cleen(home.path(), &["use", "1.0.0"]).status.success());
+
+    let shim = home.path().join(".cleen").join("bin").join("cln");
+    let target = fs::read_link(&shim).unwrap();
+    assert_eq!(target.file_stem().unwrap(), "cleen");
+
+    let outside = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    pin(project.path(), "2.0.0");
+    let nested = project.path().join("app").join("pages");
+    fs::create_dir_all(&nested).unwrap();
+
+    assert_eq!(
+        stdout(&cln(home.path(), outside.path(), &["--version"])),
+        "1.0.0 --version"
+    );
+    assert_eq!(
+        stdout(&cln(home.path(), project.path(), &["build", "x.cln"])),
+        "2.0.0 build x.cln"
+    );
+    assert_eq!(
+        stdout(&cln(home.path(), &nested, &["--version"])),
+        "2.0.0 --version"
+    );
+}
+
+#[test]
+fn trampoline_reports_pinned_version_that_is_not_installed() {
+    let home = TempDir::new().unwrap();
+    fake_compiler(home.path(), "1.0.0");
+    assert!(cleen(home.path(), &["use", "1.0.0"]).status.success());
+
+    let project = TempDir::new().unwrap();
+    pin(project.path(), "9.9.9");
+
+    let output = cln(home.path(), project.path(), &["--version"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("9.9.9"), "unexpected stderr: {stderr}");
+}
+
+#[test]
+fn doctor_reports_shim_pointing_at_another_version() {
+    let home = TempDir::new().unwrap();
+    let stale = fake_compiler(home.path(), "1.0.0");
+    fake_compiler(home.path(), "2.0.0");
+    assert!(cleen(home.path(), &["use", "2.0.0"]).status.success());
+
+    // Simulate a hand-edited shim left over from before the last `use`.
+    let shim = home.path().join(".cleen").join("bin").join("cln");
+    fs::remove_file(&shim).unwrap();
+    std::os::unix::fs::symlink(&stale, &shim).unwrap();
+
+    let output = cleen(home.path(), &["doctor"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Shim runs 1.0.0 but the active version in config is 2.0.0"),
+        "unexpected doctor output: {stdout}"
+    );
+    assert!(stdout.contains("cleen use 2.0.0"), "{stdout}");
+}
@@ -0,0 +1,84 @@
+//! `cleen uninstall --all` removes every compiler version, keeping the
+//! active one unless `--force` is given and leaving Frame CLI versions
+//! alone. Output is piped, so no confirmation prompt is shown.
+
+use cleen::core::config::Config;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn cleen(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(args)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen")
+}
+
+fn load_config(home: &Path) -> Config {
+    let content = fs::read_to_string(home.join(".cleen").join("config.json")).unwrap();
+    serde_json::from_str(&content).unwrap()
+}
+
+#[test]
+fn uninstall_all_keeps_active_version_unless_forced() {
+    let home = TempDir::new().unwrap();
+    let config = Config {
+        active_version: Some("0.2.0".to_string()),
+        cleen_dir: home.path().join(".cleen"),
+        ..Config::default()
+    };
+    for version in ["0.1.0", "0.2.0", "0.3.0"] {
+        let binary = config.get_version_binary(version);
+        fs::create_dir_all(binary.parent().unwrap()).unwrap();
+        fs::write(&binary, "x".repeat(1024)).unwrap();
+    }
+    let frame_dir = config.get_frame_versions_dir().join("1.0.0");
+    fs::create_dir_all(&frame_dir).unwrap();
+    fs::write(
+        config.cleen_dir.join("config.json"),
+        serde_json::to_string(&config).unwrap(),
+    )
+    .unwrap();
+
+    let output = cleen(home.path(), &["uninstall", "--all"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Skipping active version 0.2.0"), "{stdout}");
+    assert!(stdout.contains("Removed 2 version(s), freed"), "{stdout}");
+    assert!(!config.get_version_dir("0.1.0").exists());
+    assert!(config.get_version_dir("0.2.0").exists());
+    assert!(!config.get_version_dir("0.3.0").exists());
+    assert_eq!(
+        load_config(home.path()).active_version.as_deref(),
+        Some("0.2.0")
+    );
+
+    let output = cleen(home.path(), &["uninstall", "--all", "--force", "--yes"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Removed 1 version(s)"), "{stdout}");
+    assert!(!config.get_version_dir("0.2.0").exists());
+    assert!(frame_dir.exists());
+    assert_eq!(load_config(home.path()).active_version, None);
+
+    let output = cleen(home.path(), &["uninstall", "--all"]);
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("No Clean Language versions installed")
+    );
+}
+
+#[test]
+fn uninstall_requires_a_version_or_all() {
+    let home = TempDir::new().unwrap();
+    assert!(!cleen(home.path(), &["uninstall"]).status.success());
+    assert!(!cleen(home.path(), &["uninstall", "0.1.0", "--all"])
+        .status
+        .success());
+    assert!(!cleen(home.path(), &["uninstall", "0.1.0", "--yes"])
+        .status
+        .success());
+}
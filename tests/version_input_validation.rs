@@ -0,0 +1,59 @@
+//! End-to-end checks that path-like version strings are rejected by every
+//! command that joins a version onto `~/.cleen/versions`, before anything
+//! on disk is touched.
+
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Run the `cleen` binary with `HOME` pointed at a throwaway directory so
+/// the user's real install is never read or modified.
+fn cleen(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .args(args)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen")
+}
+
+fn assert_rejected(home: &Path, args: &[&str]) {
+    let output = cleen(home, args);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{args:?} should fail");
+    assert!(
+        stderr.contains("Invalid version format"),
+        "{args:?} should report an invalid version, got: {stderr}"
+    );
+}
+
+#[test]
+fn install_rejects_path_traversal() {
+    let home = TempDir::new().unwrap();
+    assert_rejected(home.path(), &["install", "../../etc"]);
+    assert_rejected(home.path(), &["install", "/etc/passwd"]);
+    assert_rejected(home.path(), &["install", "..\\..\\Windows"]);
+    // Rejected before Config::load, so not even ~/.cleen was created.
+    assert!(!home.path().join(".cleen").exists());
+}
+
+#[test]
+fn use_local_uninstall_and_repair_reject_path_traversal() {
+    let home = TempDir::new().unwrap();
+    assert_rejected(home.path(), &["use", "../../etc"]);
+    assert_rejected(home.path(), &["local", "../.."]);
+    assert_rejected(home.path(), &["uninstall", "."]);
+    assert_rejected(home.path(), &["uninstall", "../versions", "--force"]);
+    assert_rejected(home.path(), &["repair", "../../etc"]);
+}
+
+#[test]
+fn uninstall_dot_does_not_remove_versions_dir() {
+    let home = TempDir::new().unwrap();
+    let versions = home.path().join(".cleen").join("versions");
+    std::fs::create_dir_all(versions.join("0.30.0")).unwrap();
+
+    assert_rejected(home.path(), &["uninstall", ".", "--force"]);
+    assert!(versions.join("0.30.0").exists());
+}
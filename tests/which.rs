@@ -0,0 +1,99 @@
+//! `cleen which` prints only the path of the binary that would run, and
+//! fails with a message on stderr when nothing resolves.
+
+use cleen::core::config::Config;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn which(home: &Path, cwd: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cleen"))
+        .arg("which")
+        .args(args)
+        .current_dir(cwd)
+        .env("HOME", home)
+        .env("USERPROFILE", home)
+        .env("CLEEN_HEARTBEAT", "off")
+        .output()
+        .expect("failed to run cleen which")
+}
+
+fn write_config(config: &Config) {
+    fs::create_dir_all(&config.cleen_dir).unwrap();
+    fs::write(
+        config.cleen_dir.join("config.json"),
+        serde_json::to_string(config).unwrap(),
+    )
+    .unwrap();
+}
+
+fn printed_path(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn canonical(path: &Path) -> String {
+    format!("{}\n", path.canonicalize().unwrap().display())
+}
+
+#[test]
+fn which_follows_project_pin_then_active_version() {
+    let home = TempDir::new().unwrap();
+    let mut config = Config {
+        active_version: Some("0.1.0".to_string()),
+        frame_version: Some("1.0.0".to_string()),
+        cleen_dir: home.path().join(".cleen"),
+        ..Config::default()
+    };
+    for binary in [
+        config.get_version_binary("0.1.0"),
+        config.get_version_binary("0.2.0"),
+        config.get_frame_version_binary("1.0.0"),
+    ] {
+        fs::create_dir_all(binary.parent().unwrap()).unwrap();
+        fs::write(&binary, "").unwrap();
+    }
+    write_config(&config);
+
+    let project = TempDir::new().unwrap();
+    let nested = project.path().join("src");
+    fs::create_dir_all(project.path().join(".cleanlanguage")).unwrap();
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(
+        project.path().join(".cleanlanguage").join(".cleanversion"),
+        "0.2.0\n",
+    )
+    .unwrap();
+    let elsewhere = TempDir::new().unwrap();
+
+    assert_eq!(
+        printed_path(&which(home.path(), &nested, &[])),
+        canonical(&config.get_version_binary("0.2.0"))
+    );
+    assert_eq!(
+        printed_path(&which(home.path(), elsewhere.path(), &[])),
+        canonical(&config.get_version_binary("0.1.0"))
+    );
+    assert_eq!(
+        printed_path(&which(home.path(), elsewhere.path(), &["--frame"])),
+        canonical(&config.get_frame_version_binary("1.0.0"))
+    );
+
+    config.active_version = None;
+    config.frame_version = None;
+    write_config(&config);
+
+    let output = which(home.path(), elsewhere.path(), &[]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No version is currently active"));
+
+    let output = which(home.path(), elsewhere.path(), &["--frame"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No Frame CLI version is active"));
+}
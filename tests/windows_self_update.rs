@@ -0,0 +1,91 @@
+//! Windows-only integration tests for the deferred self-update swap.
+//!
+//! The running `cleen.exe` is locked by the OS, so `cleen self-update`
+//! stages the new binary at `cleen.exe.new` and hands the swap to a batch
+//! script that waits for the parent process to exit. These tests drive
+//! the generated script against throwaway files rather than a real
+//! `cleen.exe` so they never touch the user's installation.
+#![cfg(windows)]
+
+use cleen::commands::update::{windows_staged_path, windows_swap_script};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// PID of a process that has already exited, so the script's wait loop
+/// falls straight through.
+fn exited_pid() -> u32 {
+    let mut child = Command::new("cmd").args(["/C", "exit 0"]).spawn().unwrap();
+    let pid = child.id();
+    child.wait().unwrap();
+    pid
+}
+
+/// A small system executable standing in for `cleen.exe`. It has to be a
+/// real program because the script relaunches the target with
+/// `--version`; `whoami.exe` rejects the flag and exits immediately
+/// instead of waiting on stdin.
+fn stand_in_exe() -> std::path::PathBuf {
+    let root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".into());
+    std::path::Path::new(&root)
+        .join("System32")
+        .join("whoami.exe")
+}
+
+#[test]
+fn swap_script_replaces_target_and_removes_old() {
+    let tmp = TempDir::new().unwrap();
+    let stand_in = stand_in_exe();
+    let target = tmp.path().join("cleen.exe");
+    fs::copy(&stand_in, &target).unwrap();
+
+    let staged = windows_staged_path(&target);
+    fs::copy(&stand_in, &staged).unwrap();
+    let staged_len = fs::metadata(&staged).unwrap().len();
+
+    let script_path = tmp.path().join("swap.bat");
+    fs::write(
+        &script_path,
+        windows_swap_script(&target, &staged, exited_pid()),
+    )
+    .unwrap();
+
+    Command::new("cmd")
+        .arg("/C")
+        .arg(&script_path)
+        .status()
+        .unwrap();
+
+    assert!(target.exists(), "target must be in place after the swap");
+    assert_eq!(fs::metadata(&target).unwrap().len(), staged_len);
+    assert!(!staged.exists(), "staged binary must have been moved");
+    assert!(
+        !tmp.path().join("cleen.exe.old").exists(),
+        ".old must be cleaned up"
+    );
+}
+
+#[test]
+fn swap_script_removes_leftover_old_from_previous_updater() {
+    let tmp = TempDir::new().unwrap();
+    let stand_in = stand_in_exe();
+    let target = tmp.path().join("cleen.exe");
+    fs::copy(&stand_in, &target).unwrap();
+    let staged = windows_staged_path(&target);
+    fs::copy(&stand_in, &staged).unwrap();
+    fs::write(tmp.path().join("cleen.exe.old"), b"stale").unwrap();
+
+    let script_path = tmp.path().join("swap.bat");
+    fs::write(
+        &script_path,
+        windows_swap_script(&target, &staged, exited_pid()),
+    )
+    .unwrap();
+    Command::new("cmd")
+        .arg("/C")
+        .arg(&script_path)
+        .status()
+        .unwrap();
+
+    assert!(!tmp.path().join("cleen.exe.old").exists());
+}